@@ -1,9 +1,10 @@
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use tauri::State;
 
 use crate::error::TessellumError;
-use crate::models::{AppState, AssetIndex};
-use crate::utils::{normalize_path, sanitize_string, validate_path_in_vault};
+use crate::models::AppState;
+use crate::utils::config::load_or_init_config;
+use crate::utils::{normalize_path, sanitize_string, validate_path_in_vault, SanitizePolicy};
 
 const SUPPORTED_EXTS: &[&str] = &[
 	"png", "jpg", "jpeg", "gif", "webp", "svg", "bmp", "tif", "tiff", "avif", "pdf",
@@ -63,14 +64,7 @@ async fn resolve_asset_inner(
 		return Ok(Some(to_asset_path(&resolved)));
 	}
 	
-	let mut index_guard = state.asset_index.lock().await;
-	if index_guard.is_none() {
-		let idx = AssetIndex::build(vault_path)
-			.map_err(|e| TessellumError::Internal(format!("Failed to build asset index: {}", e)))?;
-		*index_guard = Some(idx);
-	}
-	
-	let asset_index = index_guard.as_ref().unwrap();
+	let asset_index = state.current_asset_index(vault_path).await?;
 	Ok(asset_index
 		.resolve(vault_path, target)
 		.map(|p| to_asset_path(&p)))
@@ -92,7 +86,7 @@ async fn save_asset_inner(
 		return Err(TessellumError::Validation("Unsupported file type".to_string()));
 	}
 	
-	let clean_base = sanitize_string(base_name.to_string());
+	let clean_base = sanitize_string(base_name.to_string(), &SanitizePolicy::default());
 	let base = if clean_base.trim().is_empty() {
 		"Pasted file".to_string()
 	} else {
@@ -137,6 +131,39 @@ async fn save_asset_inner(
 	Ok(normalize_path(&relative.to_string_lossy()))
 }
 
+/// Save into the configured attachments folder, named after `original_name`
+/// with the same sanitizing and collision suffixes as `save_asset_inner`.
+async fn save_attachment_inner(
+	state: &AppState,
+	vault_path: &str,
+	bytes: Vec<u8>,
+	original_name: &str,
+) -> Result<String, TessellumError> {
+	let original = Path::new(original_name.trim());
+	let ext = original
+		.extension()
+		.map(|s| s.to_string_lossy().to_string())
+		.unwrap_or_default();
+	let stem = original
+		.file_stem()
+		.map(|s| s.to_string_lossy().to_string())
+		.unwrap_or_default();
+	
+	let vault_root = validate_path_in_vault(vault_path, vault_path)
+		.map_err(TessellumError::Validation)?;
+	let config = load_or_init_config(vault_path)?;
+	let folder = config.attachments.folder.trim().to_string();
+	if !Path::new(&folder).components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir)) {
+		return Err(TessellumError::Validation(
+			"Attachments folder must be relative to the vault".to_string(),
+		));
+	}
+	// `save_asset_inner` only accepts folders that already exist.
+	tokio::fs::create_dir_all(vault_root.join(&folder)).await?;
+	
+	save_asset_inner(state, vault_path, &folder, &stem, &ext, bytes).await
+}
+
 #[tauri::command]
 pub async fn resolve_asset(
 	state: State<'_, AppState>,
@@ -168,26 +195,28 @@ pub async fn save_asset(
 	.await
 }
 
+/// Save binary data (e.g. a pasted screenshot) into the configured attachments
+/// folder and return its vault-relative path for an `![[...]]` embed.
+#[tauri::command]
+pub async fn save_attachment(
+	state: State<'_, AppState>,
+	vault_path: String,
+	bytes: Vec<u8>,
+	original_name: String,
+) -> Result<String, TessellumError> {
+	save_attachment_inner(state.inner(), &vault_path, bytes, &original_name).await
+}
+
 #[cfg(test)]
 mod tests {
 	use tempfile::tempdir;
 
-	use super::{resolve_asset_inner, save_asset_inner};
-	use crate::db::Database;
-	use crate::models::AppState;
-	use crate::search::SearchIndex;
+	use super::{resolve_asset_inner, save_asset_inner, save_attachment_inner};
+	use crate::test_support::TestAppState;
 
-	async fn build_app_state(vault_path: &str) -> AppState {
-		let db_dir = tempdir().unwrap();
-		let db = Database::init(db_dir.path().join("test.sqlite").to_str().unwrap())
-			.await
-			.unwrap();
-		let search_dir = tempdir().unwrap();
-		let index_path = search_dir.path().join("search-index");
-		let search_index = SearchIndex::open_or_create(&index_path).unwrap();
-		let state = AppState::new(db, search_index);
+	async fn build_app_state(vault_path: &str) -> TestAppState {
 		let _ = vault_path;
-		state
+		TestAppState::new().await
 	}
 
 	#[tokio::test]
@@ -277,4 +306,30 @@ mod tests {
 
 		assert!(err.to_string().contains("Unsupported file type"));
 	}
+
+	#[tokio::test]
+	async fn save_attachment_uses_configured_folder_and_the_save_asset_naming() {
+		let vault = tempdir().unwrap();
+		let vault_path = vault.path().to_str().unwrap();
+		let state = build_app_state(vault_path).await;
+
+		let first = save_attachment_inner(&state, vault_path, vec![1], "Screen shot?.PNG")
+			.await
+			.unwrap();
+		let second = save_attachment_inner(&state, vault_path, vec![2], "Screen shot?.PNG")
+			.await
+			.unwrap();
+
+		assert_eq!(first, "attachments/Screen shot.PNG");
+		assert_eq!(second, "attachments/Screen shot-1.PNG");
+		assert_eq!(std::fs::read(vault.path().join("attachments/Screen shot-1.PNG")).unwrap(), vec![2]);
+
+		std::fs::write(
+			crate::utils::config::config_path(vault_path),
+			r#"{"attachments":{"folder":"../outside"}}"#,
+		)
+		.unwrap();
+		assert!(save_attachment_inner(&state, vault_path, vec![3], "a.png").await.is_err());
+		assert!(!vault.path().join("../outside").exists());
+	}
 }
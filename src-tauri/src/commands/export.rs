@@ -1,10 +1,31 @@
+use crate::commands::indexer::run_sync_vault;
+use crate::commands::links::extract_wikilinks;
+use crate::commands::notes::unique_note_path;
+use crate::commands::publish::escape_html;
+use crate::commands::render::render_markdown_html;
+use crate::commands::vault::{backlink_rewrite_regex, rewrite_links_in_text};
 use crate::error::TessellumError;
+use crate::grafeo_projection::ManagedGrafeoConnection;
+use crate::models::AppState;
+use crate::utils::config::load_or_init_config;
+use crate::utils::{
+    is_hidden_or_special, normalize_path, sanitize_string, validate_path_in_vault, validate_vault_root,
+    IgnoreRules, SanitizePolicy,
+};
+use base64::Engine;
 use docx_rs::{
     AbstractNumbering, Docx, Level, LevelJc, LevelText, NumberFormat, Numbering,
     Paragraph, Run, Start,
 };
 use regex::Regex;
-use std::path::PathBuf;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Component, Path, PathBuf};
+use std::sync::LazyLock;
+use tauri::State;
+use walkdir::WalkDir;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
 
 // ────────────────────────────────────────────────────────────────────────────
 // D7 — DOCX export
@@ -201,3 +222,606 @@ pub async fn import_from_url(
     .await
     .map_err(|e| TessellumError::Internal(format!("Task error: {e}")))?
 }
+
+// ────────────────────────────────────────────────────────────────────────────
+// Folder import
+// ────────────────────────────────────────────────────────────────────────────
+
+static MD_IMAGE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"!\[[^\]]*\]\(<?([^)>\s]+)>?(?:\s+"[^"]*")?\)"#).unwrap());
+
+/// A source file `import_folder` could not bring in.
+#[derive(Serialize, Debug)]
+pub struct ImportFailure {
+    pub path: String,
+    pub message: String,
+}
+
+/// What an `import_folder` run copied into the vault.
+///
+/// `imported` counts notes and images written; `skipped` lists the source
+/// files left behind and why.
+#[derive(Serialize, Debug, Default)]
+pub struct ImportFolderResult {
+    pub imported: usize,
+    pub skipped: Vec<ImportFailure>,
+}
+
+/// Sanitize each component of a relative path, dropping ones left empty.
+fn sanitize_relative(path: &Path, policy: &SanitizePolicy) -> PathBuf {
+    path.iter()
+        .map(|part| sanitize_string(part.to_string_lossy().to_string(), policy))
+        .filter(|part| !part.trim().is_empty())
+        .collect()
+}
+
+/// Source images a note embeds, as `![[...]]` or `![alt](...)`.
+///
+/// Wikilink embeds resolve next to the note, then from the source root, then
+/// by file name anywhere in the source folder, like they would in a vault.
+/// Remote URLs and targets outside `source` are ignored.
+fn referenced_images(
+    content: &str,
+    note_dir: &Path,
+    source: &Path,
+    images_by_name: &HashMap<String, PathBuf>,
+) -> Vec<PathBuf> {
+    let wikilinks = extract_wikilinks(content)
+        .into_iter()
+        .filter(|link| link.is_embed)
+        .filter_map(|link| {
+            [note_dir.join(&link.target), source.join(&link.target)]
+                .into_iter()
+                .find(|path| path.is_file())
+                .or_else(|| images_by_name.get(&link.target.to_lowercase()).cloned())
+        });
+    let markdown = MD_IMAGE_RE
+        .captures_iter(content)
+        .map(|caps| caps[1].to_string())
+        .filter(|src| !src.contains("://"))
+        .map(|src| note_dir.join(src.replace("%20", " ")));
+
+    wikilinks
+        .chain(markdown)
+        .filter_map(|path| path.canonicalize().ok())
+        .filter(|path| path.starts_with(source) && image_mime(path).is_some())
+        .collect()
+}
+
+/// Copy the markdown files under `source` into `dest_subfolder` of the vault.
+///
+/// Folder structure is kept, with folder names sanitized like note names.
+/// Note names are sanitized with the vault's filename policy and get a ` (n)`
+/// suffix on collision; wikilinks between the imported notes are rewritten
+/// to follow those renames. Notes that are not UTF-8 text are skipped. With
+/// `include_images`, images the notes embed are copied to the same relative
+/// location so their links keep working; an image whose destination already
+/// exists is skipped rather than renamed.
+fn import_folder_files(
+    vault_path: &str,
+    source: &Path,
+    dest_subfolder: &str,
+    include_images: bool,
+) -> Result<ImportFolderResult, TessellumError> {
+    let source = source
+        .canonicalize()
+        .ok()
+        .filter(|path| path.is_dir())
+        .ok_or_else(|| TessellumError::NotFound("Source folder does not exist".to_string()))?;
+    let policy = load_or_init_config(vault_path)?.filenames;
+    let dest_subfolder = Path::new(dest_subfolder.trim());
+    if !dest_subfolder.components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir)) {
+        return Err(TessellumError::Validation(
+            "Destination folder must be relative to the vault".to_string(),
+        ));
+    }
+    let dest_root = Path::new(vault_path).join(sanitize_relative(dest_subfolder, &policy));
+    std::fs::create_dir_all(&dest_root)?;
+    let dest_root = validate_path_in_vault(&dest_root.to_string_lossy(), vault_path)
+        .map_err(TessellumError::Validation)?;
+
+    // Collect first so files written under `dest_root` are never re-imported
+    // when the source folder contains it.
+    let mut notes = Vec::new();
+    let mut images_by_name = HashMap::new();
+    let entries = WalkDir::new(&source)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !is_hidden_or_special(e.path().strip_prefix(&source).unwrap_or(e.path())))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file());
+    for entry in entries {
+        let path = entry.into_path();
+        if path.extension().and_then(|ext| ext.to_str()) == Some("md") {
+            notes.push(path);
+        } else if image_mime(&path).is_some() {
+            let name = path.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
+            images_by_name.entry(name).or_insert(path);
+        }
+    }
+
+    let mut result = ImportFolderResult::default();
+    let mut images = BTreeSet::new();
+    let mut written = Vec::new();
+    let mut renames = Vec::new();
+    for note in notes {
+        let display = note.to_string_lossy().to_string();
+        let content = match std::fs::read(&note).map(String::from_utf8) {
+            Ok(Ok(content)) => content,
+            Ok(Err(_)) => {
+                result.skipped.push(ImportFailure {
+                    path: display,
+                    message: "Not a UTF-8 text file".to_string(),
+                });
+                continue;
+            }
+            Err(e) => {
+                result.skipped.push(ImportFailure { path: display, message: e.to_string() });
+                continue;
+            }
+        };
+        let relative = note.strip_prefix(&source).unwrap_or(&note);
+        let dir = dest_root.join(sanitize_relative(relative.parent().unwrap_or(Path::new("")), &policy));
+        let stem = sanitize_string(
+            relative.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+            &policy,
+        );
+        let stem = if stem.trim().is_empty() { "Untitled".to_string() } else { stem };
+        let target = unique_note_path(&dir, &stem);
+        if let Err(e) = std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&target, &content)) {
+            result.skipped.push(ImportFailure { path: display, message: e.to_string() });
+            continue;
+        }
+        result.imported += 1;
+
+        let old_stem = relative.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let new_stem = target.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        if old_stem != new_stem {
+            let old_link = normalize_path(&relative.with_extension("").to_string_lossy()).to_lowercase();
+            renames.push((old_link, old_stem, new_stem));
+        }
+        if include_images {
+            let note_dir = note.parent().unwrap_or(&source);
+            images.extend(referenced_images(&content, note_dir, &source, &images_by_name));
+        }
+        written.push((target, content));
+    }
+
+    // Links between the imported notes still use the source names.
+    if !renames.is_empty() {
+        for (path, content) in &written {
+            let mut updated = content.clone();
+            for (old_link, old_stem, new_stem) in &renames {
+                let re = backlink_rewrite_regex(old_stem)?;
+                let links_to_renamed = |target: &str| {
+                    let target = normalize_path(target).to_lowercase();
+                    *old_link == target || old_link.ends_with(&format!("/{target}"))
+                };
+                updated = rewrite_links_in_text(&re, &updated, new_stem, &links_to_renamed).into_owned();
+            }
+            if updated != *content
+                && let Err(e) = std::fs::write(path, updated)
+            {
+                log::warn!("Could not update links in imported note '{}': {}", path.display(), e);
+            }
+        }
+    }
+
+    for image in images {
+        let relative = image.strip_prefix(&source).unwrap_or(&image);
+        let dir = dest_root.join(sanitize_relative(relative.parent().unwrap_or(Path::new("")), &policy));
+        let target = dir.join(relative.file_name().unwrap_or_default());
+        let display = image.to_string_lossy().to_string();
+        if target.exists() {
+            result.skipped.push(ImportFailure {
+                path: display,
+                message: "A file already exists at the destination".to_string(),
+            });
+            continue;
+        }
+        let copied = target
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::copy(&image, &target));
+        match copied {
+            Ok(_) => result.imported += 1,
+            Err(e) => result.skipped.push(ImportFailure { path: display, message: e.to_string() }),
+        }
+    }
+
+    Ok(result)
+}
+
+async fn import_folder_inner(
+    state: &AppState,
+    kuzu: &ManagedGrafeoConnection,
+    vault_path: &str,
+    source_dir: &str,
+    dest_subfolder: &str,
+    include_images: bool,
+) -> Result<ImportFolderResult, TessellumError> {
+    validate_vault_root(vault_path)?;
+    let vault = vault_path.to_string();
+    let source = PathBuf::from(source_dir);
+    let dest = dest_subfolder.to_string();
+    let result = tokio::task::spawn_blocking(move || {
+        import_folder_files(&vault, &source, &dest, include_images)
+    })
+    .await
+    .map_err(|e| TessellumError::Internal(format!("Task error: {e}")))??;
+
+    if result.imported > 0 {
+        let sync = run_sync_vault(state, kuzu, vault_path).await?;
+        if let Some(error) = sync.error {
+            log::warn!("Index sync after folder import failed: {}", error);
+        }
+    }
+    Ok(result)
+}
+
+/// Import a folder of markdown notes from another app into the vault.
+///
+/// Notes land under `dest_subfolder` (the vault root when empty) and the
+/// vault is re-synced so links between them resolve. With `include_images`,
+/// images the notes embed are copied too. Returns how many files were
+/// imported and which were skipped.
+#[tauri::command]
+pub async fn import_folder(
+    state: State<'_, AppState>,
+    kuzu_state: State<'_, ManagedGrafeoConnection>,
+    vault_path: String,
+    source_dir: String,
+    dest_subfolder: String,
+    include_images: Option<bool>,
+) -> Result<ImportFolderResult, TessellumError> {
+    import_folder_inner(
+        &state,
+        &kuzu_state,
+        &vault_path,
+        &source_dir,
+        &dest_subfolder,
+        include_images.unwrap_or(false),
+    )
+    .await
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Standalone HTML export
+// ────────────────────────────────────────────────────────────────────────────
+
+static IMG_SRC_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"<img src="([^"]+)""#).unwrap());
+static INTERNAL_LINK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r##"<a class="internal-link" data-path="([^"]*)"((?: data-anchor="[^"]*")?) href="#">"##)
+        .unwrap()
+});
+
+fn image_mime(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    Some(match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "bmp" => "image/bmp",
+        "avif" => "image/avif",
+        "tif" | "tiff" => "image/tiff",
+        _ => return None,
+    })
+}
+
+/// Replace vault image sources with `data:` URIs so the document is self-contained.
+///
+/// Relative sources resolve against `note_dir`; remote URLs, files outside the
+/// vault and unreadable files are left as they are.
+fn inline_images(html: &str, note_dir: &Path, vault_path: &str) -> String {
+    IMG_SRC_RE
+        .replace_all(html, |caps: &regex::Captures<'_>| {
+            let src = &caps[1];
+            let candidate = if Path::new(src).is_absolute() {
+                PathBuf::from(src)
+            } else {
+                note_dir.join(src.replace("%20", " "))
+            };
+            let inlined = validate_path_in_vault(&candidate.to_string_lossy(), vault_path)
+                .ok()
+                .and_then(|path| Some((image_mime(&path)?, std::fs::read(&path).ok()?)))
+                .map(|(mime, bytes)| {
+                    let data = base64::engine::general_purpose::STANDARD.encode(bytes);
+                    format!("<img src=\"data:{mime};base64,{data}\"")
+                });
+            inlined.unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+fn note_title(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Note".to_string())
+}
+
+async fn export_note_html_inner(
+    state: &AppState,
+    vault_path: &str,
+    path: &str,
+    include_links: bool,
+) -> Result<String, TessellumError> {
+    let root = validate_path_in_vault(path, vault_path).map_err(TessellumError::Validation)?;
+    let root_path = normalize_path(path);
+
+    // The exported note first, then each linked note that exists, once.
+    let mut notes = vec![(root_path.clone(), root)];
+    if include_links {
+        for target in state.db.get_outgoing_links(&root_path).await? {
+            if notes.iter().any(|(p, _)| *p == target) || !target.ends_with(".md") {
+                continue;
+            }
+            if let Ok(resolved) = validate_path_in_vault(&target, vault_path) {
+                notes.push((target, resolved));
+            }
+        }
+    }
+    let section_ids: HashMap<&str, String> = notes
+        .iter()
+        .enumerate()
+        .map(|(i, (p, _))| (p.as_str(), format!("note-{i}")))
+        .collect();
+
+    let file_index = state.current_file_index(vault_path).await?;
+    let asset_index = state.current_asset_index(vault_path).await?;
+    let strategy = crate::utils::config::read_config(vault_path).link_resolution;
+
+    let mut body = String::new();
+    for (note_path, resolved) in &notes {
+        let content = tokio::fs::read_to_string(resolved).await?;
+        let rendered = render_markdown_html(
+            &content,
+            vault_path,
+            Path::new(note_path),
+            &file_index,
+            &asset_index,
+            strategy,
+        );
+        let note_dir = resolved.parent().unwrap_or(resolved);
+        let inlined = inline_images(&rendered, note_dir, vault_path);
+        // Links to notes inside the document jump to their section.
+        let linked = INTERNAL_LINK_RE.replace_all(&inlined, |caps: &regex::Captures<'_>| {
+            match section_ids.get(&caps[1]) {
+                Some(id) => format!(
+                    "<a class=\"internal-link\" data-path=\"{}\"{} href=\"#{id}\">",
+                    &caps[1], &caps[2]
+                ),
+                None => caps[0].to_string(),
+            }
+        });
+        body.push_str(&format!(
+            "<section id=\"{}\">\n<h1>{}</h1>\n{}</section>\n",
+            section_ids[note_path.as_str()],
+            escape_html(&note_title(note_path)),
+            linked
+        ));
+    }
+
+    Ok(format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="UTF-8" />
+<meta name="viewport" content="width=device-width, initial-scale=1.0" />
+<title>{title}</title>
+<style>
+{css}</style>
+</head>
+<body>
+<main>
+{body}</main>
+</body>
+</html>
+"#,
+        title = escape_html(&note_title(&root_path)),
+        css = crate::commands::publish::CSS,
+        body = body,
+    ))
+}
+
+/// Export a note as a standalone HTML document with images inlined.
+///
+/// With `include_links`, notes it links to are appended one level deep as
+/// sections, and links between them become in-page anchors. The HTML is
+/// returned, and also written to `output_path` when one is given.
+#[tauri::command]
+pub async fn export_note_html(
+    state: State<'_, AppState>,
+    vault_path: String,
+    path: String,
+    include_links: bool,
+    output_path: Option<String>,
+) -> Result<String, TessellumError> {
+    let html = export_note_html_inner(&state, &vault_path, &path, include_links).await?;
+    if let Some(output_path) = output_path {
+        tokio::fs::write(&output_path, &html).await?;
+    }
+    Ok(html)
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Vault backup archive
+// ────────────────────────────────────────────────────────────────────────────
+
+fn zip_error(e: zip::result::ZipError) -> TessellumError {
+    TessellumError::Internal(format!("Failed to write zip archive: {e}"))
+}
+
+/// Write every vault entry the ignore rules keep into a zip at `output`.
+///
+/// Entry names are vault-relative with `/` separators, and folders get their
+/// own entries so empty ones survive. An archive written inside the vault is
+/// not added to itself. Returns the archive size in bytes.
+fn write_vault_zip(vault_path: &Path, output: &Path) -> Result<u64, TessellumError> {
+    let ignore = IgnoreRules::load(&vault_path.to_string_lossy());
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = std::fs::File::create(output)?;
+    let output = output.canonicalize()?;
+    let mut zip = ZipWriter::new(file);
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let entries = WalkDir::new(vault_path)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| ignore.keeps(vault_path, e))
+        .filter_map(|e| e.ok());
+    for entry in entries {
+        let rel_path = entry.path().strip_prefix(vault_path).unwrap_or(entry.path());
+        let name = normalize_path(&rel_path.to_string_lossy());
+        if entry.file_type().is_dir() {
+            zip.add_directory(name, options).map_err(zip_error)?;
+            continue;
+        }
+        let is_output = entry.path().canonicalize().is_ok_and(|path| path == output);
+        if !entry.file_type().is_file() || is_output {
+            continue;
+        }
+        zip.start_file(name, options).map_err(zip_error)?;
+        let mut source = std::fs::File::open(entry.path())?;
+        std::io::copy(&mut source, &mut zip)?;
+    }
+
+    zip.finish().map_err(zip_error)?;
+    Ok(std::fs::metadata(&output)?.len())
+}
+
+/// Archive the vault's notes and attachments into a zip file for backup.
+///
+/// Hidden entries (`.trash`, `.git`, `.tessellum`, ...) and paths matched by
+/// the ignore rules are left out; the folder structure is kept. Returns the
+/// size of the written archive in bytes.
+#[tauri::command]
+pub async fn export_vault_zip(vault_path: String, output_path: String) -> Result<u64, TessellumError> {
+    validate_vault_root(&vault_path)?;
+    tokio::task::spawn_blocking(move || write_vault_zip(Path::new(&vault_path), Path::new(&output_path)))
+        .await
+        .map_err(|e| TessellumError::Internal(format!("Task error: {e}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export_note_html_inner, import_folder_inner, write_vault_zip};
+    use crate::grafeo_projection::ManagedGrafeoConnection;
+    use crate::indexer::VaultIndexer;
+    use crate::test_support::{TestAppState, TestVault};
+
+    #[tokio::test]
+    async fn exports_linked_notes_as_sections_with_inlined_images() {
+        let vault = TestVault::new()
+            .with_markdown("Main.md", "Intro [[Other]]\n\n![[pic.png]]")
+            .with_markdown("Other.md", "Details")
+            .with_markdown("Unlinked.md", "Not exported")
+            .build();
+        std::fs::write(vault.path().join("pic.png"), [0x89, b'P', b'N', b'G']).unwrap();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let state = TestAppState::new().await;
+        VaultIndexer::full_sync(&state.db, state.search_index.clone(), &vault_path)
+            .await
+            .unwrap();
+        let main = vault.path().join("Main.md").to_string_lossy().to_string();
+
+        let alone = export_note_html_inner(&state, &vault_path, &main, false).await.unwrap();
+        assert!(!alone.contains("Details"));
+
+        let html = export_note_html_inner(&state, &vault_path, &main, true).await.unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<section id=\"note-1\">\n<h1>Other</h1>"));
+        assert!(html.contains("href=\"#note-1\">Other</a>"));
+        assert!(html.contains("<img src=\"data:image/png;base64,iVBORw==\""));
+        assert!(!html.contains("Not exported"));
+    }
+
+    #[test]
+    fn zips_the_vault_without_hidden_or_ignored_entries() {
+        let vault = TestVault::new()
+            .with_markdown("Home.md", "# Home")
+            .with_markdown("Projects/Plan.md", "# Plan")
+            .with_markdown("Drafts/Scratch.md", "draft")
+            .with_markdown(".trash/1_Old.md", "old")
+            .build();
+        std::fs::create_dir_all(vault.path().join("Empty")).unwrap();
+        std::fs::create_dir_all(vault.path().join(".git")).unwrap();
+        std::fs::write(vault.path().join(".git/HEAD"), "ref").unwrap();
+        std::fs::write(vault.path().join("pic.png"), [0x89, b'P', b'N', b'G']).unwrap();
+        std::fs::write(vault.path().join(".tessellumignore"), "Drafts/\n").unwrap();
+        let output = vault.path().join("backup.zip");
+
+        let size = write_vault_zip(vault.path(), &output).unwrap();
+
+        assert_eq!(size, std::fs::metadata(&output).unwrap().len());
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&output).unwrap()).unwrap();
+        let mut names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        names.sort();
+        assert_eq!(names, vec!["Empty/", "Home.md", "Projects/", "Projects/Plan.md", "pic.png"]);
+        let mut plan = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name("Projects/Plan.md").unwrap(), &mut plan).unwrap();
+        assert_eq!(plan, "# Plan");
+    }
+
+    #[tokio::test]
+    async fn imports_notes_and_embedded_images_then_indexes_links() {
+        let vault = TestVault::new().with_markdown("Imported/Home.md", "existing").build();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let source = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(source.path().join("Inbox/img")).unwrap();
+        std::fs::create_dir_all(source.path().join("Att: old")).unwrap();
+        std::fs::write(source.path().join("Home.md"), "[[Inbox/Plan?]] ![[pic.png]] ![[scan.png]]").unwrap();
+        std::fs::write(
+            source.path().join("Inbox/Plan?.md"),
+            "[[Home#Top|start]] ![chart](img/chart%20one.png)",
+        )
+        .unwrap();
+        std::fs::write(source.path().join("Inbox/img/chart one.png"), "png").unwrap();
+        std::fs::write(source.path().join("Inbox/img/pic.png"), "png").unwrap();
+        std::fs::write(source.path().join("Att: old/scan.png"), "png").unwrap();
+        std::fs::write(source.path().join("unused.png"), "png").unwrap();
+        std::fs::write(source.path().join("Broken.md"), [0xff, 0xfe, 0x00]).unwrap();
+        let state = TestAppState::new().await;
+
+        let result = import_folder_inner(
+            &state,
+            &ManagedGrafeoConnection::default(),
+            &vault_path,
+            &source.path().to_string_lossy(),
+            "Imported",
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.imported, 5);
+        assert_eq!(result.skipped.len(), 1);
+        assert!(result.skipped[0].path.ends_with("Broken.md"));
+        let imported = vault.path().join("Imported");
+        assert_eq!(std::fs::read_to_string(imported.join("Home.md")).unwrap(), "existing");
+        assert!(imported.join("Home (1).md").is_file());
+        assert!(imported.join("Inbox/Plan.md").is_file());
+        assert!(imported.join("Inbox/img/chart one.png").is_file());
+        assert!(imported.join("Inbox/img/pic.png").is_file());
+        assert!(imported.join("Att old/scan.png").is_file());
+        assert_eq!(
+            std::fs::read_to_string(imported.join("Home (1).md")).unwrap(),
+            "[[Inbox/Plan]] ![[pic.png]] ![[scan.png]]"
+        );
+        assert_eq!(
+            std::fs::read_to_string(imported.join("Inbox/Plan.md")).unwrap(),
+            "[[Home (1)#Top|start]] ![chart](img/chart%20one.png)"
+        );
+        assert!(!imported.join("unused.png").exists());
+        let home = crate::utils::normalize_path(&imported.join("Home (1).md").to_string_lossy());
+        let plan = crate::utils::normalize_path(&imported.join("Inbox/Plan.md").to_string_lossy());
+        assert!(state.db.get_outgoing_links(&home).await.unwrap().contains(&plan));
+    }
+}
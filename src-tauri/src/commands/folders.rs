@@ -1,6 +1,10 @@
 use std::path::Path;
 
-use crate::utils::sanitize_string;
+use walkdir::WalkDir;
+
+use crate::error::TessellumError;
+use crate::utils::config::load_or_init_config;
+use crate::utils::{is_hidden_or_special, sanitize_string, validate_path_in_vault, validate_target_dir};
 
 /// Asynchronous command to create a new folder within a specified vault path.
 ///
@@ -9,9 +13,21 @@ use crate::utils::sanitize_string;
 /// 2. Validates that the folder name is not empty after sanitization.
 /// 3. Checks if a folder with the same name already exists at the desired location.
 /// 4. Creates the folder if it does not already exist.
+///
+/// `vault_path` is the parent folder; it must resolve inside the vault at
+/// `vault_root`, whose filename policy is applied.
 #[tauri::command]
-pub async fn create_folder(vault_path: String, folder_name: String) -> Result<String, String> {
-    let sanitized_folder_name = sanitize_string(folder_name);
+pub async fn create_folder(
+    vault_path: String,
+    folder_name: String,
+    vault_root: String,
+) -> Result<String, String> {
+    validate_target_dir(&vault_path, &vault_root).map_err(|e| e.to_string())?;
+    
+    let policy = load_or_init_config(&vault_root)
+        .map_err(|e| e.to_string())?
+        .filenames;
+    let sanitized_folder_name = sanitize_string(folder_name, &policy);
     
     // SECURITY & VALIDATION:
     // Ensure the name isn't empty after sanitization.
@@ -43,13 +59,56 @@ pub async fn create_folder(vault_path: String, folder_name: String) -> Result<St
     Ok(folder_path.to_string_lossy().to_string())
 }
 
+/// Total size in bytes of the files under `path`, recursively.
+///
+/// Hidden entries such as `.trash` and `.git` are skipped, as are files
+/// whose metadata cannot be read.
+fn folder_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|e| !is_hidden_or_special(e.path().strip_prefix(path).unwrap_or(e.path())))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|meta| meta.len())
+        .sum()
+}
+
+/// Recursive size of a folder, for showing in the UI.
+///
+/// `FileMetadata.size` is 0 for directories; this sums the files beneath.
+/// The folder must resolve inside `vault_path`.
+#[tauri::command]
+pub async fn get_folder_size(vault_path: String, path: String) -> Result<u64, TessellumError> {
+    // Only report a missing folder when its parent is inside the vault, so
+    // paths outside it can't be probed.
+    let folder = validate_path_in_vault(&path, &vault_path).map_err(|message| {
+        let parent_in_vault = Path::new(&path)
+            .parent()
+            .is_some_and(|parent| validate_path_in_vault(&parent.to_string_lossy(), &vault_path).is_ok());
+        if parent_in_vault && !Path::new(&path).exists() {
+            TessellumError::NotFound(format!("Folder not found: {}", path))
+        } else {
+            TessellumError::Validation(message)
+        }
+    })?;
+    if !folder.is_dir() {
+        return Err(TessellumError::Validation(format!("Not a folder: {}", path)));
+    }
+    tokio::task::spawn_blocking(move || folder_size(&folder))
+        .await
+        .map_err(|e| TessellumError::Internal(format!("Folder size task failed: {e}")))
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs;
 
     use tempfile::tempdir;
 
-    use super::create_folder;
+    use super::{create_folder, get_folder_size};
+    use crate::error::TessellumError;
 
     #[tokio::test]
     async fn creates_a_folder_inside_the_vault() {
@@ -58,6 +117,7 @@ mod tests {
         let created = create_folder(
             vault.path().to_str().unwrap().to_string(),
             "Projects".to_string(),
+            vault.path().to_string_lossy().to_string(),
         )
         .await
         .unwrap();
@@ -73,6 +133,7 @@ mod tests {
         let err = create_folder(
             vault.path().to_str().unwrap().to_string(),
             "...   ".to_string(),
+            vault.path().to_string_lossy().to_string(),
         )
         .await
         .unwrap_err();
@@ -88,10 +149,122 @@ mod tests {
         let err = create_folder(
             vault.path().to_str().unwrap().to_string(),
             "Projects".to_string(),
+            vault.path().to_string_lossy().to_string(),
         )
         .await
         .unwrap_err();
 
         assert_eq!(err, "Folder already exists");
     }
+
+    #[tokio::test]
+    async fn rejects_bogus_vault_paths() {
+        let vault = tempdir().unwrap();
+        let file = vault.path().join("not-a-dir.md");
+        fs::write(&file, "").unwrap();
+
+        let missing = create_folder(
+            vault.path().join("missing").to_str().unwrap().to_string(),
+            "Projects".to_string(),
+            vault.path().to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap_err();
+        assert!(missing.contains("Vault path does not exist"));
+
+        let not_dir = create_folder(
+            file.to_str().unwrap().to_string(),
+            "Projects".to_string(),
+            vault.path().to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap_err();
+        assert!(not_dir.contains("Vault path is not a directory"));
+    }
+
+    #[tokio::test]
+    async fn rejects_parent_folders_that_escape_the_vault_root() {
+        let outer = tempdir().unwrap();
+        let vault = outer.path().join("Vault");
+        fs::create_dir_all(vault.join("Projects")).unwrap();
+        let root = vault.to_string_lossy().to_string();
+
+        let err = create_folder(
+            vault.join("..").to_string_lossy().to_string(),
+            "Escaped".to_string(),
+            root.clone(),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("outside the vault"));
+        assert!(!outer.path().join("Escaped").exists());
+
+        create_folder(vault.join("Projects").to_string_lossy().to_string(), "Inner".to_string(), root)
+            .await
+            .unwrap();
+        assert!(vault.join("Projects/Inner").is_dir());
+    }
+
+    #[tokio::test]
+    async fn sums_nested_file_sizes_skipping_hidden_folders() {
+        let vault = tempdir().unwrap();
+        fs::create_dir_all(vault.path().join("Projects/Deep")).unwrap();
+        fs::create_dir_all(vault.path().join("Projects/.trash")).unwrap();
+        fs::write(vault.path().join("Projects/Plan.md"), "12345").unwrap();
+        fs::write(vault.path().join("Projects/Deep/Notes.md"), "123").unwrap();
+        fs::write(vault.path().join("Projects/.trash/Old.md"), "1234567890").unwrap();
+
+        let vault_path = vault.path().to_string_lossy().to_string();
+
+        let projects = vault.path().join("Projects").to_string_lossy().to_string();
+        let size = get_folder_size(vault_path.clone(), projects).await.unwrap();
+        assert_eq!(size, 8);
+
+        let file = get_folder_size(
+            vault_path,
+            vault.path().join("Projects/Plan.md").to_string_lossy().to_string(),
+        )
+        .await;
+        assert!(file.is_err());
+    }
+
+    #[tokio::test]
+    async fn refuses_folders_outside_the_vault() {
+        let outer = tempdir().unwrap();
+        let vault = outer.path().join("Vault");
+        fs::create_dir_all(vault.join("Projects")).unwrap();
+        fs::write(outer.path().join("secret.md"), "12345").unwrap();
+
+        let err = get_folder_size(
+            vault.to_string_lossy().to_string(),
+            vault.join("..").to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(err, TessellumError::Validation(_)));
+
+        let outside = get_folder_size(
+            vault.to_string_lossy().to_string(),
+            outer.path().join("secret.md").to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(outside, TessellumError::Validation(_)));
+        let missing_outside = get_folder_size(
+            vault.to_string_lossy().to_string(),
+            outer.path().join("Missing").to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(missing_outside, TessellumError::Validation(_)));
+
+        let missing = get_folder_size(
+            vault.to_string_lossy().to_string(),
+            vault.join("Missing").to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(missing, TessellumError::NotFound(_)));
+    }
 }
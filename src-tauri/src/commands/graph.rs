@@ -1,5 +1,5 @@
 use serde::Serialize;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use tauri::State;
 
 use crate::error::TessellumError;
@@ -9,6 +9,12 @@ use crate::models::AppState;
 pub struct GraphNode {
 	pub id: String,
 	pub label: String,
+	/// Display title: frontmatter `title:`, else the first `# Heading`, else
+	/// the file stem. Ghost nodes use their label.
+	pub title: String,
+	/// File name including its extension, e.g. `Plan.md`.
+	pub filename: String,
+	/// `false` for ghost nodes: link targets with no note behind them.
 	pub exists: bool,
 	pub orphan: bool,
 	pub tags: Vec<String>,
@@ -27,6 +33,13 @@ pub struct GraphData {
 	pub edges: Vec<GraphEdge>,
 }
 
+fn path_to_filename(path: &str) -> String {
+	std::path::Path::new(path)
+		.file_name()
+		.map(|name| name.to_string_lossy().to_string())
+		.unwrap_or_default()
+}
+
 fn path_to_label(path: &str, vault_path: &str) -> String {
 	let normalized = crate::utils::normalize_path(path);
 	let normalized_vault = crate::utils::normalize_path(vault_path);
@@ -56,6 +69,263 @@ pub async fn get_graph_data(
 	build_graph_data(&state, &vault_path).await
 }
 
+/// Deepest traversal `get_local_graph` will perform, whatever the caller asks for.
+const MAX_LOCAL_GRAPH_DEPTH: usize = 5;
+/// Upper bound on nodes in a local graph, so dense vaults stay responsive.
+const MAX_LOCAL_GRAPH_NODES: usize = 500;
+
+/// Retrieves the subgraph reachable from `path` within `depth` hops, following
+/// both outgoing links and backlinks. Same shape as `get_graph_data`.
+#[tauri::command]
+pub async fn get_local_graph(
+	state: State<'_, AppState>,
+	vault_path: String,
+	path: String,
+	depth: usize,
+) -> Result<GraphData, TessellumError> {
+	let graph = build_graph_data(&state, &vault_path).await?;
+	Ok(local_subgraph(
+		graph,
+		&crate::utils::normalize_path(&path),
+		depth.min(MAX_LOCAL_GRAPH_DEPTH),
+		MAX_LOCAL_GRAPH_NODES,
+	))
+}
+
+/// Breadth-first walk over the undirected link graph from `center`.
+///
+/// Stops after `depth` hops or once `max_nodes` nodes have been reached; the
+/// returned edges are those whose endpoints were both kept.
+fn local_subgraph(graph: GraphData, center: &str, depth: usize, max_nodes: usize) -> GraphData {
+	let mut neighbors: HashMap<&str, Vec<&str>> = HashMap::new();
+	for edge in &graph.edges {
+		neighbors.entry(edge.source.as_str()).or_default().push(edge.target.as_str());
+		neighbors.entry(edge.target.as_str()).or_default().push(edge.source.as_str());
+	}
+	
+	let mut seen: HashSet<&str> = HashSet::new();
+	let mut queue: VecDeque<(&str, usize)> = VecDeque::new();
+	if let Some(node) = graph.nodes.iter().find(|node| node.id == center) {
+		seen.insert(node.id.as_str());
+		queue.push_back((node.id.as_str(), 0));
+	}
+	'walk: while let Some((id, distance)) = queue.pop_front() {
+		if distance == depth {
+			continue;
+		}
+		for &next in neighbors.get(id).into_iter().flatten() {
+			if seen.len() >= max_nodes {
+				break 'walk;
+			}
+			if seen.insert(next) {
+				queue.push_back((next, distance + 1));
+			}
+		}
+	}
+	let reached: HashSet<String> = seen.into_iter().map(str::to_string).collect();
+	
+	let nodes = graph
+		.nodes
+		.into_iter()
+		.filter(|node| reached.contains(&node.id))
+		.collect();
+	let edges = graph
+		.edges
+		.into_iter()
+		.filter(|edge| reached.contains(&edge.source) && reached.contains(&edge.target))
+		.collect();
+	GraphData { nodes, edges }
+}
+
+/// Nodes whose path lies inside `folder` and the edges among them.
+///
+/// `folder` is a normalized path without a trailing `/`; matching stops at
+/// folder boundaries, so `Projects` does not take in `Projects2`. An empty
+/// folder keeps the whole graph.
+fn folder_subgraph(graph: GraphData, folder: &str) -> GraphData {
+	let prefix = format!("{folder}/");
+	let inside = |id: &str| folder.is_empty() || id.starts_with(&prefix);
+	let edges = graph
+		.edges
+		.into_iter()
+		.filter(|edge| inside(&edge.source) && inside(&edge.target))
+		.collect();
+	let nodes = graph.nodes.into_iter().filter(|node| inside(&node.id)).collect();
+	GraphData { nodes, edges }
+}
+
+/// Resolve `folder_prefix` to an existing folder inside the vault, written
+/// from `vault_path` as given so it lines up with the graph's node ids.
+///
+/// `..` and symlinks are resolved first, so the prefix can't point outside
+/// the vault.
+fn graph_folder(vault_path: &str, folder_prefix: &str) -> Result<String, TessellumError> {
+	let requested = std::path::Path::new(vault_path).join(folder_prefix.trim());
+	let folder = crate::utils::validate_path_in_vault(&requested.to_string_lossy(), vault_path)
+		.map_err(TessellumError::Validation)?;
+	let root = crate::utils::validate_path_in_vault(vault_path, vault_path).map_err(TessellumError::Validation)?;
+	let relative = folder.strip_prefix(&root).unwrap_or(&folder);
+	let folder = crate::utils::normalize_path(&std::path::Path::new(vault_path).join(relative).to_string_lossy());
+	Ok(folder.trim_end_matches('/').to_string())
+}
+
+/// Retrieves the graph of one folder: the notes inside `folder_prefix`, at
+/// any depth, and the links among them. Same shape as `get_graph_data`.
+///
+/// `folder_prefix` may be absolute or relative to the vault, and must name
+/// a folder inside it. Ghost nodes are kept when the missing note would live
+/// inside the folder.
+#[tauri::command]
+pub async fn get_graph_data_filtered(
+	state: State<'_, AppState>,
+	vault_path: String,
+	folder_prefix: String,
+) -> Result<GraphData, TessellumError> {
+	let folder = graph_folder(&vault_path, &folder_prefix)?;
+	let graph = build_graph_data(&state, &vault_path).await?;
+	Ok(folder_subgraph(graph, &folder))
+}
+
+/// Longest cycle, in notes, `find_cycles` looks for.
+const MAX_CYCLE_LENGTH: usize = 8;
+/// Most cycles `find_cycles` reports.
+const MAX_CYCLES: usize = 200;
+/// Edges `find_cycles` may follow in total before giving up on dense vaults.
+const MAX_CYCLE_SEARCH_STEPS: usize = 200_000;
+
+/// Depth-first cycle search over a link adjacency map, with its limits.
+struct CycleSearch<'a> {
+	adjacency: &'a BTreeMap<&'a str, BTreeSet<&'a str>>,
+	max_length: usize,
+	max_cycles: usize,
+	steps_left: usize,
+	cycles: Vec<Vec<String>>,
+}
+
+impl<'a> CycleSearch<'a> {
+	fn exhausted(&self) -> bool {
+		self.cycles.len() >= self.max_cycles || self.steps_left == 0
+	}
+	
+	/// Follow links from the end of `path`, recording each way back to `path[0]`.
+	///
+	/// Only notes sorting after the start are entered, so every cycle is found
+	/// once, from its smallest path, and never as a rotation of itself.
+	fn extend(&mut self, path: &mut Vec<&'a str>) {
+		let adjacency = self.adjacency;
+		let start = path[0];
+		let last = path[path.len() - 1];
+		for &next in adjacency.get(last).into_iter().flatten() {
+			if self.exhausted() {
+				return;
+			}
+			self.steps_left -= 1;
+			if next == start {
+				self.cycles.push(path.iter().map(|p| p.to_string()).collect());
+			} else if next > start && path.len() < self.max_length && !path.contains(&next) {
+				path.push(next);
+				self.extend(path);
+				path.pop();
+			}
+		}
+	}
+}
+
+/// Cycles in the directed link graph given as `(source, target)` edges.
+///
+/// Each cycle lists its notes in link order, starting from the smallest path;
+/// rotations are reported once and self-links are ignored. At most
+/// `max_cycles` cycles of up to `max_length` notes are returned, and the search
+/// stops early after [`MAX_CYCLE_SEARCH_STEPS`] edges.
+fn find_link_cycles(edges: &[(String, String)], max_length: usize, max_cycles: usize) -> Vec<Vec<String>> {
+	let mut adjacency: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+	for (source, target) in edges {
+		if source != target {
+			adjacency.entry(source.as_str()).or_default().insert(target.as_str());
+		}
+	}
+	
+	let mut search = CycleSearch {
+		adjacency: &adjacency,
+		max_length,
+		max_cycles,
+		steps_left: MAX_CYCLE_SEARCH_STEPS,
+		cycles: Vec::new(),
+	};
+	for &start in adjacency.keys() {
+		if search.exhausted() {
+			break;
+		}
+		search.extend(&mut vec![start]);
+	}
+	search.cycles
+}
+
+/// Circular link chains (A → B → C → A) in the vault, each as an ordered list
+/// of note paths.
+///
+/// Bounded to cycles of up to 8 notes and 200 results so large vaults stay fast.
+#[tauri::command]
+pub async fn find_cycles(state: State<'_, AppState>) -> Result<Vec<Vec<String>>, TessellumError> {
+	let edges = state.db.get_all_links().await?;
+	Ok(find_link_cycles(&edges, MAX_CYCLE_LENGTH, MAX_CYCLES))
+}
+
+/// Shortest chain of notes from `from` to `to`, following links either way.
+///
+/// Both ends are included; `[from]` when they are the same note, empty when
+/// no chain exists. Neighbors are visited in path order, so ties are broken
+/// the same way on every call.
+fn shortest_link_path(edges: &[(String, String)], from: &str, to: &str) -> Vec<String> {
+	let mut neighbors: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+	for (source, target) in edges {
+		neighbors.entry(source.as_str()).or_default().insert(target.as_str());
+		neighbors.entry(target.as_str()).or_default().insert(source.as_str());
+	}
+	if !neighbors.contains_key(from) || !neighbors.contains_key(to) {
+		return if from == to { vec![from.to_string()] } else { Vec::new() };
+	}
+	
+	// Each reached note maps to the note it was reached from.
+	let mut previous: HashMap<&str, &str> = HashMap::from([(from, from)]);
+	let mut queue = VecDeque::from([from]);
+	while let Some(id) = queue.pop_front() {
+		if id == to {
+			let mut path = vec![to.to_string()];
+			let mut current = to;
+			while current != from {
+				current = previous[current];
+				path.push(current.to_string());
+			}
+			path.reverse();
+			return path;
+		}
+		for &next in &neighbors[id] {
+			if !previous.contains_key(next) {
+				previous.insert(next, id);
+				queue.push_back(next);
+			}
+		}
+	}
+	Vec::new()
+}
+
+/// How two notes are connected: the shortest chain of links between them,
+/// ignoring link direction, as ordered note paths. Empty if disconnected.
+#[tauri::command]
+pub async fn path_between(
+	state: State<'_, AppState>,
+	from_path: String,
+	to_path: String,
+) -> Result<Vec<String>, TessellumError> {
+	let edges = state.db.get_all_links().await?;
+	Ok(shortest_link_path(
+		&edges,
+		&crate::utils::normalize_path(&from_path),
+		&crate::utils::normalize_path(&to_path),
+	))
+}
+
 /// Execute a GQL/Cypher query on the Grafeo database
 #[tauri::command]
 pub fn execute_graph_query(cypher: String) -> Result<serde_json::Value, TessellumError> {
@@ -70,7 +340,7 @@ pub async fn build_graph_data(
 	let db = state.db.clone();
 	
 	let notes = db
-		.get_all_indexed_files()
+		.get_all_notes_with_titles()
 		.await
 		.map_err(TessellumError::from)?;
 	let links = db
@@ -109,7 +379,7 @@ pub async fn build_graph_data(
 	let mut existing_paths = HashSet::new();
 	
 	// Add existing nodes
-	for (path, _) in notes {
+	for (path, _, title) in notes {
 		let normalized = crate::utils::normalize_path(&path);
 		existing_paths.insert(normalized.clone());
 		
@@ -118,6 +388,8 @@ pub async fn build_graph_data(
 		nodes.push(GraphNode {
 			id: normalized.clone(),
 			label: path_to_label(&path, vault_path),
+			title: title.unwrap_or_else(|| crate::utils::frontmatter::title_from_path(&path)),
+			filename: path_to_filename(&path),
 			exists: true,
 			orphan: orphaned_files.contains(&normalized),
 			tags,
@@ -131,19 +403,19 @@ pub async fn build_graph_data(
 		
 		let broken = broken_links.contains(&(normalized_source.clone(), normalized_target.clone()));
 		
-		if broken && !existing_paths.contains(&normalized_target) {
-			// Check if we already added a ghost node for this target
-			let already_added = nodes.iter().any(|n| n.id == normalized_target);
-			if !already_added {
-				nodes.push(GraphNode {
-					id: normalized_target.clone(),
-					label: path_to_label(&target, vault_path),
-					exists: false,
-					orphan: false,
-					tags: Vec::new(),
-				});
-				existing_paths.insert(normalized_target.clone());
-			}
+		// Targets outside the notes table are either ghost nodes (broken links)
+		// or embedded attachments; each is added once.
+		if existing_paths.insert(normalized_target.clone()) {
+			let label = path_to_label(&target, vault_path);
+			nodes.push(GraphNode {
+				id: normalized_target.clone(),
+				title: label.clone(),
+				label,
+				filename: path_to_filename(&target),
+				exists: !broken,
+				orphan: false,
+				tags: Vec::new(),
+			});
 		}
 		
 		edges.push(GraphEdge {
@@ -160,10 +432,12 @@ pub async fn build_graph_data(
 mod tests {
     use tempfile::tempdir;
 
-    use super::{build_graph_data, path_to_label};
+    use super::{
+        build_graph_data, find_link_cycles, folder_subgraph, graph_folder, local_subgraph, path_to_label,
+        shortest_link_path, GraphData, GraphEdge, GraphNode,
+    };
     use crate::db::Database;
-    use crate::models::AppState;
-    use crate::search::SearchIndex;
+    use crate::test_support::TestAppState;
 
     #[test]
     fn strips_vault_prefix_and_markdown_extension_for_labels() {
@@ -199,9 +473,9 @@ mod tests {
         .unwrap();
         db.index_file(&beta.to_string_lossy(), 1, 10, None, None, &[]).await.unwrap();
         db.index_file(&orphan.to_string_lossy(), 1, 10, None, None, &[]).await.unwrap();
+        db.set_note_title(&beta.to_string_lossy(), "Second Phase").await.unwrap();
 
-        let search_dir = tempdir().unwrap();
-        let app_state = AppState::new(db, SearchIndex::open_or_create(&search_dir.path().join("search-index")).unwrap());
+        let app_state = TestAppState::with_db(db);
         let normalized_alpha = crate::utils::normalize_path(&alpha.to_string_lossy());
         let normalized_orphan = crate::utils::normalize_path(&orphan.to_string_lossy());
         let normalized_missing = crate::utils::normalize_path(&missing.to_string_lossy());
@@ -211,6 +485,137 @@ mod tests {
         assert!(graph.nodes.iter().any(|node| node.id == normalized_alpha && node.exists));
         assert!(graph.nodes.iter().any(|node| node.id == normalized_orphan && node.orphan));
         assert!(graph.nodes.iter().any(|node| node.id == normalized_missing && !node.exists));
+        assert_eq!(graph.nodes.iter().filter(|node| !node.exists).count(), 1);
+        assert!(graph.nodes.iter().any(|node| node.id == normalized_alpha && node.filename == "Alpha.md"));
+        assert!(graph.nodes.iter().any(|node| node.id == normalized_alpha && node.title == "Alpha"));
+        assert!(graph.nodes.iter().any(|node| node.title == "Second Phase"));
         assert!(graph.edges.iter().any(|edge| edge.target == normalized_missing && edge.broken));
     }
+
+    fn chain_graph() -> GraphData {
+        let node = |id: &str| GraphNode {
+            id: id.to_string(),
+            label: id.to_string(),
+            title: id.to_string(),
+            filename: format!("{id}.md"),
+            exists: true,
+            orphan: false,
+            tags: Vec::new(),
+        };
+        let edge = |source: &str, target: &str| GraphEdge {
+            source: source.to_string(),
+            target: target.to_string(),
+            broken: false,
+        };
+        // A -> B <- C -> D, plus an unrelated E
+        GraphData {
+            nodes: ["A", "B", "C", "D", "E"].into_iter().map(node).collect(),
+            edges: vec![edge("A", "B"), edge("C", "B"), edge("C", "D")],
+        }
+    }
+
+    fn ids(graph: &GraphData) -> Vec<&str> {
+        let mut ids: Vec<&str> = graph.nodes.iter().map(|node| node.id.as_str()).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn local_graph_follows_links_and_backlinks_up_to_depth() {
+        let depth_one = local_subgraph(chain_graph(), "B", 1, 100);
+        assert_eq!(ids(&depth_one), vec!["A", "B", "C"]);
+        assert_eq!(depth_one.edges.len(), 2);
+
+        let depth_two = local_subgraph(chain_graph(), "A", 2, 100);
+        assert_eq!(ids(&depth_two), vec!["A", "B", "C"]);
+
+        let depth_three = local_subgraph(chain_graph(), "A", 3, 100);
+        assert_eq!(ids(&depth_three), vec!["A", "B", "C", "D"]);
+
+        assert!(local_subgraph(chain_graph(), "Missing", 2, 100).nodes.is_empty());
+    }
+
+    #[test]
+    fn local_graph_stops_at_the_node_cap() {
+        let capped = local_subgraph(chain_graph(), "B", 5, 2);
+        assert_eq!(capped.nodes.len(), 2);
+        assert_eq!(capped.edges.len(), 1);
+    }
+
+    #[test]
+    fn folder_graph_keeps_notes_inside_the_folder_and_their_links() {
+        let mut graph = chain_graph();
+        let path = |id: &str| match id {
+            "A" => "V/Projects/A.md",
+            "B" => "V/Projects/Sub/B.md",
+            "C" => "V/Projects2/C.md",
+            "D" => "V/Projects/D.md",
+            _ => "V/E.md",
+        };
+        for node in &mut graph.nodes {
+            node.id = path(&node.id).to_string();
+        }
+        for edge in &mut graph.edges {
+            edge.source = path(&edge.source).to_string();
+            edge.target = path(&edge.target).to_string();
+        }
+
+        let projects = folder_subgraph(graph.clone(), "V/Projects");
+        assert_eq!(ids(&projects), vec!["V/Projects/A.md", "V/Projects/D.md", "V/Projects/Sub/B.md"]);
+        assert_eq!(projects.edges.len(), 1);
+        assert_eq!(projects.edges[0].source, "V/Projects/A.md");
+
+        assert_eq!(folder_subgraph(graph, "").nodes.len(), 5);
+    }
+
+    #[test]
+    fn graph_folder_resolves_inside_the_vault_only() {
+        let outer = tempdir().unwrap();
+        let vault = outer.path().join("Vault");
+        std::fs::create_dir_all(vault.join("Projects/Sub")).unwrap();
+        std::fs::create_dir_all(outer.path().join("Other")).unwrap();
+        let vault_path = vault.to_string_lossy().to_string();
+        let projects = crate::utils::normalize_path(&vault.join("Projects").to_string_lossy());
+
+        assert_eq!(graph_folder(&vault_path, "Projects/Sub/..").unwrap(), projects);
+        assert_eq!(graph_folder(&vault_path, &format!("{projects}/")).unwrap(), projects);
+        assert!(graph_folder(&vault_path, "../Other").is_err());
+        assert!(graph_folder(&vault_path, "Missing").is_err());
+    }
+
+    #[test]
+    fn finds_each_cycle_once_within_the_length_bound() {
+        let edges: Vec<(String, String)> = [
+            ("B", "C"), ("C", "A"), ("A", "B"),
+            ("D", "E"), ("E", "D"),
+            ("F", "F"),
+            ("A", "G"),
+        ]
+        .into_iter()
+        .map(|(source, target)| (source.to_string(), target.to_string()))
+        .collect();
+
+        assert_eq!(
+            find_link_cycles(&edges, 8, 100),
+            vec![vec!["A", "B", "C"], vec!["D", "E"]]
+        );
+        assert_eq!(find_link_cycles(&edges, 2, 100), vec![vec!["D", "E"]]);
+        assert_eq!(find_link_cycles(&edges, 8, 1).len(), 1);
+    }
+
+    #[test]
+    fn shortest_link_path_ignores_direction_and_reports_disconnected_notes() {
+        // A -> B <- C -> D <- A, D -> E, and a separate X -> Y
+        let edges: Vec<(String, String)> =
+            [("A", "B"), ("C", "B"), ("C", "D"), ("A", "D"), ("D", "E"), ("X", "Y")]
+            .into_iter()
+            .map(|(source, target)| (source.to_string(), target.to_string()))
+            .collect();
+
+        assert_eq!(shortest_link_path(&edges, "B", "E"), vec!["B", "A", "D", "E"]);
+        assert_eq!(shortest_link_path(&edges, "E", "A"), vec!["E", "D", "A"]);
+        assert_eq!(shortest_link_path(&edges, "C", "C"), vec!["C"]);
+        assert!(shortest_link_path(&edges, "A", "Y").is_empty());
+        assert!(shortest_link_path(&edges, "A", "Missing").is_empty());
+    }
 }
@@ -1,7 +1,13 @@
 use std::path::{Path, PathBuf};
 use std::fs;
 use serde::{Deserialize, Serialize};
+use tauri::State;
+use crate::commands::notes::write_and_index_note;
 use crate::error::TessellumError;
+use crate::grafeo_projection::ManagedGrafeoConnection;
+use crate::models::AppState;
+use crate::utils::config::read_config;
+use crate::utils::validate_path_in_vault;
 
 const HISTORY_DIR: &str = ".tessellum/history";
 const SNAPSHOT_EXT: &str = ".md";
@@ -91,33 +97,73 @@ fn time_from_secs(secs: i64) -> (i32, u32, u32, u32, u32, u32) {
     (y, mo, r as u32 + 1, h, m, s)
 }
 
-/// Write a snapshot of a note. Called on every save.
-#[tauri::command]
-pub async fn write_note_snapshot(
-    vault_path: String,
-    note_path: String,
-    content: String,
-) -> Result<String, TessellumError> {
-    let dir = history_dir_for_note(&vault_path, &note_path);
-    fs::create_dir_all(&dir)
+/// Snapshot timestamps in `dir`, newest first. Pin label files are skipped.
+fn snapshot_timestamps(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut timestamps: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            name.strip_suffix(SNAPSHOT_EXT).map(str::to_string)
+        })
+        .collect();
+    // The compact ISO names sort chronologically.
+    timestamps.sort_by(|a, b| b.cmp(a));
+    timestamps
+}
+
+/// Write `content` as a new snapshot in `dir`, then drop the oldest unpinned
+/// snapshots beyond `max_snapshots` (at least 1). Returns the timestamp.
+fn write_snapshot(dir: &Path, content: &str, max_snapshots: usize) -> Result<String, TessellumError> {
+    fs::create_dir_all(dir)
         .map_err(|e| TessellumError::Internal(format!("Failed to create history dir: {e}")))?;
 
-    let ts_ms = timestamp_now_ms();
+    // Two snapshots in the same millisecond still get distinct files.
+    let mut ts_ms = timestamp_now_ms();
+    while dir.join(format!("{}{SNAPSHOT_EXT}", ms_to_iso(ts_ms))).exists() {
+        ts_ms += 1;
+    }
     let ts = ms_to_iso(ts_ms);
     let snapshot_path = dir.join(format!("{ts}{SNAPSHOT_EXT}"));
 
-    fs::write(&snapshot_path, &content)
+    fs::write(&snapshot_path, content)
         .map_err(|e| TessellumError::Internal(format!("Failed to write snapshot '{}': {e}", snapshot_path.display())))?;
 
+    let unpinned = snapshot_timestamps(dir)
+        .into_iter()
+        .filter(|stale| !dir.join(format!("{stale}{SNAPSHOT_EXT}{PIN_EXT}")).exists());
+    for stale in unpinned.skip(max_snapshots.max(1)) {
+        let stale_path = dir.join(format!("{stale}{SNAPSHOT_EXT}"));
+        if let Err(e) = fs::remove_file(&stale_path) {
+            log::warn!("Failed to prune snapshot '{}': {}", stale_path.display(), e);
+        }
+    }
     Ok(ts)
 }
 
+/// Write a snapshot of a note. Called on every save.
+///
+/// Only the newest `versions.max_per_note` unpinned snapshots of the note are
+/// kept; pinned ones are never pruned.
+#[tauri::command]
+pub async fn write_note_snapshot(
+    vault_path: String,
+    note_path: String,
+    content: String,
+) -> Result<String, TessellumError> {
+    let max_snapshots = read_config(&vault_path).versions.max_per_note;
+    write_snapshot(&history_dir_for_note(&vault_path, &note_path), &content, max_snapshots)
+}
+
 /// List all snapshots for a note, newest first.
 #[tauri::command]
 pub async fn list_note_snapshots(
     vault_path: String,
     note_path: String,
 ) -> Result<Vec<SnapshotInfo>, TessellumError> {
+    validate_path_in_vault(&note_path, &vault_path).map_err(TessellumError::Validation)?;
     let dir = history_dir_for_note(&vault_path, &note_path);
     if !dir.exists() {
         return Ok(vec![]);
@@ -167,6 +213,21 @@ pub async fn list_note_snapshots(
     Ok(snapshots)
 }
 
+/// Content of the snapshot `timestamp` of a note.
+fn read_snapshot(vault_path: &str, note_path: &str, timestamp: &str) -> Result<String, TessellumError> {
+    if timestamp.contains(['/', '\\']) || timestamp.contains("..") {
+        return Err(TessellumError::Validation(format!("Invalid snapshot timestamp '{timestamp}'")));
+    }
+    let path = history_dir_for_note(vault_path, note_path).join(format!("{timestamp}{SNAPSHOT_EXT}"));
+    fs::read_to_string(&path).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            TessellumError::NotFound(format!("Snapshot {timestamp} of '{note_path}'"))
+        } else {
+            TessellumError::Internal(format!("Failed to read snapshot '{}': {e}", path.display()))
+        }
+    })
+}
+
 /// Read the content of a specific snapshot.
 #[tauri::command]
 pub async fn get_note_snapshot(
@@ -174,10 +235,8 @@ pub async fn get_note_snapshot(
     note_path: String,
     timestamp: String,
 ) -> Result<String, TessellumError> {
-    let dir = history_dir_for_note(&vault_path, &note_path);
-    let path = dir.join(format!("{timestamp}{SNAPSHOT_EXT}"));
-    fs::read_to_string(&path)
-        .map_err(|e| TessellumError::Internal(format!("Failed to read snapshot '{}': {e}", path.display())))
+    validate_path_in_vault(&note_path, &vault_path).map_err(TessellumError::Validation)?;
+    read_snapshot(&vault_path, &note_path, &timestamp)
 }
 
 /// Pin a snapshot with a user label.
@@ -210,6 +269,77 @@ pub async fn unpin_snapshot(
     Ok(())
 }
 
+/// Snapshot a note's current content before it is overwritten.
+///
+/// Saves already snapshot what they write, so this only adds a snapshot when
+/// the note changed outside the app since then. Nothing is saved when the note
+/// doesn't exist yet or `new_content` is the same as what's on disk.
+pub(crate) fn save_previous_version(
+    vault_path: &str,
+    note_path: &str,
+    new_content: &str,
+    max_snapshots: usize,
+) -> Result<(), TessellumError> {
+    let Ok(previous) = fs::read_to_string(note_path) else {
+        return Ok(());
+    };
+    if previous == new_content {
+        return Ok(());
+    }
+
+    let dir = history_dir_for_note(vault_path, note_path);
+    let newest = snapshot_timestamps(&dir).into_iter().next();
+    if newest.is_some_and(|ts| fs::read_to_string(dir.join(format!("{ts}{SNAPSHOT_EXT}"))).is_ok_and(|s| s == previous)) {
+        return Ok(());
+    }
+    write_snapshot(&dir, &previous, max_snapshots).map(|_| ())
+}
+
+/// Put a snapshot back as the note's content and re-index it.
+///
+/// The restore goes through the normal save path, so the restored content is
+/// snapshotted too and the content it replaced is already in the history.
+#[tauri::command]
+pub async fn restore_note_snapshot(
+    state: State<'_, AppState>,
+    kuzu_state: State<'_, ManagedGrafeoConnection>,
+    vault_path: String,
+    note_path: String,
+    timestamp: String,
+) -> Result<String, TessellumError> {
+    validate_path_in_vault(&note_path, &vault_path).map_err(TessellumError::Validation)?;
+    let content = read_snapshot(&vault_path, &note_path, &timestamp)?;
+
+    write_and_index_note(&state, &kuzu_state, vault_path, note_path, content.clone()).await?;
+    Ok(content)
+}
+
+/// List the saved versions of a note, newest first.
+///
+/// Versions are the note's history snapshots; this is `list_note_snapshots`
+/// under the name the version commands use.
+#[tauri::command]
+pub async fn list_versions(
+    vault_path: String,
+    path: String,
+) -> Result<Vec<SnapshotInfo>, TessellumError> {
+    list_note_snapshots(vault_path, path).await
+}
+
+/// Roll a note back to the version saved at `timestamp`.
+///
+/// Same as `restore_note_snapshot`; returns the restored content.
+#[tauri::command]
+pub async fn restore_version(
+    state: State<'_, AppState>,
+    kuzu_state: State<'_, ManagedGrafeoConnection>,
+    vault_path: String,
+    path: String,
+    timestamp: String,
+) -> Result<String, TessellumError> {
+    restore_note_snapshot(state, kuzu_state, vault_path, path, timestamp).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,4 +367,47 @@ mod tests {
         assert!(ts.starts_with("2024"), "timestamp: {ts}");
         assert!(ts.ends_with('Z'), "timestamp: {ts}");
     }
+
+    #[test]
+    fn snapshots_are_capped_but_keep_pinned_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        for content in ["v1", "v2", "v3", "v4"] {
+            write_snapshot(dir.path(), content, 2).unwrap();
+            if content == "v1" {
+                let ts = snapshot_timestamps(dir.path()).remove(0);
+                fs::write(dir.path().join(format!("{ts}{SNAPSHOT_EXT}{PIN_EXT}")), "first").unwrap();
+            }
+        }
+
+        let contents: Vec<String> = snapshot_timestamps(dir.path())
+            .iter()
+            .map(|ts| fs::read_to_string(dir.path().join(format!("{ts}{SNAPSHOT_EXT}"))).unwrap())
+            .collect();
+        assert_eq!(contents, vec!["v4", "v3", "v1"]);
+    }
+
+    #[test]
+    fn previous_versions_only_snapshot_content_not_already_in_history() {
+        let vault = tempfile::tempdir().unwrap();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let note = vault.path().join("Draft.md").to_string_lossy().to_string();
+        let dir = history_dir_for_note(&vault_path, &note);
+
+        // A note that doesn't exist yet has nothing to keep.
+        save_previous_version(&vault_path, &note, "v1", 5).unwrap();
+        fs::write(&note, "v1").unwrap();
+        save_previous_version(&vault_path, &note, "v1", 5).unwrap();
+        assert!(snapshot_timestamps(&dir).is_empty());
+
+        // The last save was snapshotted, so overwriting it adds nothing.
+        write_snapshot(&dir, "v1", 5).unwrap();
+        save_previous_version(&vault_path, &note, "v2", 5).unwrap();
+        assert_eq!(snapshot_timestamps(&dir).len(), 1);
+
+        // An outside edit is kept before the app overwrites it.
+        fs::write(&note, "edited elsewhere").unwrap();
+        save_previous_version(&vault_path, &note, "v2", 5).unwrap();
+        let newest = snapshot_timestamps(&dir).remove(0);
+        assert_eq!(fs::read_to_string(dir.join(format!("{newest}{SNAPSHOT_EXT}"))).unwrap(), "edited elsewhere");
+    }
 }
@@ -1,13 +1,15 @@
 use serde::Serialize;
+use std::path::Path;
 use std::sync::atomic::Ordering;
 use std::time::UNIX_EPOCH;
 use tauri::State;
 
 use crate::error::TessellumError;
-use crate::indexer::{IndexStats, VaultIndexer};
+use crate::indexer::{read_frontmatter_aliases, IndexStats, VaultIndexer};
 use crate::grafeo_projection::{sync_full, ManagedGrafeoConnection};
 use crate::models::AppState;
-use crate::utils::is_hidden_or_special;
+use crate::utils::config::load_or_init_config;
+use crate::utils::{validate_path_in_vault, validate_vault_root, IgnoreRules};
 
 /// Response from the sync_vault command.
 #[derive(Serialize)]
@@ -17,6 +19,8 @@ pub struct SyncResult {
     pub files_deleted: usize,
     pub files_skipped: usize,
     pub duration_ms: u128,
+    /// `true` when `cancel_sync` stopped the run; counts are partial.
+    pub cancelled: bool,
     pub error: Option<String>,
 }
 
@@ -28,6 +32,7 @@ impl From<IndexStats> for SyncResult {
             files_deleted: stats.files_deleted,
             files_skipped: stats.files_skipped,
             duration_ms: stats.duration_ms,
+            cancelled: stats.cancelled,
             error: None,
         }
     }
@@ -65,48 +70,258 @@ pub async fn run_sync_vault(
             files_deleted: 0,
             files_skipped: 0,
             duration_ms: 0,
+            cancelled: false,
             error: None,
         });
     }
 
-    let db = state.db.clone();
-    let search_index = state.search_index.clone();
-
-    let result = match VaultIndexer::full_sync(db.as_ref(), search_index, vault_path).await {
-        Ok(stats) => {
-            // Only do full Grafeo sync if this is an initial/manual sync with many changes
-            // Individual note changes are synced incrementally via write_file command
-            let total_changes = stats.files_indexed + stats.files_deleted;
-            if total_changes > 10
-                && let Err(err) = sync_full(grafeo_state, db.as_ref()).await {
-                    log::warn!(
-                        "Grafeo sync_full failed after vault sync for '{}': {}",
-                        vault_path,
-                        err
-                    );
-                }
-            let mut idx_guard = state.file_index.lock().await;
-            *idx_guard = None;
-            let mut asset_guard = state.asset_index.lock().await;
-            *asset_guard = None;
-            Ok(SyncResult::from(stats))
-        }
-        Err(e) => Ok(SyncResult {
+    let result = match sync_vault_claimed(state, grafeo_state, vault_path).await {
+        Ok(stats) => SyncResult::from(stats),
+        Err(e) => SyncResult {
             success: false,
             files_indexed: 0,
             files_deleted: 0,
             files_skipped: 0,
             duration_ms: 0,
+            cancelled: false,
             error: Some(e),
-        }),
+        },
     };
 
-    // Always clear the flag, even on error, so future syncs can proceed.
+    // Always release the flag, even on error, so future syncs can proceed.
+    release_sync(state);
+
+    Ok(result)
+}
+
+/// Full sync for a caller that already holds `sync_in_progress`.
+///
+/// The flag is left set; the caller releases it with `release_sync`.
+async fn sync_vault_claimed(
+    state: &AppState,
+    grafeo_state: &ManagedGrafeoConnection,
+    vault_path: &str,
+) -> Result<IndexStats, String> {
+    let db = state.db.clone();
+    let search_index = state.search_index.clone();
+
+    let indexing = load_or_init_config(vault_path)
+        .map(|config| config.indexing)
+        .unwrap_or_default();
+
+    let stats = VaultIndexer::full_sync_with_config(
+        db.as_ref(),
+        search_index,
+        vault_path,
+        &indexing,
+        &state.sync_cancel_requested,
+    )
+    .await?;
+
+    // Only do full Grafeo sync if this is an initial/manual sync with many changes
+    // Individual note changes are synced incrementally via write_file command
+    let total_changes = stats.files_indexed + stats.files_deleted;
+    if total_changes > 10
+        && let Err(err) = sync_full(grafeo_state, db.as_ref()).await {
+            log::warn!(
+                "Grafeo sync_full failed after vault sync for '{}': {}",
+                vault_path,
+                err
+            );
+        }
+    let mut idx_guard = state.file_index.lock().await;
+    *idx_guard = None;
+    let mut asset_guard = state.asset_index.lock().await;
+    *asset_guard = None;
+    Ok(stats)
+}
+
+/// Release `sync_in_progress` at the end of a run.
+///
+/// A pending cancel is cleared here rather than when the flag is claimed, so
+/// one accepted right after the claim still stops that run.
+fn release_sync(state: &AppState) {
+    state.sync_cancel_requested.store(false, Ordering::Release);
     state.sync_in_progress.store(false, Ordering::Release);
+}
+
+/// Throw the index away and re-scan the vault from scratch.
+///
+/// The escape hatch for an index that incremental syncs can't repair, e.g.
+/// when backlinks look wrong. Notes, links, tags and the full-text index are
+/// dropped and rebuilt by a clean full sync, whose `IndexStats` are returned.
+/// Fails with a validation error while another sync is running.
+#[tauri::command]
+pub async fn rebuild_index(
+    state: State<'_, AppState>,
+    kuzu_state: State<'_, ManagedGrafeoConnection>,
+    vault_path: String,
+) -> Result<IndexStats, TessellumError> {
+    rebuild_index_inner(state.inner(), kuzu_state.inner(), &vault_path).await
+}
+
+async fn rebuild_index_inner(
+    state: &AppState,
+    grafeo_state: &ManagedGrafeoConnection,
+    vault_path: &str,
+) -> Result<IndexStats, TessellumError> {
+    validate_vault_root(vault_path)?;
+    // Hold the sync flag through the reset and the re-scan, so no other sync
+    // sees half-dropped tables or slips in between the two.
+    if state.sync_in_progress
+        .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+        .is_err()
+    {
+        return Err(TessellumError::Validation(
+            "Cannot rebuild the index while a sync is running".to_string(),
+        ));
+    }
+
+    log::info!("rebuild_index: dropping the index for '{}'", vault_path);
+    let result = async {
+        state.db.reset_index().await?;
+        state.search_index.lock().await.clear().map_err(TessellumError::Internal)?;
+        sync_vault_claimed(state, grafeo_state, vault_path)
+            .await
+            .map_err(TessellumError::Internal)
+    }
+    .await;
+    release_sync(state);
 
     result
 }
 
+/// Ask the running full sync to stop at the next file or batch boundary.
+///
+/// Returns `true` if a sync was running and will be cancelled. The sync itself
+/// resolves with a `SyncResult` whose `cancelled` flag is set.
+#[tauri::command]
+pub async fn cancel_sync(state: State<'_, AppState>) -> Result<bool, TessellumError> {
+    Ok(request_sync_cancel(state.inner()))
+}
+
+fn request_sync_cancel(state: &AppState) -> bool {
+    if !state.sync_in_progress.load(Ordering::Acquire) {
+        return false;
+    }
+    state.sync_cancel_requested.store(true, Ordering::Release);
+    true
+}
+
+/// Database size in bytes before and after `vacuum_index`.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VacuumResult {
+    pub size_before: u64,
+    pub size_after: u64,
+}
+
+/// Compact the SQLite index and refresh its query statistics.
+///
+/// Meant as a maintenance action after many deletions. It claims the same
+/// flag as `sync_vault`, so a vacuum and a full sync never run at once; while
+/// a sync is running this fails with a validation error.
+#[tauri::command]
+pub async fn vacuum_index(state: State<'_, AppState>) -> Result<VacuumResult, TessellumError> {
+    vacuum_index_inner(state.inner()).await
+}
+
+async fn vacuum_index_inner(state: &AppState) -> Result<VacuumResult, TessellumError> {
+    if state.sync_in_progress
+        .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+        .is_err()
+    {
+        return Err(TessellumError::Validation(
+            "Cannot compact the index while a sync is running".to_string(),
+        ));
+    }
+
+    let result = state.db.vacuum().await;
+    release_sync(state);
+
+    let (size_before, size_after) = result?;
+    log::info!("vacuum_index: {} -> {} bytes", size_before, size_after);
+    Ok(VacuumResult { size_before, size_after })
+}
+
+/// What `index_file_command` did with the file.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IndexFileOutcome {
+    /// The note was not indexed before.
+    Added,
+    /// The note was already indexed and has been refreshed.
+    Updated,
+    /// The note no longer exists on disk and was dropped from the index.
+    Removed,
+    /// Not an indexable note (hidden, special, or not markdown).
+    Skipped,
+}
+
+/// Index a single note without walking the whole vault.
+///
+/// Meant to be called right after a save instead of a full `sync_vault`.
+#[tauri::command]
+pub async fn index_file_command(
+    state: State<'_, AppState>,
+    vault_path: String,
+    path: String,
+) -> Result<IndexFileOutcome, TessellumError> {
+    index_single_file(state.inner(), &vault_path, &path).await
+}
+
+async fn index_single_file(
+    state: &AppState,
+    vault_path: &str,
+    path: &str,
+) -> Result<IndexFileOutcome, TessellumError> {
+    validate_vault_root(vault_path)?;
+    let normalized = crate::utils::normalize_path(path);
+    let was_indexed = state.db.is_note_indexed(&normalized).await?;
+
+    if Path::new(path).exists() {
+        validate_path_in_vault(path, vault_path).map_err(TessellumError::Validation)?;
+    } else if !was_indexed {
+        // Only paths the index already knows about may be dropped.
+        return Err(TessellumError::NotFound(format!("File not found: {}", path)));
+    }
+
+    let file_index = state.current_file_index(vault_path).await?;
+    let asset_index = state.current_asset_index(vault_path).await?;
+
+    let changed = VaultIndexer::index_path(
+        state.db.as_ref(),
+        &state.search_index,
+        vault_path,
+        Path::new(path),
+        &file_index,
+        &asset_index,
+    )
+    .await
+    .map_err(TessellumError::Internal)?;
+    if !changed {
+        return Ok(IndexFileOutcome::Skipped);
+    }
+
+    let is_indexed = state.db.is_note_indexed(&normalized).await?;
+    let outcome = match (was_indexed, is_indexed) {
+        (true, true) => IndexFileOutcome::Updated,
+        (false, true) => IndexFileOutcome::Added,
+        (_, false) => IndexFileOutcome::Removed,
+    };
+    if let Some(idx) = state.file_index.lock().await.as_mut() {
+        match outcome {
+            IndexFileOutcome::Added | IndexFileOutcome::Updated => {
+                idx.insert(path);
+                idx.set_aliases(path, &read_frontmatter_aliases(path));
+            }
+            IndexFileOutcome::Removed => idx.remove(path),
+            IndexFileOutcome::Skipped => {}
+        }
+    }
+
+    Ok(outcome)
+}
+
 /// Index status returned to the frontend.
 #[derive(Serialize, Clone)]
 pub struct IndexStatus {
@@ -144,14 +359,14 @@ pub async fn get_index_status(
     let mut total: u64 = 0;
     let mut stale: u64 = 0;
 
+    let ignore = IgnoreRules::load(&vault_path);
     if let Ok(entries) = walkdir::WalkDir::new(&vault_path)
         .into_iter()
+        .filter_entry(|e| ignore.keeps(&vault_path, e))
         .collect::<Result<Vec<_>, _>>()
     {
         for entry in entries {
             let path = entry.path();
-            let rel = path.strip_prefix(&vault_path).unwrap_or(path);
-            if is_hidden_or_special(rel) { continue; }
             if path.extension().and_then(|s| s.to_str()) != Some("md") { continue; }
             if !path.is_file() { continue; }
 
@@ -184,12 +399,16 @@ pub async fn get_index_status(
 mod tests {
     use tempfile::tempdir;
 
-    use super::{run_sync_vault, SyncResult};
+    use std::sync::atomic::Ordering;
+
+    use super::{
+        index_single_file, rebuild_index_inner, release_sync, request_sync_cancel, run_sync_vault,
+        sync_vault_claimed, vacuum_index_inner, IndexFileOutcome, SyncResult,
+    };
     use crate::db::Database;
     use crate::grafeo_projection::ManagedGrafeoConnection;
-    use crate::models::{AppState, AssetIndex, FileIndex};
-    use crate::search::SearchIndex;
-    use crate::test_support::TestVault;
+    use crate::models::{AssetIndex, FileIndex};
+    use crate::test_support::{TestAppState, TestVault};
 
     #[test]
     fn sync_result_maps_index_stats_fields() {
@@ -198,6 +417,7 @@ mod tests {
             files_deleted: 1,
             files_skipped: 3,
             duration_ms: 42,
+            cancelled: false,
         });
 
         assert!(result.success);
@@ -208,6 +428,102 @@ mod tests {
         assert_eq!(result.error, None);
     }
 
+    #[tokio::test]
+    async fn cancel_only_flags_a_running_sync() {
+        let db_dir = tempdir().unwrap();
+        let db = Database::init(db_dir.path().join("cancel.sqlite").to_str().unwrap())
+            .await
+            .unwrap();
+        let state = TestAppState::with_db(db);
+
+        assert!(!request_sync_cancel(&state));
+        assert!(!state.sync_cancel_requested.load(Ordering::Acquire));
+
+        state.sync_in_progress.store(true, Ordering::Release);
+        assert!(request_sync_cancel(&state));
+        assert!(state.sync_cancel_requested.load(Ordering::Acquire));
+    }
+
+    #[tokio::test]
+    async fn cancel_requested_right_after_the_flag_is_claimed_stops_the_run() {
+        let vault = TestVault::new()
+            .with_markdown("Alpha.md", "# Alpha")
+            .with_markdown("Beta.md", "# Beta")
+            .build();
+        let vault_path = vault.path().to_str().unwrap();
+        let state = TestAppState::new().await;
+        let grafeo_state = ManagedGrafeoConnection::default();
+
+        state.sync_in_progress.store(true, Ordering::Release);
+        assert!(request_sync_cancel(&state));
+        let stats = sync_vault_claimed(&state, &grafeo_state, vault_path).await.unwrap();
+        release_sync(&state);
+
+        assert!(stats.cancelled);
+        assert_eq!(stats.files_indexed, 0);
+        assert!(!state.sync_cancel_requested.load(Ordering::Acquire));
+        assert!(!state.sync_in_progress.load(Ordering::Acquire));
+
+        let resumed = run_sync_vault(&state, &grafeo_state, vault_path).await.unwrap();
+        assert!(!resumed.cancelled);
+        assert_eq!(resumed.files_indexed, 2);
+    }
+
+    #[tokio::test]
+    async fn rebuild_index_drops_stale_rows_and_reindexes_the_vault() {
+        let vault = TestVault::new()
+            .with_markdown("Alpha.md", "See [[Beta]]")
+            .with_markdown("Beta.md", "# Beta")
+            .build();
+        let vault_path = vault.path().to_str().unwrap();
+        let db_dir = tempdir().unwrap();
+        let db = Database::init(db_dir.path().join("rebuild.sqlite").to_str().unwrap())
+            .await
+            .unwrap();
+        let state = TestAppState::with_db(db);
+        let grafeo_state = ManagedGrafeoConnection::default();
+        run_sync_vault(&state, &grafeo_state, vault_path).await.unwrap();
+        // A row incremental syncs would never revisit.
+        state.db.index_file("Ghost.md", 1, 1, None, None, &["Nowhere.md".to_string()]).await.unwrap();
+
+        let stats = rebuild_index_inner(&state, &grafeo_state, vault_path).await.unwrap();
+
+        assert!(!stats.cancelled);
+        assert_eq!(stats.files_indexed, 2);
+        assert!(!state.sync_in_progress.load(Ordering::Acquire));
+        let mut indexed: Vec<String> =
+            state.db.get_all_indexed_files().await.unwrap().into_iter().map(|(path, _)| path).collect();
+        indexed.sort();
+        assert_eq!(
+            indexed,
+            vec![
+                crate::utils::normalize_path(&vault.path().join("Alpha.md").to_string_lossy()),
+                crate::utils::normalize_path(&vault.path().join("Beta.md").to_string_lossy()),
+            ]
+        );
+        assert_eq!(state.db.get_all_links().await.unwrap().len(), 1);
+
+        state.sync_in_progress.store(true, Ordering::Release);
+        assert!(rebuild_index_inner(&state, &grafeo_state, vault_path).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn vacuum_index_refuses_to_run_during_a_sync() {
+        let db_dir = tempdir().unwrap();
+        let db = Database::init(db_dir.path().join("vacuum.sqlite").to_str().unwrap())
+            .await
+            .unwrap();
+        let state = TestAppState::with_db(db);
+
+        state.sync_in_progress.store(true, Ordering::Release);
+        assert!(vacuum_index_inner(&state).await.is_err());
+
+        state.sync_in_progress.store(false, Ordering::Release);
+        let result = vacuum_index_inner(&state).await.unwrap();
+        assert!(result.size_after > 0);
+        assert!(!state.sync_in_progress.load(Ordering::Acquire));
+    }
+
     #[tokio::test]
     async fn run_sync_vault_returns_success_and_invalidates_cached_indexes() {
         let vault = TestVault::new()
@@ -218,11 +534,7 @@ mod tests {
         let db = Database::init(db_dir.path().join("indexer-command.sqlite").to_str().unwrap())
             .await
             .unwrap();
-        let search_dir = tempdir().unwrap();
-        let state = AppState::new(
-            db,
-            SearchIndex::open_or_create(&search_dir.path().join("search-index")).unwrap(),
-        );
+        let state = TestAppState::with_db(db);
         let grafeo_state = ManagedGrafeoConnection::default();
 
         *state.file_index.lock().await = Some(FileIndex::build(vault.path().to_str().unwrap()).unwrap());
@@ -237,4 +549,33 @@ mod tests {
         assert!(state.file_index.lock().await.is_none());
         assert!(state.asset_index.lock().await.is_none());
     }
+
+    #[tokio::test]
+    async fn index_single_file_reports_added_updated_and_removed() {
+        let vault = TestVault::new()
+            .with_markdown("Target.md", "# Target")
+            .build();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let state = TestAppState::new().await;
+        let note = vault.path().join("Note.md");
+        let note_path = note.to_string_lossy().to_string();
+        std::fs::write(&note, "See [[Target]]").unwrap();
+
+        let outcome = index_single_file(&state, &vault_path, &note_path).await.unwrap();
+        assert_eq!(outcome, IndexFileOutcome::Added);
+        let target = crate::utils::normalize_path(&vault.path().join("Target.md").to_string_lossy());
+        assert_eq!(
+            state.db.get_outgoing_links(&crate::utils::normalize_path(&note_path)).await.unwrap(),
+            vec![target]
+        );
+
+        std::fs::write(&note, "No links now").unwrap();
+        let outcome = index_single_file(&state, &vault_path, &note_path).await.unwrap();
+        assert_eq!(outcome, IndexFileOutcome::Updated);
+
+        std::fs::remove_file(&note).unwrap();
+        let outcome = index_single_file(&state, &vault_path, &note_path).await.unwrap();
+        assert_eq!(outcome, IndexFileOutcome::Removed);
+        assert!(!state.db.is_note_indexed(&crate::utils::normalize_path(&note_path)).await.unwrap());
+    }
 }
@@ -1,44 +1,223 @@
+use pulldown_cmark::{Event, LinkType, Parser, Tag};
 use regex::Regex;
-use std::sync::LazyLock;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
 use tauri::State;
 
+use crate::commands::notes::write_and_index_note;
 use crate::error::TessellumError;
-use crate::models::{AppState, WikiLink};
-
-static WIKILINK_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\\)?\[\[(.*?)\]\]").unwrap());
-
-/// Extracts all wikilinks from the given input string.
-///
-/// Wikilinks are denoted by the pattern `[[...]]`, where "..." represents
-/// the content of the link. This function uses a statically compiled regex
-/// to find all occurrences and extracts their inner content.
-pub fn extract_wikilinks(content: &str) -> Vec<WikiLink> {
-    WIKILINK_RE
-        .captures_iter(content)
-        .filter_map(|c| {
-            // If there is a backslash before `[[`, this was an escaped literal
-            if c.get(1).is_some() {
-                None
-            } else {
-                let inner = c[2].to_string();
-                
-                // Split on | to separate target from alias
-                if let Some(pipe_pos) = inner.find('|') {
-                    let target = inner[..pipe_pos].trim().to_string();
-                    let alias = inner[pipe_pos + 1..].trim().to_string();
-                    Some(WikiLink {
-                        target,
-                        alias: Some(alias),
-                    })
-                } else {
-                    Some(WikiLink {
-                        target: inner.trim().to_string(),
-                        alias: None,
-                    })
-                }
+use crate::grafeo_projection::ManagedGrafeoConnection;
+use crate::models::{AppState, AssetIndex, FileIndex, LinkStrategy, WikiLink};
+use crate::utils::{code_ranges, WIKILINK_RE};
+
+pub use crate::utils::extract_wikilinks;
+
+/// Decode `%XX` escapes, as in `My%20Note.md`. Invalid input is returned as-is.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = s.get(i + 1..i + 3).and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            out.push(byte);
+            i += 3;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8(out).unwrap_or_else(|_| s.to_string())
+}
+
+/// Whether a link destination is a URL or other scheme (`https:`, `mailto:`,
+/// `obsidian:`, ...) rather than a path.
+fn is_external_destination(dest: &str) -> bool {
+    dest.contains("://") || dest.split_once(':').is_some_and(|(scheme, _)| {
+        !scheme.is_empty() && scheme.chars().all(|c| c.is_ascii_alphanumeric() || c == '+')
+    })
+}
+
+/// `base` joined with the relative path `rest`, with `.` and `..` applied
+/// lexically. `None` for absolute paths.
+fn join_lexically(base: &Path, rest: &str) -> Option<PathBuf> {
+    let mut path = base.to_path_buf();
+    for component in Path::new(rest).components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::ParentDir => {
+                path.pop();
             }
-        })
-        .collect()
+            Component::CurDir => {}
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(path)
+}
+
+/// Vault path of a markdown link destination, or `None` if it isn't a note
+/// inside the vault.
+fn markdown_link_path(dest: &str, source_dir: &Path, vault_root: &Path) -> Option<PathBuf> {
+    if is_external_destination(dest) {
+        return None;
+    }
+    let dest = dest.split(['#', '?']).next().unwrap_or_default();
+    let dest = percent_decode(dest);
+    if !dest.to_ascii_lowercase().ends_with(".md") {
+        return None;
+    }
+
+    // A leading `/` is relative to the vault root, anything else to the note's folder.
+    let path = match dest.strip_prefix('/') {
+        Some(rest) => join_lexically(vault_root, rest)?,
+        None => join_lexically(source_dir, &dest)?,
+    };
+    path.starts_with(vault_root).then_some(path)
+}
+
+/// `target` as a `/`-separated path relative to the folder `base`.
+fn relative_link_path(target: &Path, base: &Path) -> String {
+    let target: Vec<_> = target.components().collect();
+    let base: Vec<_> = base.components().collect();
+    let common = target.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    let mut parts: Vec<String> = vec!["..".to_string(); base.len() - common];
+    parts.extend(target[common..].iter().map(|c| c.as_os_str().to_string_lossy().to_string()));
+    parts.join("/")
+}
+
+/// Rewrite relative markdown link and image destinations in a note that moved
+/// from `old_note` to `new_note`, so they point at the same files as before.
+///
+/// `moves` lists every (from, to) item moved in the same batch, notes or
+/// folders; targets inside any of them are assumed to have moved along. URLs,
+/// vault-absolute `/...` destinations, bare `#anchors`, reference-style links
+/// and links inside code are left alone. Returns `None` when nothing changed.
+pub(crate) fn rebase_relative_links(
+    content: &str,
+    old_note: &Path,
+    new_note: &Path,
+    moves: &[(PathBuf, PathBuf)],
+) -> Option<String> {
+    let old_dir = old_note.parent()?;
+    let new_dir = new_note.parent()?;
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+    for (event, range) in Parser::new(content).into_offset_iter() {
+        let dest_url = match event {
+            Event::Start(Tag::Link { link_type: LinkType::Inline, dest_url, .. })
+            | Event::Start(Tag::Image { link_type: LinkType::Inline, dest_url, .. }) => dest_url,
+            _ => continue,
+        };
+        if dest_url.is_empty()
+            || dest_url.starts_with(['#', '/'])
+            || is_external_destination(&dest_url)
+        {
+            continue;
+        }
+        let split = dest_url.find(['#', '?']).unwrap_or(dest_url.len());
+        let (raw_path, suffix) = dest_url.split_at(split);
+        let decoded = percent_decode(raw_path);
+        let Some(target) = join_lexically(old_dir, &decoded) else {
+            continue;
+        };
+        let target = moves
+            .iter()
+            .find_map(|(from, to)| match target.strip_prefix(from) {
+                Ok(rest) if rest.as_os_str().is_empty() => Some(to.to_path_buf()),
+                Ok(rest) => Some(to.join(rest)),
+                Err(_) => None,
+            })
+            .unwrap_or(target);
+
+        let mut rebased = relative_link_path(&target, new_dir);
+        if raw_path.starts_with("./") && !rebased.starts_with("..") {
+            rebased = format!("./{rebased}");
+        }
+        // Keep the destination a single token unless it was written as `<...>`.
+        let link_text = &content[range.clone()];
+        let Some(offset) = link_text.rfind(&*dest_url) else {
+            continue;
+        };
+        let bracketed = link_text[..offset].ends_with('<');
+        if decoded != raw_path || !bracketed {
+            rebased = rebased.replace(' ', "%20");
+        }
+        let rebased = format!("{rebased}{suffix}");
+        if rebased != *dest_url {
+            let start = range.start + offset;
+            edits.push((start..start + dest_url.len(), rebased));
+        }
+    }
+    if edits.is_empty() {
+        return None;
+    }
+
+    // A linked image sits inside its link, so order by position first.
+    edits.sort_by_key(|(range, _)| range.start);
+    let mut rewritten = content.to_string();
+    for (range, replacement) in edits.into_iter().rev() {
+        rewritten.replace_range(range, &replacement);
+    }
+    Some(rewritten)
+}
+
+/// Extracts note links written as standard markdown: `[label](folder/note.md)`.
+///
+/// Only relative `.md` destinations count; external URLs, other file types and
+/// images are skipped, as are links inside code since the body is parsed as
+/// markdown. Targets are resolved against the source note's folder (or the
+/// vault root for a leading `/`), and links leaving the vault are dropped.
+/// Returns normalized paths, deduplicated in order of appearance.
+pub fn extract_markdown_links(body: &str, source_path: &str, vault_path: &str) -> Vec<String> {
+    let vault_root = Path::new(vault_path);
+    let source_dir = Path::new(source_path).parent().unwrap_or(vault_root);
+    let mut paths: Vec<String> = Vec::new();
+    for event in Parser::new(body) {
+        if let Event::Start(Tag::Link { dest_url, .. }) = event
+            && let Some(path) = markdown_link_path(&dest_url, source_dir, vault_root)
+        {
+            let path = crate::utils::normalize_path(&path.to_string_lossy());
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    paths
+}
+
+/// Split attachment embeds off a note's links.
+///
+/// An embed such as `![[diagram.png]]` that does not name a note but does
+/// resolve through the asset index is returned as a normalized attachment
+/// path; every other link (including `![[Note]]` transclusions) is kept.
+/// Notes are matched as written in `source` under `strategy`.
+pub fn partition_attachment_embeds(
+    links: Vec<WikiLink>,
+    file_index: &FileIndex,
+    asset_index: &AssetIndex,
+    vault_path: &str,
+    source: &Path,
+    strategy: LinkStrategy,
+) -> (Vec<WikiLink>, Vec<String>) {
+    let mut kept = Vec::with_capacity(links.len());
+    let mut embeds = Vec::new();
+    for link in links {
+        let names_note = file_index
+            .resolve_with(vault_path, &link.target, source, strategy)
+            .is_some();
+        let attachment = (link.is_embed && !names_note)
+            .then(|| asset_index.resolve(vault_path, &link.target))
+            .flatten();
+        match attachment {
+            Some(path) => embeds.push(crate::utils::normalize_path(&path.to_string_lossy())),
+            None => kept.push(link),
+        }
+    }
+    embeds.sort();
+    embeds.dedup();
+    (kept, embeds)
 }
 
 /// Get all files that link to the specified file (backlinks).
@@ -55,6 +234,109 @@ pub async fn get_backlinks(
         .map_err(TessellumError::from)
 }
 
+/// Most linking notes read by `get_backlinks_with_context`.
+const MAX_BACKLINK_SOURCES: usize = 200;
+
+/// Most context lines returned per linking note.
+const MAX_CONTEXT_LINES: usize = 5;
+
+/// A line in a linking note that contains a link to the target.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct BacklinkLine {
+    /// 1-based line number in the file.
+    pub line: usize,
+    pub text: String,
+}
+
+/// A note linking to the target, with the lines where the links appear.
+///
+/// `lines` is empty when the link only comes from frontmatter or a markdown
+/// `[label](note.md)` link.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct BacklinkContext {
+    pub path: String,
+    pub lines: Vec<BacklinkLine>,
+}
+
+/// Lines of `content`, the note at `source`, holding a wikilink that resolves
+/// to `target` under `strategy`.
+///
+/// Escaped links and links inside code are ignored, and each line is listed
+/// once, up to `MAX_CONTEXT_LINES`.
+fn backlink_lines(
+    content: &str,
+    source: &Path,
+    target: &str,
+    vault_path: &str,
+    file_index: &FileIndex,
+    strategy: LinkStrategy,
+) -> Vec<BacklinkLine> {
+    let code = code_ranges(content);
+    let mut lines: Vec<BacklinkLine> = Vec::new();
+    for caps in WIKILINK_RE.captures_iter(content) {
+        let whole = caps.get(0).expect("capture 0 is the whole match");
+        if caps.get(2).is_some() || code.iter().any(|range| range.contains(&whole.start())) {
+            continue;
+        }
+        let link = WikiLink::parse(&caps[3]);
+        let resolves_to_target = !link.target.is_empty()
+            && file_index
+                .resolve_with(vault_path, &link.target, source, strategy)
+                .is_some_and(|path| crate::utils::normalize_path(&path.to_string_lossy()) == target);
+        if !resolves_to_target {
+            continue;
+        }
+
+        let line = content[..whole.start()].matches('\n').count() + 1;
+        if lines.last().is_some_and(|last| last.line == line) {
+            continue;
+        }
+        if lines.len() == MAX_CONTEXT_LINES {
+            break;
+        }
+        let start = content[..whole.start()].rfind('\n').map_or(0, |i| i + 1);
+        let end = content[whole.end()..]
+            .find('\n')
+            .map_or(content.len(), |i| whole.end() + i);
+        lines.push(BacklinkLine {
+            line,
+            text: content[start..end].trim().to_string(),
+        });
+    }
+    lines
+}
+
+/// Backlinks with the lines where each linking note mentions the target.
+///
+/// Only the first `MAX_BACKLINK_SOURCES` linking notes (by path) are read, so
+/// heavily linked notes stay responsive; sources that can't be read are
+/// skipped.
+#[tauri::command]
+pub async fn get_backlinks_with_context(
+    state: State<'_, AppState>,
+    vault_path: String,
+    path: String,
+) -> Result<Vec<BacklinkContext>, TessellumError> {
+    let target = crate::utils::normalize_path(&path);
+    let mut sources = state.db.get_backlinks(&target).await?;
+    sources.sort();
+    sources.dedup();
+    sources.truncate(MAX_BACKLINK_SOURCES);
+
+    let file_index = state.current_file_index(&vault_path).await?;
+    let strategy = crate::utils::config::read_config(&vault_path).link_resolution;
+
+    let mut backlinks = Vec::with_capacity(sources.len());
+    for source in sources {
+        let Ok(content) = tokio::fs::read_to_string(&source).await else {
+            continue;
+        };
+        let lines = backlink_lines(&content, Path::new(&source), &target, &vault_path, &file_index, strategy);
+        backlinks.push(BacklinkContext { path: source, lines });
+    }
+    Ok(backlinks)
+}
+
 /// Get all files that the specified file links to (outgoing links).
 #[tauri::command]
 pub async fn get_outgoing_links(
@@ -69,6 +351,356 @@ pub async fn get_outgoing_links(
         .map_err(TessellumError::from)
 }
 
+/// One link of a note as written, with how it resolves.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct OutgoingLink {
+    /// Text shown for the link: the alias if present, otherwise the target.
+    pub display: String,
+    /// The target as written, including any `#heading` or `#^block` anchor.
+    pub target: String,
+    /// Resolved note or attachment path; `None` for dangling links.
+    pub path: Option<String>,
+    pub resolved: bool,
+    pub is_embed: bool,
+    /// Frontmatter property the link was declared under, if any.
+    pub field: Option<String>,
+}
+
+/// Describe every link in a note's body and frontmatter, in document order.
+///
+/// Frontmatter links come first. Repeated links are reported each time they
+/// appear so the editor can decorate every occurrence. Targets resolve as
+/// written in the note at `source` under `strategy`.
+pub fn describe_outgoing_links(
+    content: &str,
+    source: &Path,
+    vault_path: &str,
+    file_index: &FileIndex,
+    asset_index: &AssetIndex,
+    strategy: LinkStrategy,
+) -> Vec<OutgoingLink> {
+    let mut links = Vec::new();
+    let mut body = content;
+    if let Some((yaml, _)) = crate::utils::frontmatter::parse_frontmatter(content) {
+        body = crate::utils::frontmatter::strip_frontmatter(content);
+        for (field, target) in crate::utils::frontmatter::extract_frontmatter_links(&yaml) {
+            let path = file_index.resolve_with(vault_path, &target, source, strategy);
+            links.push(OutgoingLink {
+                display: target.clone(),
+                target,
+                resolved: path.is_some(),
+                path: path.map(|p| crate::utils::normalize_path(&p.to_string_lossy())),
+                is_embed: false,
+                field: Some(field),
+            });
+        }
+    }
+
+    for link in extract_wikilinks(body) {
+        // `[[#Heading]]` points inside this note
+        if link.target.is_empty() {
+            continue;
+        }
+        let path = file_index.resolve_with(vault_path, &link.target, source, strategy).or_else(|| {
+            link.is_embed
+                .then(|| asset_index.resolve(vault_path, &link.target))
+                .flatten()
+        });
+        let target = match (&link.heading, &link.block_id) {
+            (Some(heading), _) => format!("{}#{}", link.target, heading),
+            (None, Some(block)) => format!("{}#^{}", link.target, block),
+            (None, None) => link.target.clone(),
+        };
+        links.push(OutgoingLink {
+            display: link.alias.unwrap_or_else(|| link.target.clone()),
+            target,
+            resolved: path.is_some(),
+            path: path.map(|p| crate::utils::normalize_path(&p.to_string_lossy())),
+            is_embed: link.is_embed,
+            field: None,
+        });
+    }
+    links
+}
+
+/// Get a note's links with their aliases and whether each one resolves.
+///
+/// Unlike `get_outgoing_links`, dangling links are reported with `path: None`
+/// instead of the placeholder path stored in the index.
+#[tauri::command]
+pub async fn get_outgoing_links_detailed(
+    state: State<'_, AppState>,
+    vault_path: String,
+    path: String,
+) -> Result<Vec<OutgoingLink>, TessellumError> {
+    crate::utils::validate_path_in_vault(&path, &vault_path).map_err(TessellumError::Validation)?;
+    let content = tokio::fs::read_to_string(&path).await?;
+
+    let file_index = state.current_file_index(&vault_path).await?;
+    let asset_index = state.current_asset_index(&vault_path).await?;
+    let strategy = crate::utils::config::read_config(&vault_path).link_resolution;
+
+    Ok(describe_outgoing_links(&content, Path::new(&path), &vault_path, &file_index, &asset_index, strategy))
+}
+
+/// A link whose target is an existing note.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ResolvedLink {
+    /// The target as written, without any anchor.
+    pub target: String,
+    pub path: String,
+}
+
+/// A note's outgoing links split by whether their target exists.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct LinksStatus {
+    pub resolved: Vec<ResolvedLink>,
+    /// Targets as written that point at no note.
+    pub dangling: Vec<String>,
+}
+
+/// Group the wikilinks of `content` by what they resolved to during indexing.
+///
+/// Each target is mapped to the path the indexer stores for it and looked up
+/// in the note's indexed links. Links the index doesn't know about yet, e.g.
+/// ones typed since the last save, fall back to the file index. Attachment
+/// embeds are left out and every target is listed once.
+async fn links_status(
+    db: &crate::db::Database,
+    vault_path: &str,
+    path: &str,
+    content: &str,
+    file_index: &FileIndex,
+    asset_index: &AssetIndex,
+    strategy: LinkStrategy,
+) -> Result<LinksStatus, TessellumError> {
+    let indexed: HashMap<String, bool> = db.get_outgoing_link_status(path).await?.into_iter().collect();
+
+    let mut targets = Vec::new();
+    let mut body = content;
+    if let Some((yaml, _)) = crate::utils::frontmatter::parse_frontmatter(content) {
+        body = crate::utils::frontmatter::strip_frontmatter(content);
+        targets.extend(
+            crate::utils::frontmatter::extract_frontmatter_links(&yaml)
+                .into_iter()
+                .map(|(_, target)| target),
+        );
+    }
+    let source = Path::new(path);
+    let (links, _) =
+        partition_attachment_embeds(extract_wikilinks(body), file_index, asset_index, vault_path, source, strategy);
+    targets.extend(links.into_iter().map(|link| link.target));
+
+    let mut status = LinksStatus {
+        resolved: Vec::new(),
+        dangling: Vec::new(),
+    };
+    let mut seen = HashSet::new();
+    for target in targets {
+        // `[[#Heading]]` points inside this note
+        if target.is_empty() || !seen.insert(target.clone()) {
+            continue;
+        }
+        let stored = crate::utils::normalize_path(
+            &file_index
+                .resolve_or_default_with(vault_path, &target, source, strategy)
+                .to_string_lossy(),
+        );
+        let exists = match indexed.get(&stored) {
+            Some(exists) => *exists,
+            None => file_index.resolve_with(vault_path, &target, source, strategy).is_some(),
+        };
+        if exists {
+            status.resolved.push(ResolvedLink { target, path: stored });
+        } else {
+            status.dangling.push(target);
+        }
+    }
+    Ok(status)
+}
+
+/// Get a note's outgoing links grouped into resolved and dangling ones.
+///
+/// Resolution follows the vault's configured link strategy, so the result
+/// matches what the graph and backlinks see.
+#[tauri::command]
+pub async fn get_links_status(
+    state: State<'_, AppState>,
+    vault_path: String,
+    path: String,
+) -> Result<LinksStatus, TessellumError> {
+    crate::utils::validate_path_in_vault(&path, &vault_path).map_err(TessellumError::Validation)?;
+    let normalized = crate::utils::normalize_path(&path);
+    let content = tokio::fs::read_to_string(&path).await?;
+
+    let file_index = state.current_file_index(&vault_path).await?;
+    let asset_index = state.current_asset_index(&vault_path).await?;
+    let strategy = crate::utils::config::read_config(&vault_path).link_resolution;
+
+    links_status(&state.db, &vault_path, &normalized, &content, &file_index, &asset_index, strategy).await
+}
+
+/// A note offered while the user types a `[[` link.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct LinkSuggestion {
+    /// Filename stem, title or alias that matched the prefix.
+    pub display: String,
+    /// Text to insert between the brackets.
+    pub link: String,
+    pub path: String,
+}
+
+/// Whether `[[target]]` resolves to `path` under `strategy`.
+fn links_to(file_index: &FileIndex, vault_path: &str, target: &str, path: &Path, strategy: LinkStrategy) -> bool {
+    file_index.resolve_with(vault_path, target, Path::new(vault_path), strategy).as_deref() == Some(path)
+}
+
+/// Shortest text linking to `path`: its stem when that resolves to it, the
+/// vault-relative path without `.md` otherwise.
+fn link_text_for(file_index: &FileIndex, vault_path: &str, path: &Path, strategy: LinkStrategy) -> String {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    if links_to(file_index, vault_path, &stem, path, strategy) {
+        return stem;
+    }
+    let relative = path.strip_prefix(vault_path).unwrap_or(path).with_extension("");
+    crate::utils::normalize_path(&relative.to_string_lossy())
+}
+
+/// Notes whose stem, title or alias starts with `prefix`, ignoring case.
+///
+/// Recently opened notes come first, then notes with more backlinks; ties go
+/// to the shorter name. A note matching under several names is offered once
+/// per name.
+async fn suggest_links_inner(
+    db: &crate::db::Database,
+    vault_path: &str,
+    file_index: &FileIndex,
+    strategy: LinkStrategy,
+    prefix: &str,
+    limit: usize,
+) -> Result<Vec<LinkSuggestion>, TessellumError> {
+    let prefix = prefix.trim();
+    let lowered = prefix.to_lowercase();
+    let mut suggestions: Vec<LinkSuggestion> = Vec::new();
+    let mut seen = HashSet::new();
+    let mut push = |display: String, link: String, path: String| {
+        if seen.insert((display.to_lowercase(), path.clone())) {
+            suggestions.push(LinkSuggestion { display, link, path });
+        }
+    };
+
+    for (name, path, is_alias) in file_index.names_with_prefix(prefix) {
+        // An alias that resolves on its own is inserted as is, otherwise as display text.
+        let link = if !is_alias {
+            link_text_for(file_index, vault_path, &path, strategy)
+        } else if links_to(file_index, vault_path, &name, &path, strategy) {
+            name.clone()
+        } else {
+            format!("{}|{}", link_text_for(file_index, vault_path, &path, strategy), name)
+        };
+        push(name, link, crate::utils::normalize_path(&path.to_string_lossy()));
+    }
+    for (path, title) in db.get_notes_with_title_prefix(prefix).await? {
+        // SQL `LIKE` only folds ASCII case; re-check with full case folding.
+        if !title.to_lowercase().starts_with(&lowered) {
+            continue;
+        }
+        let link = link_text_for(file_index, vault_path, Path::new(&path), strategy);
+        push(title, link, crate::utils::normalize_path(&path));
+    }
+
+    let mut candidates: Vec<String> = suggestions.iter().map(|s| s.path.clone()).collect();
+    candidates.sort_unstable();
+    candidates.dedup();
+    let ranks: HashMap<String, (i64, i64)> = db
+        .get_note_ranks(&candidates)
+        .await?
+        .into_iter()
+        .map(|(path, opened_at, backlinks)| (crate::utils::normalize_path(&path), (opened_at, backlinks)))
+        .collect();
+    suggestions.sort_by_cached_key(|s| {
+        let (opened_at, backlinks) = ranks.get(&s.path).copied().unwrap_or((0, 0));
+        (
+            std::cmp::Reverse(opened_at),
+            std::cmp::Reverse(backlinks),
+            s.display.chars().count(),
+            s.display.to_lowercase(),
+            s.path.clone(),
+        )
+    });
+    suggestions.truncate(limit);
+    Ok(suggestions)
+}
+
+/// Suggest link targets for the text typed after `[[`.
+///
+/// Candidates are note filenames and frontmatter aliases from the file index
+/// plus indexed titles, ranked by recent opens and link popularity.
+#[tauri::command]
+pub async fn suggest_links(
+    state: State<'_, AppState>,
+    vault_path: String,
+    prefix: String,
+    limit: Option<usize>,
+) -> Result<Vec<LinkSuggestion>, TessellumError> {
+    let file_index = state.current_file_index(&vault_path).await?;
+    let strategy = crate::utils::config::read_config(&vault_path).link_resolution;
+
+    suggest_links_inner(&state.db, &vault_path, &file_index, strategy, &prefix, limit.unwrap_or(20)).await
+}
+
+/// A note that shares link neighbors with the queried note.
+#[derive(Serialize, Debug)]
+pub struct RelatedNote {
+    pub path: String,
+    pub shared_neighbors: i64,
+}
+
+/// Suggest related notes: those sharing the most outgoing targets or backlink sources.
+/// When `exclude_linked` is true, notes the queried note already links to are left out.
+#[tauri::command]
+pub async fn get_related_notes(
+    state: State<'_, AppState>,
+    path: String,
+    limit: Option<i64>,
+    exclude_linked: Option<bool>,
+) -> Result<Vec<RelatedNote>, TessellumError> {
+    let normalized = crate::utils::normalize_path(&path);
+    let rows = state
+        .db
+        .get_related(&normalized, limit.unwrap_or(10).max(0), exclude_linked.unwrap_or(false))
+        .await
+        .map_err(TessellumError::from)?;
+    Ok(rows
+        .into_iter()
+        .map(|(path, shared_neighbors)| RelatedNote { path, shared_neighbors })
+        .collect())
+}
+
+/// A note and how many notes link to it.
+#[derive(Serialize, Debug)]
+pub struct LinkedNote {
+    pub path: String,
+    pub backlink_count: i64,
+}
+
+/// Hub notes: the notes with the most backlinks, highest first.
+#[tauri::command]
+pub async fn get_most_linked(
+    state: State<'_, AppState>,
+    limit: Option<i64>,
+) -> Result<Vec<LinkedNote>, TessellumError> {
+    let rows = state
+        .db
+        .get_most_linked(limit.unwrap_or(20).max(0))
+        .await
+        .map_err(TessellumError::from)?;
+    Ok(rows
+        .into_iter()
+        .map(|(path, backlink_count)| LinkedNote { path, backlink_count })
+        .collect())
+}
+
 /// Get all links in the vault (for graph visualization).
 /// Returns a vector of [source_path, target_path] pairs.
 #[tauri::command]
@@ -79,47 +711,304 @@ pub async fn get_all_links(
     db.get_all_links().await.map_err(TessellumError::from)
 }
 
-/// Resolves a wikilink target to its full path.
-/// Uses the cached in-memory FileIndex for fast lookup without traversing the filesystem.
+/// A wikilink whose resolved target has no indexed note.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct BrokenLink {
+    pub source: String,
+    pub target: String,
+}
+
+/// Orphaned notes and broken links, for the vault health panel.
+#[derive(Serialize, Debug)]
+pub struct VaultHealth {
+    pub orphans: Vec<String>,
+    pub broken_links: Vec<BrokenLink>,
+    pub orphan_count: usize,
+    pub broken_link_count: usize,
+}
+
+/// Collect orphaned notes (no links in or out) and links to notes that are not indexed.
 #[tauri::command]
-pub async fn resolve_wikilink(
+pub async fn get_vault_health(state: State<'_, AppState>) -> Result<VaultHealth, TessellumError> {
+    vault_health(&state.db).await
+}
+
+async fn vault_health(db: &crate::db::Database) -> Result<VaultHealth, TessellumError> {
+    let orphans = db.get_orphaned_files().await?;
+    let broken_links: Vec<BrokenLink> = db
+        .get_broken_links()
+        .await?
+        .into_iter()
+        .map(|(source, target)| BrokenLink { source, target })
+        .collect();
+
+    Ok(VaultHealth {
+        orphan_count: orphans.len(),
+        broken_link_count: broken_links.len(),
+        orphans,
+        broken_links,
+    })
+}
+
+/// Notes sharing a name, which makes `[[name]]` ambiguous.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct DuplicateNameGroup {
+    pub name: String,
+    pub paths: Vec<String>,
+}
+
+/// List note names used by more than one note anywhere in the vault.
+///
+/// `[[name]]` resolves to the note closest to the vault root, which is rarely
+/// obvious to the user; these groups show where renaming would help.
+#[tauri::command]
+pub async fn find_duplicate_names(
     state: State<'_, AppState>,
     vault_path: String,
-    target: String,
-) -> Result<Option<String>, TessellumError> {
-    let resolved_note = {
-        let mut index_guard = state.file_index.lock().await;
+) -> Result<Vec<DuplicateNameGroup>, TessellumError> {
+    let file_index = state.current_file_index(&vault_path).await?;
+
+    Ok(file_index
+        .duplicate_names()
+        .into_iter()
+        .map(|(name, paths)| DuplicateNameGroup {
+            name,
+            paths: paths
+                .iter()
+                .map(|path| crate::utils::normalize_path(&path.to_string_lossy()))
+                .collect(),
+        })
+        .collect())
+}
+
+/// Most notes read from disk when looking for unlinked mentions.
+const MAX_MENTION_CANDIDATES: i64 = 500;
 
-        // Build markdown index if not cached yet
-        if index_guard.is_none() {
-            let idx = crate::models::FileIndex::build(&vault_path)
-                .map_err(|e| TessellumError::Internal(format!("Failed to build file index: {}", e)))?;
-            *index_guard = Some(idx);
+/// Characters of context kept on each side of a mention.
+const SNIPPET_RADIUS: usize = 40;
+
+/// A plain-text occurrence of a note's title in another note.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct UnlinkedMention {
+    pub path: String,
+    /// Byte offset of the occurrence in the note content, BOM excluded.
+    pub offset: usize,
+    pub snippet: String,
+}
+
+/// Byte ranges where text must not be treated as a mention: frontmatter,
+/// code, existing wikilinks, markdown links and raw HTML.
+fn protected_ranges(content: &str) -> Vec<Range<usize>> {
+    let body_start = content.len() - crate::utils::frontmatter::strip_frontmatter(content).len();
+    let mut ranges = vec![0..body_start];
+    ranges.extend(WIKILINK_RE.find_iter(content).map(|m| m.range()));
+    for (event, range) in Parser::new(&content[body_start..]).into_offset_iter() {
+        if matches!(
+            event,
+            Event::Code(_)
+                | Event::Html(_)
+                | Event::InlineHtml(_)
+                | Event::Start(Tag::CodeBlock(_))
+                | Event::Start(Tag::Link { .. })
+        ) {
+            ranges.push(range.start + body_start..range.end + body_start);
         }
+    }
+    ranges
+}
 
-        index_guard
-            .as_ref()
-            .and_then(|file_index| file_index.resolve(&vault_path, &target))
+/// Byte ranges of plain-text occurrences of `title`, ignoring case.
+///
+/// Only whole-word matches count; anything inside a protected range (see
+/// `protected_ranges`) is skipped.
+pub(crate) fn unlinked_mention_ranges(content: &str, title: &str) -> Vec<Range<usize>> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Vec::new();
+    }
+    let Ok(re) = Regex::new(&format!("(?i){}", regex::escape(title))) else {
+        return Vec::new();
     };
+    let protected = protected_ranges(content);
+    re.find_iter(content)
+        .map(|m| m.range())
+        .filter(|m| {
+            let before = content[..m.start].chars().next_back();
+            let after = content[m.end..].chars().next();
+            !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+        })
+        .filter(|m| !protected.iter().any(|p| p.start < m.end && m.start < p.end))
+        .collect()
+}
 
-    if let Some(path) = resolved_note {
-        return Ok(Some(crate::utils::normalize_path(&path.to_string_lossy())));
+/// The line around a mention, cut to `SNIPPET_RADIUS` characters each side.
+fn mention_snippet(content: &str, mention: &Range<usize>) -> String {
+    let line_start = content[..mention.start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = content[mention.end..]
+        .find('\n')
+        .map_or(content.len(), |i| mention.end + i);
+    let start = content[line_start..mention.start]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_RADIUS - 1)
+        .map_or(line_start, |(i, _)| line_start + i);
+    let end = content[mention.end..line_end]
+        .char_indices()
+        .nth(SNIPPET_RADIUS)
+        .map_or(line_end, |(i, _)| mention.end + i);
+
+    let mut snippet = String::new();
+    if start > line_start {
+        snippet.push('…');
+    }
+    snippet.push_str(content[start..end].trim());
+    if end < line_end {
+        snippet.push('…');
     }
+    snippet
+}
 
-    // Wikilinks can target media too (e.g. [[image.png]]), so fall back to the asset index.
-    let resolved_asset = {
-        let mut index_guard = state.asset_index.lock().await;
+/// Find places where a note's title appears in other notes without a link.
+///
+/// Candidates come from the full-text index, then each file is re-read so
+/// offsets match the file on disk. The note named `note_title` is skipped.
+#[tauri::command]
+pub async fn get_unlinked_mentions(
+    state: State<'_, AppState>,
+    vault_path: String,
+    note_title: String,
+) -> Result<Vec<UnlinkedMention>, TessellumError> {
+    crate::utils::validate_vault_root(&vault_path)?;
+    let title = note_title.trim();
+    if title.is_empty() {
+        return Err(TessellumError::Validation("Note title cannot be empty".to_string()));
+    }
+
+    let own_path = state
+        .current_file_index(&vault_path)
+        .await?
+        .resolve(&vault_path, title)
+        .map(|path| crate::utils::normalize_path(&path.to_string_lossy()));
 
-        if index_guard.is_none() {
-            let idx = crate::models::AssetIndex::build(&vault_path)
-                .map_err(|e| TessellumError::Internal(format!("Failed to build asset index: {}", e)))?;
-            *index_guard = Some(idx);
+    let candidates = state.db.search_note_contents(title, MAX_MENTION_CANDIDATES).await?;
+    let mut mentions = Vec::new();
+    for (path, _, _) in candidates {
+        if own_path.as_deref() == Some(path.as_str()) {
+            continue;
         }
+        let Ok(content) = tokio::fs::read_to_string(&path).await else {
+            continue;
+        };
+        // Offsets are relative to the BOM-less text the editor shows.
+        let content = crate::utils::frontmatter::strip_bom(&content);
+        for mention in unlinked_mention_ranges(content, title) {
+            mentions.push(UnlinkedMention {
+                path: path.clone(),
+                offset: mention.start,
+                snippet: mention_snippet(content, &mention),
+            });
+        }
+    }
+    mentions.sort_by(|a, b| a.path.cmp(&b.path).then(a.offset.cmp(&b.offset)));
 
-        index_guard
-            .as_ref()
-            .and_then(|asset_index| asset_index.resolve(&vault_path, &target))
+    Ok(mentions)
+}
+
+/// Wrap the unlinked mention of `target_title` starting at `offset` in a wikilink.
+///
+/// The mention keeps its original casing: `rust` linking to `Rust` becomes
+/// `[[Rust|rust]]`. Offsets inside code, frontmatter or existing links are
+/// rejected, as is any offset that isn't the start of a mention.
+pub(crate) fn wrap_mention(
+    content: &str,
+    offset: usize,
+    target_title: &str,
+) -> Result<String, TessellumError> {
+    let title = target_title.trim();
+    let mention = unlinked_mention_ranges(content, title)
+        .into_iter()
+        .find(|m| m.start == offset)
+        .ok_or_else(|| {
+            TessellumError::Validation(format!(
+                "No unlinked mention of '{}' at offset {}",
+                title, offset
+            ))
+        })?;
+
+    let text = &content[mention.clone()];
+    let link = if text == title {
+        format!("[[{}]]", title)
+    } else {
+        format!("[[{}|{}]]", title, text)
     };
+    Ok(format!("{}{}{}", &content[..mention.start], link, &content[mention.end..]))
+}
+
+async fn link_mention_inner(
+    state: &AppState,
+    kuzu_state: &ManagedGrafeoConnection,
+    vault_path: String,
+    path: String,
+    occurrence_offset: usize,
+    target_title: String,
+) -> Result<String, TessellumError> {
+    crate::utils::validate_vault_root(&vault_path)?;
+    crate::utils::validate_path_in_vault(&path, &vault_path).map_err(TessellumError::Validation)?;
+
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| TessellumError::Internal(format!("Failed to read '{}': {}", path, e)))?;
+    let content = crate::utils::frontmatter::strip_bom(&content);
+    let updated = wrap_mention(content, occurrence_offset, &target_title)?;
+
+    write_and_index_note(state, kuzu_state, vault_path, path, updated.clone()).await?;
+    Ok(updated)
+}
+
+/// Turn one unlinked mention (from `get_unlinked_mentions`) into a wikilink.
+///
+/// Saves and re-indexes the note, then returns the new content so the editor
+/// can refresh.
+#[tauri::command]
+pub async fn link_mention(
+    state: State<'_, AppState>,
+    kuzu_state: State<'_, ManagedGrafeoConnection>,
+    vault_path: String,
+    path: String,
+    occurrence_offset: usize,
+    target_title: String,
+) -> Result<String, TessellumError> {
+    link_mention_inner(&state, &kuzu_state, vault_path, path, occurrence_offset, target_title).await
+}
+
+/// Resolves a wikilink target to its full path.
+/// Uses the cached in-memory FileIndex for fast lookup without traversing the filesystem.
+///
+/// Notes resolve under the vault's configured link strategy. `source_path` is
+/// the linking note; without it, relative links fall back to shortest-path rules.
+#[tauri::command]
+pub async fn resolve_wikilink(
+    state: State<'_, AppState>,
+    vault_path: String,
+    target: String,
+    source_path: Option<String>,
+) -> Result<Option<String>, TessellumError> {
+    let source = source_path.as_deref().unwrap_or(&vault_path);
+    let strategy = crate::utils::config::read_config(&vault_path).link_resolution;
+    let resolved_note = state
+        .current_file_index(&vault_path)
+        .await?
+        .resolve_with(&vault_path, &target, Path::new(source), strategy);
+
+    if let Some(path) = resolved_note {
+        return Ok(Some(crate::utils::normalize_path(&path.to_string_lossy())));
+    }
+
+    // Wikilinks can target media too (e.g. [[image.png]]), so fall back to the asset index.
+    let resolved_asset = state
+        .current_asset_index(&vault_path)
+        .await?
+        .resolve(&vault_path, &target);
 
 	Ok(resolved_asset
         .map(|p| crate::utils::normalize_path(&p.to_string_lossy())))
@@ -127,7 +1016,159 @@ pub async fn resolve_wikilink(
 
 #[cfg(test)]
 mod tests {
-    use super::extract_wikilinks;
+    use std::path::PathBuf;
+
+    use super::{
+        backlink_lines, describe_outgoing_links, extract_markdown_links, extract_wikilinks,
+        link_mention_inner, links_status, mention_snippet, partition_attachment_embeds,
+        suggest_links_inner, unlinked_mention_ranges, vault_health, BacklinkLine, BrokenLink,
+        LinkSuggestion, LinksStatus, OutgoingLink, ResolvedLink,
+    };
+    use crate::db::Database;
+    use crate::grafeo_projection::ManagedGrafeoConnection;
+    use crate::models::{AssetIndex, FileIndex, LinkStrategy};
+    use crate::test_support::{TestAppState, TestVault};
+
+    #[test]
+    fn flags_embeds_and_splits_off_attachments() {
+        let vault = TestVault::new().with_markdown("Note.md", "# Note").build();
+        std::fs::write(vault.path().join("diagram.png"), "png").unwrap();
+        let vault_path = vault.path().to_string_lossy().to_string();
+
+        let links = extract_wikilinks(r"![[diagram.png]] ![[Note]] [[Note]] !\[[skipped.png]]");
+        assert_eq!(
+            links.iter().map(|link| link.is_embed).collect::<Vec<_>>(),
+            vec![true, true, false]
+        );
+
+        let (kept, embeds) = partition_attachment_embeds(
+            links,
+            &FileIndex::build(&vault_path).unwrap(),
+            &AssetIndex::build(&vault_path).unwrap(),
+            &vault_path,
+            vault.path(),
+            LinkStrategy::ShortestPath,
+        );
+        assert_eq!(kept.iter().map(|link| link.target.as_str()).collect::<Vec<_>>(), vec!["Note", "Note"]);
+        assert_eq!(
+            embeds,
+            vec![crate::utils::normalize_path(&vault.path().join("diagram.png").to_string_lossy())]
+        );
+    }
+
+    #[tokio::test]
+    async fn vault_health_reports_orphans_and_dangling_targets() {
+        let db = Database::init_memory().await.unwrap();
+        db.index_file("Vault/Alpha.md", 1, 10, None, None, &["Vault/Missing.md".to_string()])
+            .await
+            .unwrap();
+        db.index_file("Vault/Lonely.md", 1, 10, None, None, &[]).await.unwrap();
+
+        let health = vault_health(&db).await.unwrap();
+
+        assert_eq!(health.orphans, vec!["Vault/Lonely.md".to_string()]);
+        assert_eq!(health.orphan_count, 1);
+        assert_eq!(
+            health.broken_links,
+            vec![BrokenLink {
+                source: "Vault/Alpha.md".to_string(),
+                target: "Vault/Missing.md".to_string(),
+            }]
+        );
+        assert_eq!(health.broken_link_count, 1);
+    }
+
+    #[tokio::test]
+    async fn links_status_splits_resolved_and_dangling_targets() {
+        let vault = TestVault::new()
+            .with_markdown("Beta.md", "# Beta")
+            .with_markdown("Gamma.md", "# Gamma")
+            .build();
+        std::fs::write(vault.path().join("pic.png"), "png").unwrap();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let note = |name: &str| crate::utils::normalize_path(&vault.path().join(name).to_string_lossy());
+        let content = "[[Beta]] [[Missing]] [[Beta#Intro]] ![[pic.png]] [[Gamma]] [[#Local]]";
+
+        // Gamma was linked after the last index run.
+        let db = Database::init_memory().await.unwrap();
+        db.index_file(&note("Beta.md"), 1, 10, None, None, &[]).await.unwrap();
+        db.index_file(&note("Alpha.md"), 1, 10, None, None, &[note("Beta.md"), note("Missing.md")])
+            .await
+            .unwrap();
+
+        let status = links_status(
+            &db,
+            &vault_path,
+            &note("Alpha.md"),
+            content,
+            &FileIndex::build(&vault_path).unwrap(),
+            &AssetIndex::build(&vault_path).unwrap(),
+            LinkStrategy::ShortestPath,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            status,
+            LinksStatus {
+                resolved: vec![
+                    ResolvedLink { target: "Beta".to_string(), path: note("Beta.md") },
+                    ResolvedLink { target: "Gamma".to_string(), path: note("Gamma.md") },
+                ],
+                dangling: vec!["Missing".to_string()],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn suggest_links_ranks_recent_then_popular_notes() {
+        let vault = TestVault::new()
+            .with_markdown("Plan.md", "# Plan")
+            .with_markdown("Projects/Plan.md", "---\naliases:\n  - Planning Doc\n---\n# Plan")
+            .with_markdown("Playbook.md", "# Playbook")
+            .with_markdown("Other.md", "# Planet Notes")
+            .build();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let note = |name: &str| crate::utils::normalize_path(&vault.path().join(name).to_string_lossy());
+
+        let db = Database::init_memory().await.unwrap();
+        for name in ["Plan.md", "Projects/Plan.md", "Playbook.md", "Other.md"] {
+            db.index_file(&note(name), 1, 10, None, None, &[]).await.unwrap();
+        }
+        db.index_file(&note("Other.md"), 1, 10, None, None, &[note("Playbook.md")]).await.unwrap();
+        db.set_note_title(&note("Other.md"), "Planet Notes").await.unwrap();
+        db.set_note_title(&note("Playbook.md"), "Playbook").await.unwrap();
+        db.record_open(&note("Projects/Plan.md"), 100).await.unwrap();
+
+        let mut file_index = FileIndex::build(&vault_path).unwrap();
+        file_index.set_aliases(vault.path().join("Projects/Plan.md"), &["Planning Doc".to_string()]);
+        let suggestions =
+            suggest_links_inner(&db, &vault_path, &file_index, LinkStrategy::ShortestPath, "PL", 10)
+                .await
+                .unwrap();
+
+        let suggestion = |display: &str, link: &str, path: &str| LinkSuggestion {
+            display: display.to_string(),
+            link: link.to_string(),
+            path: note(path),
+        };
+        assert_eq!(
+            suggestions,
+            vec![
+                suggestion("Plan", "Projects/Plan", "Projects/Plan.md"),
+                suggestion("Planning Doc", "Planning Doc", "Projects/Plan.md"),
+                suggestion("Playbook", "Playbook", "Playbook.md"),
+                suggestion("Plan", "Plan", "Plan.md"),
+                suggestion("Planet Notes", "Other", "Other.md"),
+            ]
+        );
+
+        let capped =
+            suggest_links_inner(&db, &vault_path, &file_index, LinkStrategy::ShortestPath, "pla", 1)
+                .await
+                .unwrap();
+        assert_eq!(capped, vec![suggestion("Plan", "Projects/Plan", "Projects/Plan.md")]);
+    }
 
     #[test]
     fn extracts_plain_and_aliased_wikilinks() {
@@ -148,4 +1189,218 @@ mod tests {
         assert_eq!(links[0].target, "Folder/Note");
         assert_eq!(links[0].alias.as_deref(), Some("Alias"));
     }
+
+    #[test]
+    fn parses_heading_and_block_anchors_alongside_aliases() {
+        let links = extract_wikilinks("[[Note#Section]] [[Note#^abc123]] [[Note#Section|text]] [[#Local]]");
+
+        assert_eq!(links.len(), 4);
+        assert_eq!(links[0].target, "Note");
+        assert_eq!(links[0].heading.as_deref(), Some("Section"));
+        assert_eq!(links[0].block_id, None);
+        assert_eq!(links[1].target, "Note");
+        assert_eq!(links[1].heading, None);
+        assert_eq!(links[1].block_id.as_deref(), Some("abc123"));
+        assert_eq!(links[2].target, "Note");
+        assert_eq!(links[2].heading.as_deref(), Some("Section"));
+        assert_eq!(links[2].alias.as_deref(), Some("text"));
+        assert_eq!(links[3].target, "");
+        assert_eq!(links[3].heading.as_deref(), Some("Local"));
+
+        let index = FileIndex::from_markdown_paths(["/vault/Note.md"]);
+        assert_eq!(
+            index.resolve("/vault", "Note#^abc123"),
+            Some(PathBuf::from("/vault/Note.md"))
+        );
+        assert_eq!(index.resolve("/vault", "#Local"), None);
+    }
+
+    #[test]
+    fn describes_aliases_and_dangling_links() {
+        let vault = TestVault::new()
+            .with_markdown("Projects/Plan.md", "# Plan")
+            .build();
+        std::fs::write(vault.path().join("pic.png"), "png").unwrap();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let plan = crate::utils::normalize_path(&vault.path().join("Projects/Plan.md").to_string_lossy());
+        let pic = crate::utils::normalize_path(&vault.path().join("pic.png").to_string_lossy());
+
+        let links = describe_outgoing_links(
+            "---\nup: \"[[Plan]]\"\n---\n[[Plan#Goals|the plan]] [[Missing]] ![[pic.png]]",
+            &vault.path().join("Index.md"),
+            &vault_path,
+            &FileIndex::build(&vault_path).unwrap(),
+            &AssetIndex::build(&vault_path).unwrap(),
+            LinkStrategy::ShortestPath,
+        );
+
+        assert_eq!(
+            links,
+            vec![
+                OutgoingLink {
+                    display: "Plan".to_string(),
+                    target: "Plan".to_string(),
+                    path: Some(plan.clone()),
+                    resolved: true,
+                    is_embed: false,
+                    field: Some("up".to_string()),
+                },
+                OutgoingLink {
+                    display: "the plan".to_string(),
+                    target: "Plan#Goals".to_string(),
+                    path: Some(plan),
+                    resolved: true,
+                    is_embed: false,
+                    field: None,
+                },
+                OutgoingLink {
+                    display: "Missing".to_string(),
+                    target: "Missing".to_string(),
+                    path: None,
+                    resolved: false,
+                    is_embed: false,
+                    field: None,
+                },
+                OutgoingLink {
+                    display: "pic.png".to_string(),
+                    target: "pic.png".to_string(),
+                    path: Some(pic),
+                    resolved: true,
+                    is_embed: true,
+                    field: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn backlink_lines_list_each_linking_line_once() {
+        let index = FileIndex::from_markdown_paths(["/vault/Plan.md", "/vault/Other.md"]);
+        let content = "# Notes\nSee [[Plan]] and [[Plan#Goals|goals]].\n[[Other]] only\n`[[Plan]]`\n  - Follow up on [[Plan]]  \n";
+
+        let source = std::path::Path::new("/vault/Notes.md");
+        let lines = backlink_lines(content, source, "/vault/Plan.md", "/vault", &index, LinkStrategy::ShortestPath);
+
+        assert_eq!(
+            lines,
+            vec![
+                BacklinkLine {
+                    line: 2,
+                    text: "See [[Plan]] and [[Plan#Goals|goals]].".to_string(),
+                },
+                BacklinkLine {
+                    line: 5,
+                    text: "- Follow up on [[Plan]]".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn backlink_lines_follow_the_configured_link_strategy() {
+        let index = FileIndex::from_markdown_paths(["/vault/Projects/Plan.md"]);
+        let content = "[[Plan]]\n[[Projects/Plan]]\n";
+        let source = std::path::Path::new("/vault/Notes.md");
+
+        let lines = backlink_lines(content, source, "/vault/Projects/Plan.md", "/vault", &index, LinkStrategy::AbsolutePath);
+
+        assert_eq!(
+            lines,
+            vec![BacklinkLine {
+                line: 2,
+                text: "[[Projects/Plan]]".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn skips_wikilinks_inside_code() {
+        let content = "[[Real]] `[[Inline]]`\n\n```md\n[[Fenced]]\n```\n\n    [[Indented]]\n\n~~~\n[[Tilde]]\n~~~\nAfter [[Also Real]]";
+
+        let targets: Vec<String> = extract_wikilinks(content)
+            .into_iter()
+            .map(|link| link.target)
+            .collect();
+
+        assert_eq!(targets, vec!["Real".to_string(), "Also Real".to_string()]);
+    }
+
+    #[test]
+    fn extracts_relative_markdown_note_links() {
+        let body = "[Plan](../Projects/Plan.md) [Home](/Home.md#top) [Same](Sibling%20Note.md)\n\
+            [Web](https://example.com/page.md) [Mail](mailto:me@example.com) ![img](pic.png)\n\
+            [Doc](report.pdf) [Out](../../outside.md) `[Code](Code.md)` [Again](Sibling%20Note.md)\n";
+
+        let links = extract_markdown_links(body, "/vault/Notes/Daily.md", "/vault");
+
+        assert_eq!(
+            links,
+            vec![
+                "/vault/Projects/Plan.md".to_string(),
+                "/vault/Home.md".to_string(),
+                "/vault/Notes/Sibling Note.md".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_only_plain_whole_word_mentions() {
+        let content = "---\ntitle: Rust notes\n---\nI like rust. [[Rust]] and [[Other|rust]] too.\n`rust` [docs](rust.md) trusty\n```\nrust\n```\nRUST again";
+
+        let mentions = unlinked_mention_ranges(content, "Rust");
+
+        let found: Vec<&str> = mentions.iter().map(|m| &content[m.clone()]).collect();
+        assert_eq!(found, vec!["rust", "RUST"]);
+        assert_eq!(
+            mention_snippet(content, &mentions[0]),
+            "I like rust. [[Rust]] and [[Other|rust]] too."
+        );
+        assert_eq!(mention_snippet(content, &mentions[1]), "RUST again");
+    }
+
+    #[tokio::test]
+    async fn link_mention_wraps_one_occurrence_and_reindexes() {
+        let vault = TestVault::new()
+            .with_markdown("Rust.md", "# Rust")
+            .with_markdown("Journal.md", "Learning rust today. `rust` stays code.")
+            .build();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let journal = vault.path().join("Journal.md").to_string_lossy().to_string();
+        let state = TestAppState::new().await;
+        let grafeo_state = ManagedGrafeoConnection::default();
+
+        let code_offset = "Learning rust today. `".len();
+        assert!(link_mention_inner(
+            &state,
+            &grafeo_state,
+            vault_path.clone(),
+            journal.clone(),
+            code_offset,
+            "Rust".to_string(),
+        )
+        .await
+        .is_err());
+
+        let updated = link_mention_inner(
+            &state,
+            &grafeo_state,
+            vault_path,
+            journal.clone(),
+            "Learning ".len(),
+            "Rust".to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(updated, "Learning [[Rust|rust]] today. `rust` stays code.");
+        assert_eq!(std::fs::read_to_string(&journal).unwrap(), updated);
+        let backlinks = state
+            .db
+            .get_backlinks(&crate::utils::normalize_path(
+                &vault.path().join("Rust.md").to_string_lossy(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(backlinks, vec![crate::utils::normalize_path(&journal)]);
+    }
 }
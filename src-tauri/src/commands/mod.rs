@@ -13,31 +13,40 @@ pub mod pdf_export;
 pub mod plugins;
 pub mod publish;
 pub mod recovery;
+pub mod render;
 pub mod scripts;
 pub mod semantic;
+pub mod settings;
 pub mod sync;
 pub mod templates;
 pub mod vault;
 pub mod watcher;
 pub mod search;
 
-pub use assets::{resolve_asset, save_asset};
+pub use assets::{resolve_asset, save_asset, save_attachment};
 pub use clipboard::{import_clipboard_files, write_file_paths_to_clipboard};
-pub use folders::create_folder;
-pub use graph::get_graph_data;
+pub use folders::{create_folder, get_folder_size};
+pub use graph::{find_cycles, get_graph_data, get_graph_data_filtered, get_local_graph, path_between};
 pub use links::{
-	extract_wikilinks, get_all_links, get_backlinks, get_outgoing_links, resolve_wikilink,
+	extract_markdown_links, extract_wikilinks, find_duplicate_names, get_all_links, get_backlinks,
+	get_backlinks_with_context, get_links_status, get_most_linked, get_outgoing_links,
+	get_outgoing_links_detailed, get_related_notes, get_unlinked_mentions, get_vault_health,
+	link_mention, partition_attachment_embeds, resolve_wikilink, suggest_links,
 };
 pub use notes::{
-	create_note, get_all_notes, get_or_create_daily_note, get_all_property_keys, get_all_tags,
-	get_file_tags, list_trash_items, read_file, restore_trash_item, search_notes, trash_item,
-	trash_items, write_file, delete_trash_item_permanently,
+	append_to_note, create_note, create_note_in, duplicate_note, get_all_notes, get_or_create_daily_note,
+	get_all_property_keys, get_all_tags, get_file_tags, get_note_metadata, get_note_stats,
+	get_tag_counts, get_notes_by_tag, get_notes_modified_between, get_tag_hierarchy, get_vault_stats,
+	get_recent_files, list_trash_items, read_file, read_file_lossy, open_note, record_open, rename_tag,
+	restore_trash_item, search_notes, trash_item, trash_items, write_file, write_file_fast, write_file_with_links,
+	delete_trash_item_permanently, empty_trash, empty_trash_older_than,
 };
 pub use pdf_export::export_markdown_pdf;
+pub use settings::{load_settings, save_settings, set_log_level};
 pub use templates::{create_note_from_template, list_templates};
 pub use vault::{
-	ensure_feature_demo_in_empty_vault, list_files, list_files_tree, move_items, rename_file,
-	set_vault_path,
+	ensure_feature_demo_in_empty_vault, find_paths, list_files, list_files_tree, move_item,
+	move_items, preview_rename_link_updates, rename_file, rename_to_heading, set_vault_path,
 };
 pub use watcher::{watch_vault, unwatch_vault};
-pub use search::{search_full_text, search_tags, rebuild_search_index};
+pub use search::{search_full_text, search_note_contents, search_tags, rebuild_search_index};
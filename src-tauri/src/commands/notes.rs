@@ -1,35 +1,88 @@
 use chrono::Local;
+use pulldown_cmark::{Event, Options, Parser, TagEnd};
 use serde::Serialize;
 use std::collections::HashSet;
 use std::fs;
 use std::path::{Component, Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
 use tauri::async_runtime;
 use tokio::time::{Duration, timeout};
 use walkdir::WalkDir;
-use crate::commands::extract_wikilinks;
+use crate::utils::WIKILINK_RE;
+use crate::commands::{extract_markdown_links, extract_wikilinks, partition_attachment_embeds};
+use crate::commands::history::save_previous_version;
 use crate::commands::templates::{apply_placeholders, templates_dir};
-use crate::error::TessellumError;
+use crate::error::{FileError, FileErrorKind, TessellumError};
 use crate::indexer::VaultIndexer;
 use crate::grafeo_projection::{
     ManagedGrafeoConnection, sync_full, sync_link_create, sync_link_delete, sync_note_delete,
     sync_note_upsert,
 };
-use crate::models::{AppState, FileIndex, FileMetadata};
+use crate::models::{AppState, FileIndex, FileMetadata, LinkStrategy, WikiLink};
 use crate::search::SearchDoc;
 use crate::trash::{
     build_restored_destination_path, generate_unique_trash_path, parse_trash_entry_name,
-    parse_trash_timestamp, permanently_delete_trash_entry, rename_recursively,
-    restore_trashed_names_recursively, ParsedTrashName,
+    parse_trash_timestamp, permanently_delete_trash_entry, purge_all_trash, purge_expired_trash,
+    rename_recursively, restore_trashed_names_recursively, ParsedTrashName, PurgeReport,
+};
+use crate::utils::config::{load_or_init_config, read_config, AppConfig};
+use crate::utils::frontmatter::{note_title, strip_bom, title_from_path};
+use crate::utils::{
+    extract_tags, is_hidden_or_special, normalize_tag, rename_tag_in_content, sanitize_string,
+    validate_path_in_vault, validate_target_dir, validate_vault_root, IgnoreRules,
 };
-use crate::utils::config::load_or_init_config;
-use crate::utils::{extract_tags, sanitize_string, validate_path_in_vault};
 
 struct NoteSyncDelta {
     note_id: String,
     previous_links: Vec<String>,
     current_links: Vec<String>,
+    link_resolution: LinkResolution,
+}
+
+/// Which wikilinks in a saved note point at an existing note.
+///
+/// Both sides count distinct targets as typed, so a target linked twice
+/// counts once and `resolved + unresolved.len()` is the number of targets.
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct LinkResolution {
+    /// Distinct link targets (as typed) that matched a note.
+    pub resolved: usize,
+    /// Link targets (as typed) that matched no note, deduplicated in order.
+    pub unresolved: Vec<String>,
+}
+
+/// Resolve link targets written in the note at `source` to note paths, falling
+/// back to the would-be path for broken links so they can still be indexed as
+/// dangling edges.
+fn resolve_link_targets<'a>(
+    file_index: &FileIndex,
+    vault_path: &str,
+    source: &Path,
+    strategy: LinkStrategy,
+    targets: impl IntoIterator<Item = &'a str>,
+) -> (Vec<String>, LinkResolution) {
+    let mut paths = Vec::new();
+    let mut resolution = LinkResolution::default();
+    let mut resolved_targets = HashSet::new();
+    for target in targets {
+        let path = match file_index.resolve_with(vault_path, target, source, strategy) {
+            Some(path) => {
+                if resolved_targets.insert(target) {
+                    resolution.resolved += 1;
+                }
+                path
+            }
+            None => {
+                if !resolution.unresolved.iter().any(|t| t == target) {
+                    resolution.unresolved.push(target.to_string());
+                }
+                file_index.resolve_or_default_with(vault_path, target, source, strategy)
+            }
+        };
+        paths.push(crate::utils::normalize_path(&path.to_string_lossy()));
+    }
+    (paths, resolution)
 }
 
 #[derive(Serialize)]
@@ -38,6 +91,22 @@ pub struct TrashItemsResult {
     failed: Vec<TrashItemFailure>,
 }
 
+/// What an empty-trash run removed.
+#[derive(Serialize, Debug)]
+pub struct EmptyTrashResult {
+    pub files_removed: usize,
+    pub bytes_freed: u64,
+}
+
+impl From<PurgeReport> for EmptyTrashResult {
+    fn from(report: PurgeReport) -> Self {
+        Self {
+            files_removed: report.files_removed,
+            bytes_freed: report.bytes_freed,
+        }
+    }
+}
+
 #[derive(Serialize)]
 pub struct TrashItemFailure {
     item_path: String,
@@ -51,6 +120,8 @@ pub struct TrashItemMetadata {
     display_name: String,
     original_name: String,
     parent_label: String,
+    /// Vault-relative folder the item was deleted from; empty for the vault root.
+    original_folder: String,
     restore_path: String,
     is_dir: bool,
     timestamp: u128,
@@ -95,19 +166,19 @@ fn candidate_directory_priority(path: &Path) -> (usize, String) {
 }
 
 fn resolve_restore_directory(vault_root: &Path, parsed: &ParsedTrashName) -> PathBuf {
-    // New format: use the encoded relative path directly.
+    // New format: use the encoded relative path directly. If the original
+    // folder was deleted as well it is recreated by the caller.
     if let Some(rel_dir) = &parsed.relative_dir {
         if rel_dir.is_empty() {
             return vault_root.to_path_buf();
         }
-        let full_path = vault_root.join(rel_dir);
-        if full_path.is_dir() {
-            return full_path;
+        if validate_relative_note_path(rel_dir).is_ok() {
+            return vault_root.join(rel_dir);
         }
-        // Original path no longer exists — fall back to name-search heuristic below.
+        // A crafted name must not restore outside the vault — fall back to name search.
         log::warn!(
-            "Restore path '{}' no longer exists; falling back to name search",
-            full_path.display()
+            "Restore path '{}' is not a plain relative path; falling back to name search",
+            rel_dir
         );
     }
 
@@ -161,6 +232,11 @@ fn build_trash_item_metadata(vault_root: &Path, entry_path: &Path) -> Option<Tra
     let parsed = parse_trash_entry_name(&filename, is_dir)?;
     let restore_dir = resolve_restore_directory(vault_root, &parsed);
     let restore_path = restore_dir.join(&parsed.original_name);
+    let original_folder = match &parsed.relative_dir {
+        Some(rel_dir) => rel_dir.clone(),
+        None if parsed.parent_label.eq_ignore_ascii_case("root") => String::new(),
+        None => parsed.parent_label.clone(),
+    };
     
     Some(TrashItemMetadata {
         path: crate::utils::normalize_path(&entry_path.to_string_lossy()),
@@ -168,6 +244,7 @@ fn build_trash_item_metadata(vault_root: &Path, entry_path: &Path) -> Option<Tra
         display_name: parsed.original_name.clone(),
         original_name: parsed.original_name.clone(),
         parent_label: parsed.parent_label,
+        original_folder,
         restore_path: crate::utils::normalize_path(&restore_path.to_string_lossy()),
         is_dir,
         timestamp,
@@ -225,12 +302,25 @@ async fn refresh_indexes_after_restore(
     let db = state.db.clone();
     let search_index = state.search_index.clone();
     
-    if let Err(error) = VaultIndexer::full_sync(db.as_ref(), search_index, vault_path).await {
-        eprintln!("Vault sync failed after restore: {}", error);
+    let indexing = load_or_init_config(vault_path)
+        .map(|config| config.indexing)
+        .unwrap_or_default();
+    
+    if let Err(error) =
+        VaultIndexer::full_sync_with_config(
+            db.as_ref(),
+            search_index,
+            vault_path,
+            &indexing,
+            &std::sync::atomic::AtomicBool::new(false),
+        )
+        .await
+    {
+        log::warn!("Vault sync failed after restore: {}", error);
     }
     
     if let Err(error) = sync_full(kuzu_state.inner(), db.as_ref()).await {
-        eprintln!("Kuzu sync_full failed after restore: {}", error);
+        log::warn!("Kuzu sync_full failed after restore: {}", error);
     }
     
     let mut idx_guard = state.file_index.lock().await;
@@ -319,7 +409,7 @@ fn validate_template_name(template_name: &str) -> Result<(), TessellumError> {
 }
 
 async fn index_note_content(
-    state: &State<'_, AppState>,
+    state: &AppState,
     vault_path: &str,
     path: &str,
     content: &str,
@@ -350,12 +440,16 @@ async fn index_note_content(
     
     let mut frontmatter_json_str = None;
     let mut body_content = content;
+    let mut frontmatter_links = Vec::new();
+    let mut aliases = Vec::new();
     
     if let Some((yaml, _)) = crate::utils::frontmatter::parse_frontmatter(content) {
         body_content = crate::utils::frontmatter::strip_frontmatter(content);
         if let Ok(json) = crate::utils::frontmatter::frontmatter_to_json(&yaml) {
             frontmatter_json_str = Some(json);
         }
+        frontmatter_links = crate::utils::frontmatter::extract_frontmatter_links(&yaml);
+        aliases = crate::utils::frontmatter::extract_aliases(&yaml);
     }
     
     let inline_tags = extract_tags(content);
@@ -366,48 +460,79 @@ async fn index_note_content(
         serde_json::to_string(&inline_tags).ok()
     };
     
-    let wikilinks = extract_wikilinks(body_content);
-    
-    let index_guard = state.file_index.lock().await;
-    let file_index = match index_guard.as_ref() {
-        Some(idx) => idx.clone(),
-        None => {
-            drop(index_guard);
-            let idx = FileIndex::build(vault_path).map_err(|e| {
-                TessellumError::Internal(format!("Failed to build file index: {}", e))
-            })?;
-            let mut guard = state.file_index.lock().await;
-            *guard = Some(idx.clone());
-            idx
-        }
-    };
+    // `[[#Heading]]` points inside this note and is not a graph edge.
+    let wikilinks: Vec<_> = extract_wikilinks(body_content)
+        .into_iter()
+        .filter(|link| !link.target.is_empty())
+        .collect();
+    
+    let file_index = state.current_file_index(vault_path).await?;
+    let asset_index = state.current_asset_index(vault_path).await?;
+    let strategy = read_config(vault_path).link_resolution;
+    let (wikilinks, embeds) = partition_attachment_embeds(
+        wikilinks,
+        &file_index,
+        &asset_index,
+        vault_path,
+        Path::new(path),
+        strategy,
+    );
     
-    let resolved_links: Vec<String> = wikilinks
+    // Body links first, then frontmatter-declared ones, resolved in one pass.
+    let (mut resolved_links, link_resolution) = resolve_link_targets(
+        &file_index,
+        vault_path,
+        Path::new(path),
+        strategy,
+        wikilinks
+            .iter()
+            .map(|link| link.target.as_str())
+            .chain(frontmatter_links.iter().map(|(_, target)| target.as_str())),
+    );
+    let field_targets = resolved_links.split_off(wikilinks.len());
+    let link_fields: Vec<(String, String)> = field_targets
+        .into_iter()
+        .zip(frontmatter_links.into_iter().map(|(field, _)| field))
+        .collect();
+    // `[label](note.md)` links already name a path and feed the same edges.
+    resolved_links.extend(extract_markdown_links(body_content, path, vault_path));
+    let mut deduped_links: Vec<String> = resolved_links
         .iter()
-        .map(|link| {
-            crate::utils::normalize_path(
-                &file_index
-                    .resolve_or_default(vault_path, &link.target)
-                    .to_string_lossy(),
-            )
-        })
+        .chain(link_fields.iter().map(|(target, _)| target))
+        .cloned()
         .collect();
-    let mut deduped_links = resolved_links.clone();
     deduped_links.sort();
     deduped_links.dedup();
     
     db
-        .index_file(
+        .index_file_with_link_fields(
             path,
             modified,
             size,
             frontmatter_json_str.as_deref(),
             inline_tags_json_str.as_deref(),
             &resolved_links,
+            &link_fields,
+            &embeds,
         )
         .await
         .map_err(TessellumError::from)?;
+    db
+        .set_note_title(path, &note_title(content, path))
+        .await
+        .map_err(TessellumError::from)?;
+    db
+        .set_note_aliases(path, &aliases)
+        .await
+        .map_err(TessellumError::from)?;
+    if let Some(idx) = state.file_index.lock().await.as_mut() {
+        idx.set_aliases(path, &aliases);
+    }
     
+    db
+        .replace_note_bodies(&[(path, body_content)])
+        .await
+        .map_err(TessellumError::from)?;
     db
         .set_note_tags(path, &inline_tags)
         .await
@@ -448,17 +573,18 @@ async fn index_note_content(
         note_id: crate::utils::normalize_path(path),
         previous_links,
         current_links: deduped_links,
+        link_resolution,
     })
 }
 
 async fn sync_note_delta_non_critical(
-    state: &State<'_, AppState>,
-    kuzu_state: &State<'_, ManagedGrafeoConnection>,
+    state: &AppState,
+    kuzu_state: &ManagedGrafeoConnection,
     delta: NoteSyncDelta,
 ) {
     let db = state.db.clone();
-    if let Err(err) = sync_note_upsert(kuzu_state.inner(), db.as_ref(), &delta.note_id).await {
-        eprintln!(
+    if let Err(err) = sync_note_upsert(kuzu_state, db.as_ref(), &delta.note_id).await {
+        log::warn!(
             "Kuzu sync_note_upsert failed for '{}': {}",
             delta.note_id, err
         );
@@ -469,8 +595,8 @@ async fn sync_note_delta_non_critical(
     let current: HashSet<String> = delta.current_links.into_iter().collect();
     
     for to_id in current.difference(&previous) {
-        if let Err(err) = sync_link_create(kuzu_state.inner(), &delta.note_id, to_id) {
-            eprintln!(
+        if let Err(err) = sync_link_create(kuzu_state, &delta.note_id, to_id) {
+            log::warn!(
                 "Kuzu sync_link_create failed for '{} -> {}': {}",
                 delta.note_id, to_id, err
             );
@@ -478,8 +604,8 @@ async fn sync_note_delta_non_critical(
     }
     
     for to_id in previous.difference(&current) {
-        if let Err(err) = sync_link_delete(kuzu_state.inner(), &delta.note_id, to_id) {
-            eprintln!(
+        if let Err(err) = sync_link_delete(kuzu_state, &delta.note_id, to_id) {
+            log::warn!(
                 "Kuzu sync_link_delete failed for '{} -> {}': {}",
                 delta.note_id, to_id, err
             );
@@ -487,45 +613,151 @@ async fn sync_note_delta_non_critical(
     }
 }
 
+/// `name` without a trailing `.md`, matched in any case (`Note.MD` -> `Note`).
+pub(crate) fn strip_md_suffix(name: &str) -> &str {
+    match name.get(name.len().saturating_sub(3)..) {
+        Some(suffix) if suffix.eq_ignore_ascii_case(".md") => &name[..name.len() - 3],
+        _ => name,
+    }
+}
+
+/// First free `Title.md`, `Title (1).md`, ... path in `dir`.
+///
+/// `title` must already be sanitized; a trailing `.md` in any case is not doubled.
+pub(crate) fn unique_note_path(dir: &Path, title: &str) -> PathBuf {
+    let stem = strip_md_suffix(title);
+    let mut file_path = dir.join(format!("{}.md", stem));
+    let mut collision_index = 1;
+    
+    // Check for collisions in the filenames
+    while file_path.exists() {
+        file_path = dir.join(format!("{} ({}).md", stem, collision_index));
+        collision_index += 1;
+    }
+    
+    file_path
+}
+
+/// Outcome of `create_note`.
+///
+/// `sanitized_title` is the title after `sanitize_string`, and `was_modified`
+/// is set when that differs from what the user typed, so the UI can say the
+/// name was changed. A numeric collision suffix only shows up in `path`.
+#[derive(Serialize, Debug)]
+pub struct CreateNoteResult {
+    pub path: String,
+    pub sanitized_title: String,
+    pub was_modified: bool,
+}
+
 /// Creates a new note file in the specified vault directory with a unique name.
 ///
 /// This function takes in a vault path and a title string to create a new `.md`
-/// file in the specified vault directory. If the provided title contains
-/// invalid characters or is empty, it is sanitized or defaulted to "Untitled".
-/// If a file with the same name already exists, the function appends a numeric suffix
-/// to the filename to ensure its uniqueness.
+/// file in the specified vault directory. Invalid characters are stripped from
+/// the title; a title with nothing left after that is rejected with a
+/// validation error rather than renamed. If a file with the same name already
+/// exists, the function appends a numeric suffix to the filename to ensure its
+/// uniqueness. When the vault config names a `templates.default_template`, the
+/// note is seeded from it; otherwise it starts empty.
+///
+/// `vault_path` is the folder the note goes in; it must resolve inside the
+/// vault at `vault_root`.
 #[tauri::command]
 pub async fn create_note(
     state: State<'_, AppState>,
     kuzu_state: State<'_, ManagedGrafeoConnection>,
     vault_path: String,
     title: String,
-) -> Result<String, TessellumError> {
-    validate_path_in_vault(&vault_path, &vault_path).map_err(TessellumError::Validation)?;
+    vault_root: String,
+) -> Result<CreateNoteResult, TessellumError> {
+    validate_target_dir(&vault_path, &vault_root)?;
+    
+    create_note_inner(&state, &kuzu_state, &vault_root, Path::new(&vault_path), title).await
+}
+
+/// Creates a new note inside a folder of the vault.
+///
+/// `folder` is relative to the vault root (an absolute path inside the vault
+/// is accepted too) and must already exist; an empty folder means the root.
+/// Title sanitizing, name collisions and the default template work as in
+/// `create_note`. Returns the full path of the new note.
+#[tauri::command]
+pub async fn create_note_in(
+    state: State<'_, AppState>,
+    kuzu_state: State<'_, ManagedGrafeoConnection>,
+    vault_path: String,
+    folder: String,
+    title: String,
+) -> Result<CreateNoteResult, TessellumError> {
+    create_note_in_inner(&state, &kuzu_state, &vault_path, &folder, title).await
+}
+
+async fn create_note_in_inner(
+    state: &AppState,
+    kuzu_state: &ManagedGrafeoConnection,
+    vault_path: &str,
+    folder: &str,
+    title: String,
+) -> Result<CreateNoteResult, TessellumError> {
+    validate_vault_root(vault_path)?;
+    let requested = Path::new(vault_path).join(folder.trim());
+    if !requested.exists() {
+        return Err(TessellumError::NotFound(format!("Folder not found: {}", folder)));
+    }
+    let resolved = validate_path_in_vault(&requested.to_string_lossy(), vault_path)
+        .map_err(TessellumError::Validation)?;
+    if !resolved.is_dir() {
+        return Err(TessellumError::Validation(format!("Not a folder: {}", folder)));
+    }
+    // Rebuild the folder from the vault path so `..` never reaches the index.
+    let root = Path::new(vault_path).canonicalize()?;
+    let relative = resolved.strip_prefix(&root).unwrap_or(Path::new(""));
+    if is_hidden_or_special(relative) {
+        return Err(TessellumError::Validation(format!(
+            "Cannot create notes in hidden folder: {}",
+            folder
+        )));
+    }
     
-    let clean_title = sanitize_string(title);
+    create_note_inner(state, kuzu_state, vault_path, &Path::new(vault_path).join(relative), title).await
+}
+
+/// Shared body of `create_note` and `create_note_in`; `dir` is already validated.
+async fn create_note_inner(
+    state: &AppState,
+    kuzu_state: &ManagedGrafeoConnection,
+    vault_path: &str,
+    dir: &Path,
+    title: String,
+) -> Result<CreateNoteResult, TessellumError> {
+    let config = load_or_init_config(vault_path)?;
+    let clean_title = sanitize_string(title.clone(), &config.filenames);
     
     if clean_title.trim().is_empty() {
-        return Err(TessellumError::Validation(
-            "Title cannot be empty".to_string(),
-        ));
+        return Err(TessellumError::Validation(format!(
+            "Title '{}' has no characters usable in a file name",
+            title
+        )));
     }
+    let was_modified = clean_title != title;
     
-    // Create a file path
-    let mut filename = if clean_title.to_lowercase().ends_with(".md") {
-        clean_title.clone()
-    } else {
-        format!("{}.md", clean_title)
-    };
-    let mut file_path = Path::new(&vault_path).join(&filename);
-    let mut collision_index = 1;
+    let file_path = unique_note_path(dir, &clean_title);
     
-    // Check for collisions in the filenames
-    while file_path.exists() {
-        let stem = clean_title.strip_suffix(".md").unwrap_or(&clean_title);
-        filename = format!("{} ({}).md", stem, collision_index);
-        file_path = Path::new(&vault_path).join(&filename);
-        collision_index += 1;
+    if let Some(content) = default_template_content(&config, vault_path, &clean_title).await? {
+        tokio::fs::write(&file_path, &content)
+            .await
+            .map_err(TessellumError::from)?;
+        if let Some(idx) = state.file_index.lock().await.as_mut() {
+            idx.insert(&file_path);
+        }
+        let path_str = crate::utils::normalize_path(&file_path.to_string_lossy());
+        let delta = index_note_content(state, vault_path, &path_str, &content).await?;
+        sync_note_delta_non_critical(state, kuzu_state, delta).await;
+        return Ok(CreateNoteResult {
+            path: path_str,
+            sanitized_title: clean_title,
+            was_modified,
+        });
     }
     
     // Create an empty file
@@ -550,11 +782,10 @@ pub async fn create_note(
         .await
         .unwrap_or_else(|e| log::warn!("Failed to index search file: {}", e));
     
-    // Invalidate the cache since a new file exists
-    let mut idx_guard = state.file_index.lock().await;
-    *idx_guard = None;
-    let mut asset_guard = state.asset_index.lock().await;
-    *asset_guard = None;
+    // Keep the cached index current instead of rebuilding it
+    if let Some(idx) = state.file_index.lock().await.as_mut() {
+        idx.insert(&file_path);
+    }
     
     let search_index = state.search_index.clone();
     let title = Path::new(&path_str)
@@ -576,13 +807,123 @@ pub async fn create_note(
     });
     
     let db = state.db.clone();
-    if let Err(err) = sync_note_upsert(kuzu_state.inner(), db.as_ref(), &path_str).await {
-        eprintln!("Kuzu sync_note_upsert failed for '{}': {}", path_str, err);
+    if let Err(err) = sync_note_upsert(kuzu_state, db.as_ref(), &path_str).await {
+        log::warn!("Kuzu sync_note_upsert failed for '{}': {}", path_str, err);
     }
     
+    Ok(CreateNoteResult {
+        path: path_str,
+        sanitized_title: clean_title,
+        was_modified,
+    })
+}
+
+/// Content of the configured default template for a new note titled `title`.
+///
+/// `None` when no default is configured or its file is missing.
+async fn default_template_content(
+    config: &AppConfig,
+    vault_path: &str,
+    title: &str,
+) -> Result<Option<String>, TessellumError> {
+    let name = config.templates.default_template.trim();
+    if name.is_empty() {
+        return Ok(None);
+    }
+    let name = name.strip_suffix(".md").unwrap_or(name);
+    validate_template_name(name)?;
+    let template_path = templates_dir(vault_path).join(format!("{}.md", name));
+    if !template_path.is_file() {
+        log::warn!("Default template '{}' not found, creating an empty note", name);
+        return Ok(None);
+    }
+    let template = tokio::fs::read_to_string(&template_path)
+        .await
+        .map_err(TessellumError::from)?;
+    Ok(Some(apply_placeholders(&template, title, vault_path, Local::now())))
+}
+
+/// Copy a note next to the original as `Name (copy).md` and index the copy.
+///
+/// Collisions get the same numeric suffix as `create_note`
+/// (`Name (copy) (1).md`, ...).
+async fn duplicate_note_inner(
+    state: &AppState,
+    vault_path: &str,
+    path: &str,
+) -> Result<NoteSyncDelta, TessellumError> {
+    validate_vault_root(vault_path)?;
+    validate_path_in_vault(path, vault_path).map_err(TessellumError::Validation)?;
+
+    let original = Path::new(path);
+    let is_note = original
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md"));
+    if !original.is_file() || !is_note {
+        return Err(TessellumError::Validation(
+            "Only notes can be duplicated".to_string(),
+        ));
+    }
+    let parent = original.parent().ok_or_else(|| {
+        TessellumError::Validation("Invalid path: No parent directory".to_string())
+    })?;
+    let stem = original
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let file_path = unique_note_path(parent, &format!("{} (copy)", stem));
+
+    // Validate destination is also inside the vault
+    let vault_canonical = Path::new(vault_path)
+        .canonicalize()
+        .map_err(|_| TessellumError::Validation("Invalid vault path".to_string()))?;
+    let parent_canonical = parent
+        .canonicalize()
+        .map_err(|_| TessellumError::Validation("Invalid destination path".to_string()))?;
+    if !parent_canonical.starts_with(&vault_canonical) {
+        return Err(TessellumError::Validation(
+            "Security Error: Cannot duplicate file to outside the vault".to_string(),
+        ));
+    }
+
+    let content = tokio::fs::read_to_string(original)
+        .await
+        .map_err(TessellumError::from)?;
+    tokio::fs::write(&file_path, &content)
+        .await
+        .map_err(TessellumError::from)?;
+
+    // Keep the cached index current instead of rebuilding it
+    if let Some(idx) = state.file_index.lock().await.as_mut() {
+        idx.insert(&file_path);
+    }
+
+    let path_str = crate::utils::normalize_path(&file_path.to_string_lossy());
+    index_note_content(state, vault_path, &path_str, &content).await
+}
+
+/// Duplicates a note and returns the path of the copy.
+#[tauri::command]
+pub async fn duplicate_note(
+    state: State<'_, AppState>,
+    kuzu_state: State<'_, ManagedGrafeoConnection>,
+    vault_path: String,
+    path: String,
+) -> Result<String, TessellumError> {
+    let delta = duplicate_note_inner(&state, &vault_path, &path).await?;
+    let path_str = delta.note_id.clone();
+    sync_note_delta_non_critical(&state, &kuzu_state, delta).await;
     Ok(path_str)
 }
 
+/// Opens today's daily note, creating it on first use.
+///
+/// The location comes from `daily_notes.path_template` in the vault config
+/// (e.g. `Journal/{YYYY}-{MM}-{DD}.md` for a flat folder) and uses the local
+/// date. A new note is seeded from the `daily_notes.template_name` template
+/// when it exists, otherwise with a `# YYYY-MM-DD` heading. An existing note
+/// is returned as is.
 #[tauri::command]
 pub async fn get_or_create_daily_note(
     state: State<'_, AppState>,
@@ -623,10 +964,9 @@ pub async fn get_or_create_daily_note(
         let delta = index_note_content(&state, &vault_path, &full_path_str, &content).await?;
         sync_note_delta_non_critical(&state, &kuzu_state, delta).await;
         
-        let mut idx_guard = state.file_index.lock().await;
-        *idx_guard = None;
-        let mut asset_guard = state.asset_index.lock().await;
-        *asset_guard = None;
+        if let Some(idx) = state.file_index.lock().await.as_mut() {
+            idx.insert(&full_path);
+        }
     }
     
     let metadata = tokio::fs::metadata(&full_path).await.map_err(|e| {
@@ -635,40 +975,20 @@ pub async fn get_or_create_daily_note(
         ))
     })?;
     
-    let filename = full_path
-        .file_name()
-        .unwrap_or_default()
-        .to_string_lossy()
-        .to_string();
-    
-    Ok(FileMetadata {
-        path: full_path_str,
-        filename,
-        is_dir: false,
-        size: metadata.len(),
-        last_modified: metadata
-            .modified()
-            .map_err(|e| {
-                TessellumError::Io(std::io::Error::other(
-                    format!("Failed to get modified time: {}", e),
-                ))
-            })?
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs() as i64,
-    })
+    Ok(FileMetadata::from_fs(&full_path, &metadata))
 }
 
 /// Moves a note or folder to a trash directory within the specified vault directory.
 ///
 /// This function is useful for "soft-deleting" items by moving them to a `.trash`
 /// subdirectory within a vault, while ensuring that the filenames are unique
-/// using a timestamp.
+/// using a timestamp. Bulk callers pass one `timestamp` for the whole batch.
 async fn trash_item_internal(
-    state: State<'_, AppState>,
-    kuzu_state: State<'_, ManagedGrafeoConnection>,
+    state: &AppState,
+    kuzu_state: &ManagedGrafeoConnection,
     item_path: String,
     vault_path: String,
+    timestamp: u128,
 ) -> Result<(), TessellumError> {
     validate_path_in_vault(&item_path, &vault_path).map_err(TessellumError::Validation)?;
     
@@ -678,17 +998,24 @@ async fn trash_item_internal(
         return Err(TessellumError::NotFound("Item does not exist".to_string()));
     }
     let was_file = item.is_file();
+    // Files leaving the vault with this item, collected before the move.
+    let normalized_item = crate::utils::normalize_path(&item_path);
+    let removed_paths: Vec<String> = if was_file {
+        vec![normalized_item.clone()]
+    } else {
+        WalkDir::new(item)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| crate::utils::normalize_path(&entry.path().to_string_lossy()))
+            .collect()
+    };
     
     let vault_root = Path::new(&vault_path);
     let trash_dir = vault_root.join(".trash");
 
     fs::create_dir_all(&trash_dir).map_err(TessellumError::Io)?;
     
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_millis();
-    
     let trash_path = generate_unique_trash_path(&trash_dir, item, vault_root, timestamp)
         .ok_or_else(|| TessellumError::Validation("Failed to generate trash name".to_string()))?;
     
@@ -705,27 +1032,31 @@ async fn trash_item_internal(
     // so we avoid blocking the entire bulk operation on long-running DB operations.
     let db = state.db.clone();
     
-    match timeout(Duration::from_secs(5), db.delete_file(&item_path)).await {
+    // Dropping the note rows removes their outgoing links; links from other
+    // notes stay and now show up as broken.
+    let delete_notes = async {
+        if was_file {
+            db.delete_file(&normalized_item).await
+        } else {
+            let prefix = format!("{}/", normalized_item.trim_end_matches('/'));
+            db.delete_files_by_prefix(&prefix).await.map(|_| ())
+        }
+    };
+    match timeout(Duration::from_secs(5), delete_notes).await {
         Ok(Ok(())) => {}
         Ok(Err(e)) => log::warn!("DB error during trash cleanup for {}: {}", item_path, e),
         Err(_) => log::warn!("DB timeout during trash cleanup for {} — index may be stale", item_path),
     }
 
-    match timeout(
-        Duration::from_secs(5),
-        db.delete_search_files(std::slice::from_ref(&item_path)),
-    )
-    .await
-    {
+    match timeout(Duration::from_secs(5), db.delete_search_files(&removed_paths)).await {
         Ok(Ok(_)) => {}
         Ok(Err(e)) => log::warn!("DB error clearing search files for {}: {}", item_path, e),
         Err(_) => log::warn!("DB timeout clearing search files for {}", item_path),
     }
 
     let search_index = state.search_index.clone();
-    let path = item_path.clone();
     tauri::async_runtime::spawn(async move {
-        search_index.lock().await.delete_path(&path).ok();
+        search_index.lock().await.index_batch(&[], &removed_paths).ok();
     });
     
     // Invalidate the cache
@@ -735,14 +1066,14 @@ async fn trash_item_internal(
     *asset_guard = None;
     
     if was_file {
-        if let Err(err) = sync_note_delete(kuzu_state.inner(), &crate::utils::normalize_path(&item_path)) {
-            eprintln!("Kuzu sync_note_delete failed for '{}': {}", item_path, err);
+        if let Err(err) = sync_note_delete(kuzu_state, &normalized_item) {
+            log::warn!("Kuzu sync_note_delete failed for '{}': {}", item_path, err);
         }
     } else {
-        match timeout(Duration::from_secs(5), sync_full(kuzu_state.inner(), db.as_ref())).await {
+        match timeout(Duration::from_secs(5), sync_full(kuzu_state, db.as_ref())).await {
             Ok(Ok(())) => {}
             Ok(Err(err)) => {
-                eprintln!(
+                log::warn!(
                     "Kuzu sync_full failed after trashing '{}': {}",
                     item_path, err
                 );
@@ -762,9 +1093,20 @@ pub async fn trash_item(
     item_path: String,
     vault_path: String,
 ) -> Result<(), TessellumError> {
-    trash_item_internal(state, kuzu_state, item_path, vault_path).await
+    trash_item_internal(&state, &kuzu_state, item_path, vault_path, trash_timestamp()).await
+}
+
+fn trash_timestamp() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
 }
 
+/// Trash several items in one call, all stamped with the same timestamp.
+///
+/// Each item succeeds or fails on its own; failures are reported per path
+/// and never abort the rest of the batch.
 #[tauri::command]
 pub async fn trash_items(
     state: State<'_, AppState>,
@@ -772,17 +1114,27 @@ pub async fn trash_items(
     item_paths: Vec<String>,
     vault_path: String,
 ) -> Result<TrashItemsResult, TessellumError> {
-    
+    Ok(trash_items_inner(&state, &kuzu_state, item_paths, &vault_path).await)
+}
+
+async fn trash_items_inner(
+    state: &AppState,
+    kuzu_state: &ManagedGrafeoConnection,
+    item_paths: Vec<String>,
+    vault_path: &str,
+) -> TrashItemsResult {
+    let timestamp = trash_timestamp();
     let mut deleted_paths = Vec::new();
     let mut failed = Vec::new();
     
     for item_path in item_paths.into_iter() {
         
         match trash_item_internal(
-            state.clone(),
-            kuzu_state.clone(),
+            state,
+            kuzu_state,
             item_path.clone(),
-            vault_path.clone(),
+            vault_path.to_string(),
+            timestamp,
         )
             .await
         {
@@ -798,10 +1150,10 @@ pub async fn trash_items(
         }
     }
     
-    Ok(TrashItemsResult {
+    TrashItemsResult {
         deleted_paths,
         failed,
-    })
+    }
 }
 
 #[tauri::command]
@@ -835,99 +1187,465 @@ pub async fn delete_trash_item_permanently(
     permanently_delete_trash_entry(&resolved_entry).map_err(TessellumError::Io)
 }
 
-/// Reads the contents of a file at the given path and returns it as a `String`.
-/// The path is validated to be inside the vault directory.
+/// Permanently deletes everything in `.trash`, including folders and their contents.
 #[tauri::command]
-pub async fn read_file(vault_path: String, path: String) -> Result<String, TessellumError> {
-    // Validate path inside vault
-    validate_path_in_vault(&path, &vault_path).map_err(TessellumError::Validation)?;
-    
-    tokio::fs::read_to_string(&path)
+pub async fn empty_trash(vault_path: String) -> Result<EmptyTrashResult, TessellumError> {
+    validate_vault_root(&vault_path)?;
+    let report = async_runtime::spawn_blocking(move || purge_all_trash(&vault_path))
         .await
-        .map_err(TessellumError::from)
+        .map_err(|e| TessellumError::Internal(format!("Empty trash task failed: {e}")))?;
+    if report.errors > 0 {
+        log::warn!("Empty trash left {} entries that could not be deleted", report.errors);
+    }
+    Ok(report.into())
 }
 
-/// Writes the specified content to a file at the given path.
-/// Also updates the database index with resolved wikilinks.
+/// Permanently deletes trash entries deleted more than `days` days ago.
 #[tauri::command]
-pub async fn write_file(
-    state: State<'_, AppState>,
-    kuzu_state: State<'_, ManagedGrafeoConnection>,
+pub async fn empty_trash_older_than(
     vault_path: String,
-    path: String,
-    content: String,
-) -> Result<(), TessellumError> {
-    // Validate path inside vault
-    validate_path_in_vault(&path, &vault_path).map_err(TessellumError::Validation)?;
-
-    // Atomic write: write to a temp file first, update the index, then rename into place.
-    // This ensures the file and its index entry never diverge — if indexing fails, the
-    // original file is untouched.
-    let tmp_path = format!("{}.tessellum-tmp", path);
-    tokio::fs::write(&tmp_path, &content)
+    days: u64,
+) -> Result<EmptyTrashResult, TessellumError> {
+    validate_vault_root(&vault_path)?;
+    let report = async_runtime::spawn_blocking(move || purge_expired_trash(&vault_path, days))
         .await
-        .map_err(|e| TessellumError::Internal(format!("Failed to write '{}': {}", tmp_path, e)))?;
+        .map_err(|e| TessellumError::Internal(format!("Empty trash task failed: {e}")))?;
+    Ok(report.into())
+}
 
-    let delta = match index_note_content(&state, &vault_path, &path, &content).await {
-        Ok(d) => d,
-        Err(e) => {
-            // Index update failed — remove the temp file and leave the original intact.
-            let _ = tokio::fs::remove_file(&tmp_path).await;
-            return Err(e);
-        }
+/// Checks that `path` is inside the vault before anything reveals whether it
+/// exists: paths outside the vault fail with `Other` either way. A missing
+/// file in a vault folder is `NotFound`.
+async fn validate_file_in_vault(path: &str, vault_path: &str) -> Result<(), FileError> {
+    let Err(message) = validate_path_in_vault(path, vault_path) else {
+        return Ok(());
     };
-
-    // Index committed — atomically replace the original file.
-    tokio::fs::rename(&tmp_path, &path)
-        .await
-        .map_err(|e| TessellumError::Internal(format!("Failed to rename '{}' to '{}': {}", tmp_path, path, e)))?;
-
-    sync_note_delta_non_critical(&state, &kuzu_state, delta).await;
-
-    // Non-critical: write a version-history snapshot in the background.
-    {
-        let vault_path_snap = vault_path.clone();
-        let path_snap = path.clone();
-        let content_snap = content.clone();
-        tokio::spawn(async move {
-            if let Err(e) = crate::commands::history::write_note_snapshot(
-                vault_path_snap, path_snap, content_snap,
-            ).await {
-                log::warn!("Failed to write version snapshot: {e}");
-            }
-        });
+    let parent_in_vault = Path::new(path)
+        .parent()
+        .is_some_and(|parent| validate_path_in_vault(&parent.to_string_lossy(), vault_path).is_ok());
+    if parent_in_vault && !tokio::fs::try_exists(path).await.unwrap_or(true) {
+        return Err(FileError::new(FileErrorKind::NotFound, format!("File not found: {}", path)));
     }
+    Err(FileError::new(FileErrorKind::Other, message))
+}
 
-    Ok(())
+/// Reads the contents of a file at the given path and returns it as a `String`.
+/// The path is validated to be inside the vault directory and a leading UTF-8 BOM is stripped.
+///
+/// Failures carry a `kind` (`NotFound`, `PermissionDenied`, `InvalidUtf8` or `Other`).
+#[tauri::command]
+pub async fn read_file(vault_path: String, path: String) -> Result<String, FileError> {
+    validate_file_in_vault(&path, &vault_path).await?;
+    
+    let content = tokio::fs::read_to_string(&path).await?;
+    
+    // Drop a leading BOM so it never surfaces in the editor or breaks frontmatter.
+    Ok(strip_bom(&content).to_string())
 }
 
+/// Like `read_file`, but bytes that are not valid UTF-8 are replaced with
+/// `U+FFFD` instead of failing, so notes in other encodings can be opened and
+/// fixed. Saving writes the replaced text back as UTF-8.
 #[tauri::command]
-pub async fn get_all_notes(
-    state: State<'_, AppState>,
-) -> Result<Vec<(String, i64)>, TessellumError> {
-    let db = state.db.clone();
-    db
-        .get_all_indexed_files()
-        .await
-        .map_err(TessellumError::from)
+pub async fn read_file_lossy(vault_path: String, path: String) -> Result<String, FileError> {
+    validate_file_in_vault(&path, &vault_path).await?;
+    let bytes = tokio::fs::read(&path).await?;
+    let content = String::from_utf8_lossy(&bytes);
+    Ok(strip_bom(&content).to_string())
 }
 
-#[derive(Serialize)]
-pub struct NoteSuggestion {
-    pub name: String,
-    pub relative_path: String,
-    pub full_path: String,
+/// A note's content together with the metadata of the same read.
+#[derive(Serialize, Debug)]
+pub struct OpenedNote {
+    pub content: String,
+    pub metadata: FileMetadata,
 }
 
-/// Search for notes matching a query.
-/// Returns suggestions formatted for the wikilink auto-complete.
+/// Like `read_file`, but also returns the note's `FileMetadata`.
+///
+/// Content and metadata come from one open file handle, so the editor never
+/// pairs new content with a stale timestamp. A missing file is `NotFound`.
 #[tauri::command]
-pub async fn search_notes(
-    state: State<'_, AppState>,
-    vault_path: String,
-    query: String,
-) -> Result<Vec<NoteSuggestion>, TessellumError> {
-    let db = state.db.clone();
+pub async fn open_note(vault_path: String, path: String) -> Result<OpenedNote, TessellumError> {
+    use tokio::io::AsyncReadExt;
+
+    validate_file_in_vault(&path, &vault_path).await.map_err(|e| match e.kind {
+        FileErrorKind::NotFound => TessellumError::NotFound(path.clone()),
+        _ => TessellumError::Validation(e.message),
+    })?;
+
+    let mut file = tokio::fs::File::open(&path).await.map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            TessellumError::NotFound(path.clone())
+        } else {
+            TessellumError::from(e)
+        }
+    })?;
+    let meta = file.metadata().await?;
+    if meta.is_dir() {
+        return Err(TessellumError::Validation(format!("'{}' is a folder, not a note", path)));
+    }
+    let mut content = String::new();
+    file.read_to_string(&mut content).await?;
+
+    Ok(OpenedNote {
+        content: strip_bom(&content).to_string(),
+        metadata: FileMetadata::from_fs(Path::new(&path), &meta),
+    })
+}
+
+/// A recently opened note and when it was last opened (Unix milliseconds).
+#[derive(Serialize, Debug)]
+pub struct RecentFile {
+    pub path: String,
+    pub opened_at: i64,
+}
+
+/// Remember that a note was opened, for the recent files list.
+///
+/// Tracked in the index, not on disk, so opening a note never touches its
+/// modification time.
+#[tauri::command]
+pub async fn record_open(state: State<'_, AppState>, path: String) -> Result<(), TessellumError> {
+    let opened_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64;
+    state
+        .db
+        .record_open(&crate::utils::normalize_path(&path), opened_at)
+        .await
+        .map_err(TessellumError::from)
+}
+
+/// The last `limit` notes opened, newest first, each listed once.
+#[tauri::command]
+pub async fn get_recent_files(
+    state: State<'_, AppState>,
+    limit: Option<i64>,
+) -> Result<Vec<RecentFile>, TessellumError> {
+    let rows = state
+        .db
+        .get_recent_files(limit.unwrap_or(20).max(0))
+        .await
+        .map_err(TessellumError::from)?;
+    Ok(rows
+        .into_iter()
+        .map(|(path, opened_at)| RecentFile { path, opened_at })
+        .collect())
+}
+
+/// Writes the specified content to a file at the given path.
+/// Also updates the database index with resolved wikilinks.
+#[tauri::command]
+pub async fn write_file(
+    state: State<'_, AppState>,
+    kuzu_state: State<'_, ManagedGrafeoConnection>,
+    vault_path: String,
+    path: String,
+    content: String,
+) -> Result<(), TessellumError> {
+    write_and_index_note(&state, &kuzu_state, vault_path, path, content).await?;
+    Ok(())
+}
+
+/// Same as `write_file`, but returns how the note's wikilinks resolved so the
+/// editor can refresh link decorations straight from the save.
+#[tauri::command]
+pub async fn write_file_with_links(
+    state: State<'_, AppState>,
+    kuzu_state: State<'_, ManagedGrafeoConnection>,
+    vault_path: String,
+    path: String,
+    content: String,
+) -> Result<LinkResolution, TessellumError> {
+    write_and_index_note(&state, &kuzu_state, vault_path, path, content).await
+}
+
+async fn append_to_note_inner(
+    state: &AppState,
+    kuzu_state: &ManagedGrafeoConnection,
+    vault_path: String,
+    path: String,
+    text: String,
+) -> Result<(), TessellumError> {
+    validate_vault_root(&vault_path)?;
+    let note = Path::new(&path);
+
+    let existing = if note.exists() {
+        validate_path_in_vault(&path, &vault_path).map_err(TessellumError::Validation)?;
+        let content = tokio::fs::read_to_string(note).await?;
+        strip_bom(&content).to_string()
+    } else {
+        if note.extension().and_then(|ext| ext.to_str()) != Some("md") {
+            return Err(TessellumError::Validation(
+                "Only markdown notes can be appended to".to_string(),
+            ));
+        }
+        let parent = note.parent().ok_or_else(|| {
+            TessellumError::Validation("Invalid path: No parent directory".to_string())
+        })?;
+        validate_path_in_vault(&parent.to_string_lossy(), &vault_path)
+            .map_err(TessellumError::Validation)?;
+        // Create the file first so the indexed write below can validate it.
+        tokio::fs::write(note, "").await?;
+        if let Some(idx) = state.file_index.lock().await.as_mut() {
+            idx.insert(note);
+        }
+        String::new()
+    };
+
+    let content = if existing.is_empty() {
+        text
+    } else {
+        format!("{}\n{}", existing, text)
+    };
+    write_and_index_note(state, kuzu_state, vault_path, path, content).await?;
+    Ok(())
+}
+
+/// Append `text` to a note on a new line and re-index it, for quick capture.
+///
+/// The note is created when missing; its folder must already exist. Links and
+/// tags are re-indexed from the whole resulting note, not just the new text.
+#[tauri::command]
+pub async fn append_to_note(
+    state: State<'_, AppState>,
+    kuzu_state: State<'_, ManagedGrafeoConnection>,
+    vault_path: String,
+    path: String,
+    text: String,
+) -> Result<(), TessellumError> {
+    append_to_note_inner(&state, &kuzu_state, vault_path, path, text).await
+}
+
+/// Write `content` to a temp file next to the note and flush it to disk.
+///
+/// Syncing before the rename means a crash right after `replace_with_temp`
+/// can't leave an empty or truncated note behind. A partial temp file is
+/// removed on failure.
+async fn write_synced(tmp_path: &str, content: &str) -> Result<(), TessellumError> {
+    use tokio::io::AsyncWriteExt;
+
+    let result = async {
+        let mut file = tokio::fs::File::create(tmp_path).await?;
+        file.write_all(content.as_bytes()).await?;
+        file.sync_all().await
+    }
+    .await;
+    if let Err(e) = result {
+        let _ = tokio::fs::remove_file(tmp_path).await;
+        return Err(TessellumError::Io(std::io::Error::new(
+            e.kind(),
+            format!("Failed to write '{}': {}", tmp_path, e),
+        )));
+    }
+    Ok(())
+}
+
+/// Atomically move a temp file written by `write_synced` over the note.
+///
+/// The rename is atomic within a directory, so readers see either the old or
+/// the new note, never a mix. On failure the temp file is removed and the
+/// original stays untouched.
+async fn replace_with_temp(tmp_path: &str, path: &str) -> Result<(), TessellumError> {
+    if let Err(e) = tokio::fs::rename(tmp_path, path).await {
+        let _ = tokio::fs::remove_file(tmp_path).await;
+        return Err(TessellumError::Io(std::io::Error::new(
+            e.kind(),
+            format!("Failed to rename '{}' to '{}': {}", tmp_path, path, e),
+        )));
+    }
+    Ok(())
+}
+
+/// Tell the watcher that the upcoming events for this note (and its temp file) are our own.
+fn mark_self_write(state: &AppState, path: &str, tmp_path: &str) {
+    let now = std::time::Instant::now();
+    let mut expected = state
+        .expected_changes
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    expected.expect(path, now);
+    expected.expect(tmp_path, now);
+}
+
+pub(crate) async fn write_and_index_note(
+    state: &AppState,
+    kuzu_state: &ManagedGrafeoConnection,
+    vault_path: String,
+    path: String,
+    content: String,
+) -> Result<LinkResolution, TessellumError> {
+    validate_vault_root(&vault_path)?;
+    // Validate path inside vault
+    validate_path_in_vault(&path, &vault_path).map_err(TessellumError::Validation)?;
+
+    // Never persist a BOM, even if the editor content carries one.
+    let content = if content.starts_with('\u{FEFF}') {
+        strip_bom(&content).to_string()
+    } else {
+        content
+    };
+
+    // Keep the old content first while version history is on; a failure here must not block the save.
+    let versions = load_or_init_config(&vault_path)
+        .map(|config| config.versions)
+        .unwrap_or_default();
+    if versions.enabled
+        && let Err(e) = save_previous_version(&vault_path, &path, &content, versions.max_per_note)
+    {
+        log::warn!("Failed to keep previous version of '{}': {}", path, e);
+    }
+
+    // Atomic write: write to a temp file first, update the index, then rename into place.
+    // This ensures the file and its index entry never diverge — if indexing fails, the
+    // original file is untouched.
+    let tmp_path = format!("{}.tessellum-tmp", path);
+    mark_self_write(state, &path, &tmp_path);
+    write_synced(&tmp_path, &content).await?;
+
+    let mut delta = match index_note_content(state, &vault_path, &path, &content).await {
+        Ok(d) => d,
+        Err(e) => {
+            // Index update failed — remove the temp file and leave the original intact.
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(e);
+        }
+    };
+
+    // Index committed — atomically replace the original file.
+    replace_with_temp(&tmp_path, &path).await?;
+
+    let link_resolution = std::mem::take(&mut delta.link_resolution);
+    sync_note_delta_non_critical(state, kuzu_state, delta).await;
+
+    // An explicit save indexes synchronously, so a pending autosave flush can skip it.
+    state.pending_index.lock().await.forget(&vault_path, &path);
+
+    // Non-critical: write a version-history snapshot in the background.
+    if versions.enabled {
+        let vault_path_snap = vault_path.clone();
+        let path_snap = path.clone();
+        let content_snap = content.clone();
+        tokio::spawn(async move {
+            if let Err(e) = crate::commands::history::write_note_snapshot(
+                vault_path_snap, path_snap, content_snap,
+            ).await {
+                log::warn!("Failed to write version snapshot: {e}");
+            }
+        });
+    }
+
+    Ok(link_resolution)
+}
+
+/// How long autosaves must pause before the background indexer runs.
+const AUTOSAVE_INDEX_DEBOUNCE_MS: u64 = 750;
+
+/// Writes the file to disk without touching the index.
+///
+/// Intended for autosave-on-keystroke: the note is queued in the `AppState`
+/// dirty set and re-indexed by a debounced background task once typing pauses.
+#[tauri::command]
+pub async fn write_file_fast(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    vault_path: String,
+    path: String,
+    content: String,
+) -> Result<(), TessellumError> {
+    validate_vault_root(&vault_path)?;
+    validate_path_in_vault(&path, &vault_path).map_err(TessellumError::Validation)?;
+
+    let tmp_path = format!("{}.tessellum-tmp", path);
+    mark_self_write(&state, &path, &tmp_path);
+    write_synced(&tmp_path, strip_bom(&content)).await?;
+    replace_with_temp(&tmp_path, &path).await?;
+
+    let needs_flush = state.pending_index.lock().await.mark_dirty(&vault_path, &path);
+    if needs_flush {
+        async_runtime::spawn(flush_pending_index(app));
+    }
+
+    Ok(())
+}
+
+/// Wait for autosaves to settle, then index every dirty note.
+async fn flush_pending_index(app: AppHandle) {
+    let state = app.state::<AppState>();
+    let grafeo = app.state::<ManagedGrafeoConnection>();
+
+    let batches = loop {
+        let seen_generation = state.pending_index.lock().await.generation;
+        tokio::time::sleep(Duration::from_millis(AUTOSAVE_INDEX_DEBOUNCE_MS)).await;
+        if let Some(batches) = state.pending_index.lock().await.take_settled(seen_generation) {
+            break batches;
+        }
+    };
+
+    for (vault_path, paths) in batches {
+        for path in paths {
+            let content = match tokio::fs::read_to_string(&path).await {
+                Ok(content) => content,
+                Err(e) => {
+                    // Deleted or moved since the save; the watcher handles those.
+                    log::warn!("Autosave index skipped '{}': {}", path, e);
+                    continue;
+                }
+            };
+            match index_note_content(&state, &vault_path, &path, &content).await {
+                Ok(delta) => sync_note_delta_non_critical(&state, &grafeo, delta).await,
+                Err(e) => log::warn!("Autosave index failed for '{}': {}", path, e),
+            }
+        }
+    }
+}
+
+/// One indexed note as listed by `get_all_notes`.
+#[derive(Serialize, Debug)]
+pub struct NoteListing {
+    pub path: String,
+    pub modified_at: i64,
+    /// Frontmatter `title:`, else the first `# Heading`, else the file stem.
+    pub title: String,
+}
+
+#[tauri::command]
+pub async fn get_all_notes(
+    state: State<'_, AppState>,
+) -> Result<Vec<NoteListing>, TessellumError> {
+    let db = state.db.clone();
+    let notes = db
+        .get_all_notes_with_titles()
+        .await
+        .map_err(TessellumError::from)?;
+    Ok(notes
+        .into_iter()
+        .map(|(path, modified_at, title)| NoteListing {
+            title: title.unwrap_or_else(|| title_from_path(&path)),
+            path,
+            modified_at,
+        })
+        .collect())
+}
+
+#[derive(Serialize)]
+pub struct NoteSuggestion {
+    pub name: String,
+    pub relative_path: String,
+    pub full_path: String,
+}
+
+/// Search for notes matching a query.
+/// Returns suggestions formatted for the wikilink auto-complete.
+#[tauri::command]
+pub async fn search_notes(
+    state: State<'_, AppState>,
+    vault_path: String,
+    query: String,
+) -> Result<Vec<NoteSuggestion>, TessellumError> {
+    let db = state.db.clone();
     let files = db
         .get_all_indexed_files()
         .await
@@ -973,11 +1691,246 @@ pub async fn search_notes(
     Ok(suggestions)
 }
 
+/// Everything the UI needs about a note when it is opened.
+#[derive(Serialize, Debug)]
+pub struct NoteMetadata {
+    pub path: String,
+    pub filename: String,
+    pub size: u64,
+    /// Creation time in Unix seconds, when the filesystem reports one.
+    pub created: Option<i64>,
+    pub modified: i64,
+    pub word_count: usize,
+    pub backlink_count: usize,
+    pub outgoing_link_count: usize,
+    pub tags: Vec<String>,
+    /// `true` when the note's frontmatter sets `starred: true`.
+    pub starred: bool,
+}
+
+fn system_time_to_unix_secs(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64
+}
+
+/// Readable text of a note: frontmatter dropped, markdown syntax removed and
+/// wikilinks replaced by their alias or target. Embeds contribute nothing.
+fn note_plain_text(content: &str) -> String {
+    let body = crate::utils::frontmatter::strip_frontmatter(content);
+    let body = WIKILINK_RE.replace_all(body, |caps: &regex::Captures| {
+        if caps.get(2).is_some() {
+            return caps[0].to_string();
+        }
+        if caps.get(1).is_some() {
+            return String::new();
+        }
+        let link = WikiLink::parse(&caps[3]);
+        link.alias.unwrap_or(link.target)
+    });
+    
+    let mut text = String::new();
+    for event in Parser::new_ext(&body, Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES) {
+        match event {
+            Event::Text(chunk) | Event::Code(chunk) => text.push_str(&chunk),
+            // Inline markup must not split the word it wraps.
+            Event::End(TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough | TagEnd::Link) => {}
+            Event::SoftBreak | Event::HardBreak | Event::End(_) => text.push(' '),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Count words in the note body, ignoring frontmatter and markdown syntax.
+fn count_words(content: &str) -> usize {
+    note_plain_text(content).split_whitespace().count()
+}
+
+/// Average silent reading speed used for the reading time estimate.
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Text statistics shown in the status bar.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct NoteStats {
+    pub word_count: usize,
+    /// Characters of the readable text, with whitespace runs counted as one space.
+    pub char_count: usize,
+    /// Estimated minutes at 200 words per minute, rounded up.
+    pub reading_time_minutes: usize,
+}
+
+fn note_stats(content: &str) -> NoteStats {
+    let text = note_plain_text(content);
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let char_count = words.iter().map(|word| word.chars().count()).sum::<usize>()
+        + words.len().saturating_sub(1);
+    
+    NoteStats {
+        word_count: words.len(),
+        char_count,
+        reading_time_minutes: words.len().div_ceil(WORDS_PER_MINUTE),
+    }
+}
+
+/// Word count, character count and reading time for a note.
+#[tauri::command]
+pub async fn get_note_stats(vault_path: String, path: String) -> Result<NoteStats, TessellumError> {
+    validate_path_in_vault(&path, &vault_path).map_err(TessellumError::Validation)?;
+    let content = tokio::fs::read_to_string(&path).await?;
+    Ok(note_stats(&content))
+}
+
+/// Vault-wide totals for the dashboard.
+#[derive(Serialize, Debug, PartialEq, Default)]
+pub struct VaultStats {
+    pub note_count: usize,
+    /// Folders below the vault root; ignored and hidden folders are skipped.
+    pub folder_count: usize,
+    pub word_count: usize,
+    pub link_count: usize,
+    pub orphan_count: usize,
+    /// Bytes used by every non-ignored file, notes and attachments alike.
+    pub total_size: u64,
+}
+
+/// Walk the vault counting notes, folders, words and bytes.
+///
+/// Notes are read one at a time, so memory use does not grow with the vault.
+fn scan_vault_stats(vault_path: &str) -> VaultStats {
+    let ignore = IgnoreRules::load(vault_path);
+    let mut stats = VaultStats::default();
+    let entries = WalkDir::new(vault_path)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|e| ignore.keeps(vault_path, e))
+        .filter_map(|e| e.ok());
+    for entry in entries {
+        if entry.file_type().is_dir() {
+            stats.folder_count += 1;
+            continue;
+        }
+        stats.total_size += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        if entry.path().extension().and_then(|ext| ext.to_str()) == Some("md") {
+            stats.note_count += 1;
+            match fs::read_to_string(entry.path()) {
+                Ok(content) => stats.word_count += count_words(&content),
+                Err(e) => log::warn!("Skipping words of {}: {}", entry.path().display(), e),
+            }
+        }
+    }
+    stats
+}
+
+async fn vault_stats(db: &crate::db::Database, vault_path: &str) -> Result<VaultStats, TessellumError> {
+    let link_count = db.get_all_links().await?.len();
+    let orphan_count = db.get_orphaned_files().await?.len();
+    let root = vault_path.to_string();
+    let stats = async_runtime::spawn_blocking(move || scan_vault_stats(&root))
+        .await
+        .map_err(|e| TessellumError::Internal(format!("Vault stats task failed: {e}")))?;
+    
+    Ok(VaultStats {
+        link_count,
+        orphan_count,
+        ..stats
+    })
+}
+
+/// Note, folder, word, link and orphan counts plus disk usage for the vault.
+#[tauri::command]
+pub async fn get_vault_stats(
+    state: State<'_, AppState>,
+    vault_path: String,
+) -> Result<VaultStats, TessellumError> {
+    validate_vault_root(&vault_path)?;
+    vault_stats(&state.db, &vault_path).await
+}
+
+/// Read the `starred` flag from a note's stored frontmatter JSON.
+fn is_starred(frontmatter_json: Option<&str>) -> bool {
+    frontmatter_json
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .and_then(|fm| fm.get("starred").cloned())
+        .is_some_and(|value| match value {
+            serde_json::Value::Bool(flag) => flag,
+            serde_json::Value::String(text) => text.eq_ignore_ascii_case("true"),
+            _ => false,
+        })
+}
+
+/// Assemble filesystem and index metadata for a single note in one call.
+#[tauri::command]
+pub async fn get_note_metadata(
+    state: State<'_, AppState>,
+    vault_path: String,
+    path: String,
+) -> Result<NoteMetadata, TessellumError> {
+    validate_path_in_vault(&path, &vault_path).map_err(TessellumError::Validation)?;
+    
+    let metadata = tokio::fs::metadata(&path).await?;
+    if !metadata.is_file() {
+        return Err(TessellumError::Validation(format!("Not a file: {}", path)));
+    }
+    let content = tokio::fs::read_to_string(&path).await?;
+    
+    let normalized = crate::utils::normalize_path(&path);
+    let db = state.db.clone();
+    let backlinks = db.get_backlinks(&normalized).await?;
+    let outgoing = db.get_outgoing_links(&normalized).await?;
+    let tags = db.get_file_tags(&normalized).await?;
+    let frontmatter = db.get_frontmatter(&normalized).await?;
+    
+    let filename = Path::new(&path)
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    
+    Ok(NoteMetadata {
+        path: normalized,
+        filename,
+        size: metadata.len(),
+        created: metadata.created().ok().map(system_time_to_unix_secs),
+        modified: metadata
+            .modified()
+            .map(system_time_to_unix_secs)
+            .unwrap_or_default(),
+        word_count: count_words(&content),
+        backlink_count: backlinks.len(),
+        outgoing_link_count: outgoing.len(),
+        tags,
+        starred: is_starred(frontmatter.as_deref()),
+    })
+}
+
+#[tauri::command]
+pub async fn get_all_tags(state: State<'_, AppState>) -> Result<Vec<String>, TessellumError> {
+    let db = state.db.clone();
+    db.get_all_tags().await.map_err(TessellumError::from)
+}
+
+/// A tag and how many notes carry it.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+/// Every tag in the vault with its note count, most used first.
+///
+/// Inline `#tags` and frontmatter `tags:` are both counted. Tags are stored
+/// normalized to lowercase, so `#Project` and `#project` are the same tag.
 #[tauri::command]
-pub async fn get_all_tags(state: State<'_, AppState>) -> Result<Vec<String>, TessellumError> {
-    let db = state.db.clone();
-    db.get_all_tags().await.map_err(TessellumError::from)
+pub async fn get_tag_counts(state: State<'_, AppState>) -> Result<Vec<TagCount>, TessellumError> {
+    let counts = state.db.get_tag_counts().await?;
+    Ok(counts
+        .into_iter()
+        .map(|(tag, count)| TagCount {
+            tag,
+            count: count as usize,
+        })
+        .collect())
 }
+
 #[tauri::command]
 pub async fn get_file_tags(
     state: State<'_, AppState>,
@@ -991,6 +1944,186 @@ pub async fn get_file_tags(
         .map_err(TessellumError::from)
 }
 
+/// Get all note paths carrying `tag`, from inline `#tags` or frontmatter `tags:`.
+/// The tag is matched case-insensitively and may be given with a leading '#'.
+#[tauri::command]
+pub async fn get_notes_by_tag(
+    state: State<'_, AppState>,
+    tag: String,
+) -> Result<Vec<String>, TessellumError> {
+    let tag = normalize_tag(&tag);
+    if tag.is_empty() {
+        return Ok(Vec::new());
+    }
+    state
+        .db
+        .get_notes_with_tag(&tag)
+        .await
+        .map(|paths| paths.iter().map(|p| crate::utils::normalize_path(p)).collect())
+        .map_err(TessellumError::from)
+}
+
+/// One segment of a nested tag such as `project/alpha/tasks`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TagNode {
+    /// The last segment, e.g. `alpha`.
+    pub name: String,
+    /// The full tag up to this segment, e.g. `project/alpha`.
+    pub tag: String,
+    /// Distinct notes tagged with this tag or any tag nested below it.
+    pub note_count: usize,
+    pub children: Vec<TagNode>,
+}
+
+#[derive(Default)]
+struct TagTreeBuilder {
+    notes: HashSet<String>,
+    children: std::collections::BTreeMap<String, TagTreeBuilder>,
+}
+
+impl TagTreeBuilder {
+    fn into_nodes(self, prefix: &str) -> Vec<TagNode> {
+        self.children
+            .into_iter()
+            .map(|(name, child)| {
+                let tag = if prefix.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}/{}", prefix, name)
+                };
+                TagNode {
+                    note_count: child.notes.len(),
+                    children: child.into_nodes(&tag),
+                    name,
+                    tag,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Assemble `(path, tag)` pairs into a tree split on `/`.
+///
+/// Counts are distinct notes, so a note tagged both `project/a` and
+/// `project/b` is counted once under `project`.
+fn build_tag_hierarchy(pairs: &[(String, String)]) -> Vec<TagNode> {
+    let mut root = TagTreeBuilder::default();
+    for (path, tag) in pairs {
+        let mut node = &mut root;
+        for segment in tag.split('/').filter(|segment| !segment.is_empty()) {
+            node = node.children.entry(segment.to_string()).or_default();
+            node.notes.insert(path.clone());
+        }
+    }
+    root.into_nodes("")
+}
+
+async fn rename_tag_inner(
+    state: &AppState,
+    kuzu_state: &ManagedGrafeoConnection,
+    vault_path: &str,
+    old: &str,
+    new: &str,
+) -> Result<usize, TessellumError> {
+    validate_vault_root(vault_path)?;
+    let old = normalize_tag(old);
+    let new = normalize_tag(new);
+    let valid_chars = |tag: &str| {
+        tag.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '/'))
+    };
+    if old.is_empty() || new.is_empty() || !valid_chars(&new) || new.starts_with('/') {
+        return Err(TessellumError::Validation(format!(
+            "Cannot rename tag '{}' to '{}'",
+            old, new
+        )));
+    }
+    if old == new {
+        return Ok(0);
+    }
+    
+    let nested = format!("{}/", old);
+    let mut paths: Vec<String> = state
+        .db
+        .get_note_tag_pairs()
+        .await?
+        .into_iter()
+        .filter(|(_, tag)| *tag == old || tag.starts_with(&nested))
+        .map(|(path, _)| path)
+        .collect();
+    paths.sort();
+    paths.dedup();
+    
+    let mut modified = 0;
+    for path in paths {
+        let content = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => content,
+            Err(e) => {
+                log::warn!("Skipping tag rename in {}: {}", path, e);
+                continue;
+            }
+        };
+        let content = strip_bom(&content);
+        let renamed = rename_tag_in_content(content, &old, &new);
+        if renamed != content {
+            write_and_index_note(state, kuzu_state, vault_path.to_string(), path, renamed).await?;
+            modified += 1;
+        }
+    }
+    Ok(modified)
+}
+
+/// Rename a tag in every note, in frontmatter `tags:` and inline `#tags`.
+///
+/// Nested tags move too (`#proj/web` becomes `#project/web`); code and URL
+/// fragments are left alone. Each changed note is saved and re-indexed.
+/// Returns how many notes were modified.
+#[tauri::command]
+pub async fn rename_tag(
+    state: State<'_, AppState>,
+    kuzu_state: State<'_, ManagedGrafeoConnection>,
+    vault_path: String,
+    old: String,
+    new: String,
+) -> Result<usize, TessellumError> {
+    rename_tag_inner(&state, &kuzu_state, &vault_path, &old, &new).await
+}
+
+/// Return nested tags as a tree with per-node note counts.
+#[tauri::command]
+pub async fn get_tag_hierarchy(
+    state: State<'_, AppState>,
+) -> Result<Vec<TagNode>, TessellumError> {
+    let pairs = state.db.get_note_tag_pairs().await?;
+    Ok(build_tag_hierarchy(&pairs))
+}
+
+/// A note and its indexed modification time (Unix seconds).
+#[derive(Serialize, Debug)]
+pub struct ModifiedNote {
+    pub path: String,
+    pub modified_at: i64,
+}
+
+/// Return notes modified between two Unix timestamps (inclusive), newest first.
+///
+/// Answered from the index alone; the vault is not walked.
+#[tauri::command]
+pub async fn get_notes_modified_between(
+    state: State<'_, AppState>,
+    start_ts: i64,
+    end_ts: i64,
+) -> Result<Vec<ModifiedNote>, TessellumError> {
+    let rows = state
+        .db
+        .get_notes_modified_between(start_ts, end_ts)
+        .await
+        .map_err(TessellumError::from)?;
+    Ok(rows
+        .into_iter()
+        .map(|(path, modified_at)| ModifiedNote { path, modified_at })
+        .collect())
+}
+
 #[tauri::command]
 pub async fn get_all_property_keys(
     state: State<'_, AppState>,
@@ -1005,13 +2138,50 @@ pub async fn get_all_property_keys(
 #[cfg(test)]
 mod tests {
     use super::{
-        build_daily_note_relative_path, ensure_daily_note_parent, list_trash_items_internal,
-        restore_trash_item_internal_for_tests, validate_relative_note_path,
+        append_to_note_inner, build_daily_note_relative_path, build_tag_hierarchy, count_words,
+        create_note_in_inner, default_template_content, duplicate_note_inner,
+        note_stats, strip_md_suffix, unique_note_path, NoteStats,
+        ensure_daily_note_parent, is_starred,
+        list_trash_items_internal, open_note, read_file, read_file_lossy, replace_with_temp,
+        resolve_link_targets,
+        rename_tag_inner, restore_trash_item_internal_for_tests, trash_items_inner, vault_stats,
+        write_synced,
+        validate_relative_note_path, LinkResolution,
     };
+    use crate::db::Database;
+    use crate::error::FileErrorKind;
+    use crate::grafeo_projection::ManagedGrafeoConnection;
+    use crate::models::{FileIndex, LinkStrategy};
+    use crate::test_support::{TestAppState, TestVault};
     use chrono::TimeZone;
     use std::fs;
+    use std::path::Path;
     use tempfile::tempdir;
 
+    #[test]
+    fn resolve_link_targets_counts_each_target_once() {
+        let index = FileIndex::from_markdown_paths(["/vault/Notes/Alpha.md"]);
+
+        let (paths, resolution) =
+            resolve_link_targets(
+                &index,
+                "/vault",
+                Path::new("/vault/Source.md"),
+                LinkStrategy::ShortestPath,
+                ["Alpha", "Missing", "Missing", "Notes/Alpha", "Alpha"],
+            );
+
+        assert_eq!(
+            resolution,
+            LinkResolution {
+                resolved: 2,
+                unresolved: vec!["Missing".to_string()],
+            }
+        );
+        assert_eq!(paths[0], "/vault/Notes/Alpha.md");
+        assert_eq!(paths[1], "/vault/Missing.md");
+    }
+
     #[tokio::test]
     async fn ensure_daily_note_parent_creates_missing_directories_in_fresh_vault() {
         // Regression: the first daily note of a month/year needs folders that do
@@ -1042,6 +2212,393 @@ mod tests {
         assert!(!vault.path().parent().unwrap().join("outside").exists());
     }
 
+    #[test]
+    fn tag_hierarchy_nests_segments_and_counts_distinct_notes() {
+        let pairs = vec![
+            ("A.md".to_string(), "project/alpha".to_string()),
+            ("A.md".to_string(), "project/beta".to_string()),
+            ("B.md".to_string(), "project/alpha/tasks".to_string()),
+            ("C.md".to_string(), "inbox".to_string()),
+        ];
+
+        let tree = build_tag_hierarchy(&pairs);
+
+        assert_eq!(tree.iter().map(|n| n.name.as_str()).collect::<Vec<_>>(), vec!["inbox", "project"]);
+        let project = &tree[1];
+        assert_eq!(project.note_count, 2);
+        let alpha = &project.children[0];
+        assert_eq!((alpha.tag.as_str(), alpha.note_count), ("project/alpha", 2));
+        assert_eq!(alpha.children[0].tag, "project/alpha/tasks");
+        assert_eq!(alpha.children[0].note_count, 1);
+        assert_eq!(project.children[1].note_count, 1);
+    }
+
+    #[tokio::test]
+    async fn read_file_strips_leading_bom() {
+        let vault = tempdir().unwrap();
+        let note = vault.path().join("Windows.md");
+        fs::write(&note, "\u{FEFF}---\ntitle: Test\n---\nBody").unwrap();
+
+        let content = read_file(
+            vault.path().to_string_lossy().to_string(),
+            note.to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(content, "---\ntitle: Test\n---\nBody");
+    }
+
+    #[tokio::test]
+    async fn read_file_reports_typed_error_kinds() {
+        let vault = tempdir().unwrap();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let binary = vault.path().join("Binary.md");
+        fs::write(&binary, [0xff, 0xfe, 0x00, 0x80]).unwrap();
+
+        let missing = read_file(
+            vault_path.clone(),
+            vault.path().join("Deleted.md").to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(missing.kind, FileErrorKind::NotFound);
+
+        // Outside the vault, existing and missing files fail alike.
+        let outside = tempdir().unwrap();
+        let secret = outside.path().join("Secret.md");
+        fs::write(&secret, "secret").unwrap();
+        for path in [secret.clone(), outside.path().join("Missing.md")] {
+            let err = read_file(vault_path.clone(), path.to_string_lossy().to_string())
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind, FileErrorKind::Other);
+        }
+
+        let invalid = read_file(vault_path.clone(), binary.to_string_lossy().to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(invalid.kind, FileErrorKind::InvalidUtf8);
+        assert_eq!(
+            serde_json::to_value(&invalid).unwrap()["kind"],
+            serde_json::json!("InvalidUtf8")
+        );
+    
+        let lossy = read_file_lossy(vault_path, binary.to_string_lossy().to_string())
+            .await
+            .unwrap();
+        assert_eq!(lossy, "\u{FFFD}\u{FFFD}\u{0}\u{FFFD}");
+    }
+
+    #[tokio::test]
+    async fn append_to_note_creates_missing_notes_and_indexes_the_whole_result() {
+        let vault = TestVault::new()
+            .with_markdown("Alpha.md", "# Alpha")
+            .with_markdown("Beta.md", "# Beta")
+            .build();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let inbox = vault.path().join("Inbox.md").to_string_lossy().to_string();
+        let state = TestAppState::new().await;
+        let grafeo_state = ManagedGrafeoConnection::default();
+
+        for text in ["See [[Alpha]]", "and [[Beta]]"] {
+            append_to_note_inner(
+                &state,
+                &grafeo_state,
+                vault_path.clone(),
+                inbox.clone(),
+                text.to_string(),
+            )
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(&inbox).unwrap(), "See [[Alpha]]\nand [[Beta]]");
+        let mut links = state.db.get_outgoing_links(&inbox).await.unwrap();
+        links.sort();
+        assert_eq!(
+            links,
+            vec![
+                crate::utils::normalize_path(&vault.path().join("Alpha.md").to_string_lossy()),
+                crate::utils::normalize_path(&vault.path().join("Beta.md").to_string_lossy()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn create_note_in_creates_inside_the_folder_and_rejects_escapes() {
+        let vault = TestVault::new().with_markdown("Projects/Plan.md", "# Plan").build();
+        fs::create_dir_all(vault.path().join(".trash")).unwrap();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let state = TestAppState::new().await;
+        let grafeo_state = ManagedGrafeoConnection::default();
+
+        let created =
+            create_note_in_inner(&state, &grafeo_state, &vault_path, "Projects", "Plan".to_string())
+                .await
+                .unwrap();
+        assert_eq!(
+            created.path,
+            crate::utils::normalize_path(&vault.path().join("Projects/Plan (1).md").to_string_lossy())
+        );
+        assert!(state.db.is_note_indexed(&created.path).await.unwrap());
+
+        for folder in ["..", ".trash", "Projects/Plan.md"] {
+            let err = create_note_in_inner(&state, &grafeo_state, &vault_path, folder, "Note".to_string())
+                .await
+                .unwrap_err();
+            assert!(matches!(err, crate::error::TessellumError::Validation(_)), "{folder}: {err:?}");
+        }
+        let err = create_note_in_inner(&state, &grafeo_state, &vault_path, "Missing", "Note".to_string())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, crate::error::TessellumError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn trash_items_shares_one_timestamp_and_reports_failures_per_item() {
+        let vault = TestVault::new()
+            .with_markdown("Alpha.md", "# Alpha")
+            .with_markdown("Projects/Beta.md", "# Beta")
+            .build();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let path = |rel: &str| vault.path().join(rel).to_string_lossy().to_string();
+        let state = TestAppState::new().await;
+        state.db.index_file(&path("Alpha.md"), 1, 1, None, None, &[]).await.unwrap();
+
+        let result = trash_items_inner(
+            &state,
+            &ManagedGrafeoConnection::default(),
+            vec![path("Alpha.md"), path("Missing.md"), path("Projects")],
+            &vault_path,
+        )
+        .await;
+
+        assert_eq!(result.deleted_paths, vec![path("Alpha.md"), path("Projects")]);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].item_path, path("Missing.md"));
+        assert!(!state.db.is_note_indexed(&path("Alpha.md")).await.unwrap());
+        let timestamps: std::collections::HashSet<u128> = fs::read_dir(vault.path().join(".trash"))
+            .unwrap()
+            .map(|entry| {
+                crate::trash::parse_trash_timestamp(&entry.unwrap().file_name().to_string_lossy()).unwrap()
+            })
+            .collect();
+        assert_eq!(timestamps.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn trashing_a_folder_de_indexes_its_notes_and_breaks_links_to_them() {
+        let vault = TestVault::new()
+            .with_markdown("Index.md", "[[Beta]]")
+            .with_markdown("Projects/Beta.md", "[[Index]]")
+            .with_markdown("Projects Archive/Gamma.md", "")
+            .build();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let index = vault.path().join("Index.md").to_string_lossy().to_string();
+        let beta = vault.path().join("Projects/Beta.md").to_string_lossy().to_string();
+        let gamma = vault.path().join("Projects Archive/Gamma.md").to_string_lossy().to_string();
+        let state = TestAppState::new().await;
+        state.db.index_file(&index, 1, 1, None, None, std::slice::from_ref(&beta)).await.unwrap();
+        state.db.index_file(&beta, 1, 1, None, None, std::slice::from_ref(&index)).await.unwrap();
+        state.db.index_file(&gamma, 1, 1, None, None, &[]).await.unwrap();
+
+        let result = trash_items_inner(
+            &state,
+            &ManagedGrafeoConnection::default(),
+            vec![vault.path().join("Projects").to_string_lossy().to_string()],
+            &vault_path,
+        )
+        .await;
+
+        assert!(result.failed.is_empty());
+        assert!(!state.db.is_note_indexed(&beta).await.unwrap());
+        assert!(state.db.is_note_indexed(&gamma).await.unwrap());
+        assert!(state.db.get_backlinks(&index).await.unwrap().is_empty());
+        assert_eq!(state.db.get_broken_links().await.unwrap(), vec![(index, beta)]);
+    }
+
+    #[tokio::test]
+    async fn vault_stats_counts_notes_folders_words_and_bytes() {
+        let vault = TestVault::new()
+            .with_markdown("Alpha.md", "---\ntags: [a]\n---\nOne **two** three")
+            .with_markdown("Projects/Beta.md", "four five")
+            .build();
+        std::fs::write(vault.path().join("Projects/diagram.png"), "png").unwrap();
+        std::fs::create_dir_all(vault.path().join(".trash")).unwrap();
+        std::fs::write(vault.path().join(".trash/Old.md"), "ignored words").unwrap();
+        let db = Database::init_memory().await.unwrap();
+        let alpha = vault.path().join("Alpha.md").to_string_lossy().to_string();
+        let beta = vault.path().join("Projects/Beta.md").to_string_lossy().to_string();
+        db.index_file(&alpha, 1, 1, None, None, std::slice::from_ref(&beta)).await.unwrap();
+        db.index_file(&beta, 1, 1, None, None, &[]).await.unwrap();
+        db.index_file("Lonely.md", 1, 1, None, None, &[]).await.unwrap();
+
+        let stats = vault_stats(&db, &vault.path().to_string_lossy()).await.unwrap();
+
+        assert_eq!(stats.note_count, 2);
+        assert_eq!(stats.folder_count, 1);
+        assert_eq!(stats.word_count, 5);
+        assert_eq!(stats.link_count, 1);
+        assert_eq!(stats.orphan_count, 1);
+        let expected_size: u64 = ["Alpha.md", "Projects/Beta.md", "Projects/diagram.png"]
+            .iter()
+            .map(|rel| std::fs::metadata(vault.path().join(rel)).unwrap().len())
+            .sum();
+        assert_eq!(stats.total_size, expected_size);
+    }
+
+    #[tokio::test]
+    async fn default_template_seeds_new_notes_only_when_configured_and_present() {
+        let vault = TestVault::new().build();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let templates = crate::commands::templates::templates_dir(&vault_path);
+        fs::create_dir_all(&templates).unwrap();
+        fs::write(templates.join("Meeting.md"), "# {{title}}\n").unwrap();
+        let mut config = crate::utils::config::AppConfig::default();
+
+        assert_eq!(default_template_content(&config, &vault_path, "Standup").await.unwrap(), None);
+
+        config.templates.default_template = "Meeting.md".to_string();
+        assert_eq!(
+            default_template_content(&config, &vault_path, "Standup").await.unwrap().as_deref(),
+            Some("# Standup\n")
+        );
+
+        config.templates.default_template = "Missing".to_string();
+        assert_eq!(default_template_content(&config, &vault_path, "Standup").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn rename_tag_rewrites_tagged_notes_and_updates_the_tags_table() {
+        let vault = TestVault::new()
+            .with_markdown("Alpha.md", "---\ntags: [proj]\n---\nBody")
+            .with_markdown("Beta.md", "Work on #proj/web today")
+            .with_markdown("Gamma.md", "Unrelated #project")
+            .build();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let path = |rel: &str| vault.path().join(rel).to_string_lossy().to_string();
+        let state = TestAppState::new().await;
+        for (note, tags) in [("Alpha.md", "proj"), ("Beta.md", "proj/web"), ("Gamma.md", "project")] {
+            state.db.index_file(&path(note), 1, 1, None, None, &[]).await.unwrap();
+            state.db.set_note_tags(&path(note), &[tags.to_string()]).await.unwrap();
+        }
+
+        let modified =
+            rename_tag_inner(&state, &ManagedGrafeoConnection::default(), &vault_path, "proj", "#Project")
+                .await
+                .unwrap();
+
+        assert_eq!(modified, 2);
+        assert_eq!(fs::read_to_string(path("Alpha.md")).unwrap(), "---\ntags: [project]\n---\nBody");
+        assert_eq!(fs::read_to_string(path("Beta.md")).unwrap(), "Work on #project/web today");
+        assert_eq!(state.db.get_file_tags(&path("Beta.md")).await.unwrap(), vec!["project/web"]);
+        assert_eq!(fs::read_to_string(path("Gamma.md")).unwrap(), "Unrelated #project");
+    }
+
+    #[tokio::test]
+    async fn temp_file_replaces_note_and_is_cleaned_up_on_failed_rename() {
+        let vault = tempdir().unwrap();
+        let note = vault.path().join("Draft.md").to_string_lossy().to_string();
+        let tmp = format!("{}.tessellum-tmp", note);
+        fs::write(&note, "old").unwrap();
+
+        write_synced(&tmp, "new").await.unwrap();
+        replace_with_temp(&tmp, &note).await.unwrap();
+        assert_eq!(fs::read_to_string(&note).unwrap(), "new");
+        assert!(!std::path::Path::new(&tmp).exists());
+
+        write_synced(&tmp, "lost").await.unwrap();
+        let missing_dir = vault.path().join("Gone/Draft.md").to_string_lossy().to_string();
+        assert!(replace_with_temp(&tmp, &missing_dir).await.is_err());
+        assert!(!std::path::Path::new(&tmp).exists());
+        assert_eq!(fs::read_to_string(&note).unwrap(), "new");
+    }
+
+    #[tokio::test]
+    async fn open_note_returns_content_with_metadata_and_not_found_for_missing_notes() {
+        let vault = TestVault::new().with_markdown("Inbox/Idea.md", "\u{FEFF}# Idea").build();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let note = vault.path().join("Inbox/Idea.md").to_string_lossy().to_string();
+
+        let opened = open_note(vault_path.clone(), note.clone()).await.unwrap();
+
+        assert_eq!(opened.content, "# Idea");
+        assert_eq!(opened.metadata.path, crate::utils::normalize_path(&note));
+        assert_eq!(opened.metadata.filename, "Idea.md");
+        assert_eq!(opened.metadata.size, "\u{FEFF}# Idea".len() as u64);
+        assert!(!opened.metadata.is_dir);
+        // Milliseconds, like every other `FileMetadata`
+        assert!(opened.metadata.last_modified > 1_000_000_000_000);
+
+        let missing = vault.path().join("Inbox/Gone.md").to_string_lossy().to_string();
+        let err = open_note(vault_path, missing).await.unwrap_err();
+        assert!(matches!(err, crate::error::TessellumError::NotFound(_)));
+    }
+
+    #[test]
+    fn pending_index_flushes_only_after_saves_settle() {
+        let mut pending = crate::models::PendingIndexState::default();
+
+        assert!(pending.mark_dirty("/vault", "/vault/B.md"));
+        let seen = pending.generation;
+        assert!(!pending.mark_dirty("/vault", "/vault/A.md"));
+        assert!(pending.take_settled(seen).is_none());
+
+        let seen = pending.generation;
+        let batches = pending.take_settled(seen).unwrap();
+        assert_eq!(batches, vec![("/vault".to_string(), vec!["/vault/A.md".to_string(), "/vault/B.md".to_string()])]);
+        assert!(pending.dirty.is_empty());
+        assert!(pending.mark_dirty("/vault", "/vault/A.md"));
+    }
+
+    #[test]
+    fn pending_index_keeps_each_vaults_notes_apart() {
+        let mut pending = crate::models::PendingIndexState::default();
+        pending.mark_dirty("/work", "/work/Plan.md");
+        pending.mark_dirty("/home", "/home/Diary.md");
+        pending.mark_dirty("/home", "/home/Todo.md");
+        pending.forget("/home", "/home/Todo.md");
+        pending.forget("/work", "/home/Diary.md");
+
+        let seen = pending.generation;
+        assert_eq!(
+            pending.take_settled(seen).unwrap(),
+            vec![
+                ("/home".to_string(), vec!["/home/Diary.md".to_string()]),
+                ("/work".to_string(), vec!["/work/Plan.md".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn note_metadata_helpers_skip_frontmatter_and_read_starred_flag() {
+        let content = "---\nstarred: true\ntags: [a]\n---\nOne two  three\nfour";
+        assert_eq!(count_words(content), 4);
+
+        assert!(is_starred(Some(r#"{"starred":true}"#)));
+        assert!(is_starred(Some(r#"{"starred":"TRUE"}"#)));
+        assert!(!is_starred(Some(r#"{"starred":false}"#)));
+        assert!(!is_starred(Some(r#"{"title":"x"}"#)));
+        assert!(!is_starred(None));
+    }
+
+    #[test]
+    fn note_stats_count_readable_text_only() {
+        let content = "---\ntitle: Ignored words here\n---\n# Heading\n\nSee [[Some Note|the alias]] and **bold** `code`.\n\n![[pic.png]]\n";
+
+        assert_eq!(
+            note_stats(content),
+            NoteStats {
+                word_count: 7,
+                char_count: "Heading See the alias and bold code.".len(),
+                reading_time_minutes: 1,
+            }
+        );
+        assert_eq!(note_stats("").reading_time_minutes, 0);
+        assert_eq!(note_stats(&"word ".repeat(401)).reading_time_minutes, 3);
+    }
+
     #[test]
     fn validate_relative_note_path_accepts_plain_nested_paths() {
         assert!(validate_relative_note_path("Daily/2026/07/21.md").is_ok());
@@ -1092,6 +2649,26 @@ mod tests {
         assert_eq!(listed[1].display_name, "Alpha.md");
     }
     
+    #[test]
+    fn list_trash_items_decodes_original_folder_and_lists_folders_once() {
+        let dir = tempdir().unwrap();
+        let vault = dir.path();
+        let trash = vault.join(".trash");
+        let trashed_dir = trash.join("Project (p:Work%2FClients) 3000");
+        fs::create_dir_all(&trashed_dir).unwrap();
+        fs::write(trashed_dir.join("Child (p:) 3000.md"), "").unwrap();
+        fs::write(trash.join("Legacy (Archive) 1000.md"), "").unwrap();
+        
+        let listed = list_trash_items_internal(vault).unwrap();
+        
+        assert_eq!(listed.len(), 2);
+        assert!(listed[0].is_dir);
+        assert_eq!(listed[0].original_name, "Project");
+        assert_eq!(listed[0].original_folder, "Work/Clients");
+        assert_eq!(listed[0].timestamp, 3000);
+        assert_eq!(listed[1].original_folder, "Archive");
+    }
+    
     #[test]
     fn restore_trash_item_moves_file_back_to_root_with_clean_name() {
         let dir = tempdir().unwrap();
@@ -1145,4 +2722,90 @@ mod tests {
             "nested"
         );
     }
+    
+    #[test]
+    fn restore_trash_item_recreates_deleted_original_folder() {
+        let dir = tempdir().unwrap();
+        let vault = dir.path();
+        let trash = vault.join(".trash");
+        fs::create_dir_all(&trash).unwrap();
+        let trashed = trash.join("Note (p:Projects%2FAlpha) 1740681450123.md");
+        fs::write(&trashed, "restored").unwrap();
+        
+        let restored_path = restore_trash_item_internal_for_tests(vault, &trashed).unwrap();
+        
+        assert_eq!(restored_path, vault.join("Projects/Alpha/Note.md"));
+        assert_eq!(fs::read_to_string(restored_path).unwrap(), "restored");
+    }
+    
+    #[test]
+    fn restore_trash_item_never_recreates_folders_outside_the_vault() {
+        let dir = tempdir().unwrap();
+        let vault = dir.path().join("vault");
+        let trash = vault.join(".trash");
+        fs::create_dir_all(&trash).unwrap();
+        let trashed = trash.join("Note (p:..%2Fescape) 1740681450123.md");
+        fs::write(&trashed, "restored").unwrap();
+        
+        let restored_path = restore_trash_item_internal_for_tests(&vault, &trashed).unwrap();
+        
+        assert_eq!(restored_path, vault.join("Note.md"));
+        assert!(!dir.path().join("escape").exists());
+    }
+    
+    #[tokio::test]
+    async fn duplicate_note_copies_contents_and_indexes_links() {
+        let vault = TestVault::new()
+            .with_markdown("Notes/Plan.md", "See [[Target]]")
+            .with_markdown("Notes/Plan (copy).md", "older copy")
+            .with_markdown("Target.md", "")
+            .build();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let state = TestAppState::new().await;
+        let original = vault.path().join("Notes/Plan.md").to_string_lossy().to_string();
+        
+        let delta = duplicate_note_inner(&state, &vault_path, &original).await.unwrap();
+        
+        let copy = vault.path().join("Notes/Plan (copy) (1).md");
+        assert_eq!(delta.note_id, crate::utils::normalize_path(&copy.to_string_lossy()));
+        assert_eq!(fs::read_to_string(&copy).unwrap(), "See [[Target]]");
+        let target = crate::utils::normalize_path(&vault.path().join("Target.md").to_string_lossy());
+        let backlinks = state.db.get_backlinks(&target).await.unwrap();
+        assert!(backlinks.contains(&delta.note_id));
+    }
+    
+    #[tokio::test]
+    async fn duplicate_note_rejects_files_that_are_not_notes() {
+        let vault = TestVault::new().with_markdown("Note.md", "").build();
+        fs::write(vault.path().join("diagram.png"), b"png").unwrap();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let state = TestAppState::new().await;
+        let image = vault.path().join("diagram.png").to_string_lossy().to_string();
+        
+        let err = duplicate_note_inner(&state, &vault_path, &image).await.unwrap_err();
+        
+        assert!(matches!(err, crate::error::TessellumError::Validation(_)));
+        assert!(!vault.path().join("diagram (copy).md").exists());
+    }
+    
+    #[test]
+    fn unique_note_path_appends_numeric_suffix_on_collision() {
+        let dir = tempdir().unwrap();
+        
+        assert_eq!(unique_note_path(dir.path(), "Idea.md"), dir.path().join("Idea.md"));
+        
+        fs::write(dir.path().join("Idea.md"), "").unwrap();
+        fs::write(dir.path().join("Idea (1).md"), "").unwrap();
+        assert_eq!(unique_note_path(dir.path(), "Idea"), dir.path().join("Idea (2).md"));
+    }
+
+    #[test]
+    fn unique_note_path_drops_md_suffix_in_any_case() {
+        let dir = tempdir().unwrap();
+        
+        assert_eq!(unique_note_path(dir.path(), "Note.MD"), dir.path().join("Note.md"));
+        assert_eq!(unique_note_path(dir.path(), "Note.Md"), dir.path().join("Note.md"));
+        assert_eq!(strip_md_suffix("md"), "md");
+        assert_eq!(strip_md_suffix("Notes.mdx"), "Notes.mdx");
+    }
 }
@@ -12,7 +12,7 @@ pub struct PublishResult {
     pub output_dir: String,
 }
 
-const CSS: &str = r#"/* Tessellum static site */
+pub(crate) const CSS: &str = r#"/* Tessellum static site */
 *, *::before, *::after { box-sizing: border-box; }
 body {
     margin: 0;
@@ -58,6 +58,20 @@ blockquote {
     border-left: 3px solid #d1d5db;
     color: #6b7280;
 }
+.callout {
+    margin: 1rem 0;
+    padding: 0.75rem 1rem;
+    border-left: 4px solid #9ca3af;
+    border-radius: 4px;
+    background: #f9fafb;
+}
+.callout-title { font-weight: 600; margin-bottom: 0.25rem; }
+.callout-content > :first-child { margin-top: 0; }
+.callout-content > :last-child { margin-bottom: 0; }
+.callout-note { border-left-color: #3b82f6; background: #eff6ff; }
+.callout-info { border-left-color: #06b6d4; background: #ecfeff; }
+.callout-tip { border-left-color: #10b981; background: #ecfdf5; }
+.callout-warning { border-left-color: #f59e0b; background: #fffbeb; }
 hr { border: none; border-top: 1px solid #e5e7eb; margin: 2rem 0; }
 img { max-width: 100%; height: auto; }
 table { border-collapse: collapse; width: 100%; margin: 1rem 0; }
@@ -99,7 +113,8 @@ fn html_page(title: &str, site_title: &str, body: &str, depth: usize) -> String
     )
 }
 
-fn escape_html(s: &str) -> String {
+/// Escape text for use in HTML content or a double-quoted attribute.
+pub(crate) fn escape_html(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
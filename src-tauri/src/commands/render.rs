@@ -0,0 +1,330 @@
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag, TagEnd, TextMergeStream};
+use regex::Regex;
+use tauri::State;
+
+use crate::utils::WIKILINK_RE;
+use crate::commands::publish::escape_html;
+use crate::error::TessellumError;
+use crate::models::{AppState, AssetIndex, FileIndex, LinkStrategy, WikiLink};
+
+/// `[!type] Title` opening an Obsidian callout; `+`/`-` fold markers are accepted.
+static CALLOUT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\[!([A-Za-z0-9_-]+)\][+-]?(?:\s+(.*))?$").unwrap());
+
+/// Callout types with their own `callout-<type>` class; others get `callout-generic`.
+const CALLOUT_TYPES: &[&str] = &["note", "info", "tip", "warning"];
+
+/// HTML for one `[[...]]` or `![[...]]` match.
+///
+/// Note links become `internal-link` anchors carrying the resolved path in
+/// `data-path` (and the heading or block in `data-anchor`); the frontend
+/// handles navigation. Embedded attachments become `<img>` tags. Notes
+/// resolve as linked from `source` under `strategy`.
+fn wikilink_html(
+    link: &WikiLink,
+    vault_path: &str,
+    source: &Path,
+    file_index: &FileIndex,
+    asset_index: &AssetIndex,
+    strategy: LinkStrategy,
+) -> String {
+    let label = link.alias.as_deref().unwrap_or(&link.target);
+    let note = file_index.resolve_with(vault_path, &link.target, source, strategy);
+
+    if link.is_embed
+        && note.is_none()
+        && let Some(asset) = asset_index.resolve(vault_path, &link.target)
+    {
+        let src = crate::utils::normalize_path(&asset.to_string_lossy());
+        return format!("<img src=\"{}\" alt=\"{}\" />", escape_html(&src), escape_html(label));
+    }
+
+    let (class, path) = match note {
+        Some(path) => ("internal-link", crate::utils::normalize_path(&path.to_string_lossy())),
+        None => ("internal-link is-unresolved", link.target.clone()),
+    };
+    let anchor = match (&link.heading, &link.block_id) {
+        (Some(heading), _) => format!(" data-anchor=\"{}\"", escape_html(heading)),
+        (None, Some(block)) => format!(" data-anchor=\"^{}\"", escape_html(block)),
+        (None, None) => String::new(),
+    };
+    format!(
+        "<a class=\"{class}\" data-path=\"{}\"{anchor} href=\"#\">{}</a>",
+        escape_html(&path),
+        escape_html(label),
+    )
+}
+
+/// Split a text run into plain text and rendered wikilinks.
+fn rewrite_wikilinks<'a>(
+    text: &str,
+    vault_path: &str,
+    source: &Path,
+    file_index: &FileIndex,
+    asset_index: &AssetIndex,
+    strategy: LinkStrategy,
+) -> Vec<Event<'a>> {
+    let mut events = Vec::new();
+    let mut last = 0;
+    for caps in WIKILINK_RE.captures_iter(text) {
+        // Escaped `\[[...]]` stays literal text.
+        if caps.get(2).is_some() {
+            continue;
+        }
+        let whole = caps.get(0).expect("capture 0 is the whole match");
+        let link = WikiLink {
+            is_embed: caps.get(1).is_some(),
+            ..WikiLink::parse(&caps[3])
+        };
+        if link.target.is_empty() {
+            continue;
+        }
+        if whole.start() > last {
+            events.push(Event::Text(CowStr::from(text[last..whole.start()].to_string())));
+        }
+        events.push(Event::InlineHtml(CowStr::from(wikilink_html(
+            &link,
+            vault_path,
+            source,
+            file_index,
+            asset_index,
+            strategy,
+        ))));
+        last = whole.end();
+    }
+    if last < text.len() {
+        events.push(Event::Text(CowStr::from(text[last..].to_string())));
+    }
+    events
+}
+
+/// Opening HTML for a callout of `kind`, with `title` or the capitalized kind.
+fn callout_open_html(kind: &str, title: Option<&str>) -> String {
+    let kind = kind.to_lowercase();
+    let class = if CALLOUT_TYPES.contains(&kind.as_str()) { kind.as_str() } else { "generic" };
+    let title = match title.map(str::trim).filter(|t| !t.is_empty()) {
+        Some(title) => title.to_string(),
+        None => {
+            let mut chars = kind.chars();
+            chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+        }
+    };
+    format!(
+        "<div class=\"callout callout-{class}\" data-callout=\"{}\">\n<div class=\"callout-title\">{}</div>\n<div class=\"callout-content\">\n",
+        escape_html(&kind),
+        escape_html(&title),
+    )
+}
+
+/// Turn blockquotes whose first line is `[!type] Title` into callout blocks.
+///
+/// The marker line becomes the title and the rest of the quote the body.
+/// Other blockquotes, including nested ones, pass through unchanged.
+fn convert_callouts(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    let mut events = VecDeque::from(events);
+    let mut out = Vec::with_capacity(events.len());
+    // One entry per open blockquote: whether it was turned into a callout.
+    let mut open_quotes = Vec::new();
+    while let Some(event) = events.pop_front() {
+        match event {
+            Event::Start(Tag::BlockQuote(kind)) => {
+                let open = match (events.front(), events.get(1)) {
+                    (Some(Event::Start(Tag::Paragraph)), Some(Event::Text(text))) => CALLOUT_RE
+                        .captures(text)
+                        .map(|caps| callout_open_html(&caps[1], caps.get(2).map(|m| m.as_str()))),
+                    _ => None,
+                };
+                let Some(open) = open else {
+                    open_quotes.push(false);
+                    out.push(Event::Start(Tag::BlockQuote(kind)));
+                    continue;
+                };
+                open_quotes.push(true);
+                out.push(Event::Html(CowStr::from(open)));
+                // Drop the paragraph start and marker line. A line break after
+                // it starts the body; a marker-only paragraph is dropped whole.
+                events.drain(..2);
+                match events.front() {
+                    Some(Event::SoftBreak | Event::HardBreak) => {
+                        events.pop_front();
+                        out.push(Event::Start(Tag::Paragraph));
+                    }
+                    Some(Event::End(TagEnd::Paragraph)) => {
+                        events.pop_front();
+                    }
+                    _ => out.push(Event::Start(Tag::Paragraph)),
+                }
+            }
+            Event::End(TagEnd::BlockQuote(kind)) => {
+                if open_quotes.pop().unwrap_or(false) {
+                    out.push(Event::Html(CowStr::from("</div>\n</div>\n")));
+                } else {
+                    out.push(Event::End(TagEnd::BlockQuote(kind)));
+                }
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Render note markdown to HTML, resolving wikilinks and attachment embeds.
+///
+/// Frontmatter is dropped. Fenced and indented code blocks render as
+/// `<pre><code>` with their contents left untouched. Obsidian callouts
+/// (`> [!note] Title`) become `<div class="callout callout-note">` blocks.
+/// Raw HTML written in the note is escaped and shows as text; only the
+/// markup generated here for wikilinks and callouts reaches the output.
+/// Wikilinks resolve as written in the note at `source` under `strategy`.
+pub fn render_markdown_html(
+    content: &str,
+    vault_path: &str,
+    source: &Path,
+    file_index: &FileIndex,
+    asset_index: &AssetIndex,
+    strategy: LinkStrategy,
+) -> String {
+    let body = crate::utils::frontmatter::strip_frontmatter(content);
+    let mut opts = Options::empty();
+    opts.insert(Options::ENABLE_STRIKETHROUGH);
+    opts.insert(Options::ENABLE_TABLES);
+    opts.insert(Options::ENABLE_TASKLISTS);
+
+    // The parser splits text at brackets; merge runs so `[[...]]` arrives whole.
+    let mut in_code_block = false;
+    let parsed = Parser::new_ext(body, opts).map(|event| match event {
+        Event::Html(raw) => Event::Html(CowStr::from(escape_html(&raw))),
+        Event::InlineHtml(raw) => Event::InlineHtml(CowStr::from(escape_html(&raw))),
+        other => other,
+    });
+    let events = convert_callouts(TextMergeStream::new(parsed).collect());
+    let events = events.into_iter().flat_map(|event| match event {
+        Event::Start(Tag::CodeBlock(_)) => {
+            in_code_block = true;
+            vec![event]
+        }
+        Event::End(TagEnd::CodeBlock) => {
+            in_code_block = false;
+            vec![event]
+        }
+        Event::Text(text) if !in_code_block && text.contains("[[") => {
+            rewrite_wikilinks(&text, vault_path, source, file_index, asset_index, strategy)
+        }
+        other => vec![other],
+    });
+
+    let mut output = String::new();
+    html::push_html(&mut output, events);
+    output
+}
+
+/// Render a note to HTML for the read-only preview and export.
+///
+/// `source_path` is the note being rendered; without it, relative links fall
+/// back to shortest-path rules.
+#[tauri::command]
+pub async fn render_markdown(
+    state: State<'_, AppState>,
+    vault_path: String,
+    content: String,
+    source_path: Option<String>,
+) -> Result<String, TessellumError> {
+    let file_index = state.current_file_index(&vault_path).await?;
+    let asset_index = state.current_asset_index(&vault_path).await?;
+    let strategy = crate::utils::config::read_config(&vault_path).link_resolution;
+    let source = source_path.as_deref().unwrap_or(&vault_path);
+
+    Ok(render_markdown_html(
+        &content,
+        &vault_path,
+        Path::new(source),
+        &file_index,
+        &asset_index,
+        strategy,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_markdown_html;
+    use crate::models::{AssetIndex, FileIndex, LinkStrategy};
+    use crate::test_support::TestVault;
+
+    #[test]
+    fn renders_wikilinks_embeds_and_untouched_code_blocks() {
+        let vault = TestVault::new()
+            .with_markdown("Projects/Plan.md", "# Plan")
+            .build();
+        std::fs::write(vault.path().join("diagram.png"), "png").unwrap();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let plan = crate::utils::normalize_path(&vault.path().join("Projects/Plan.md").to_string_lossy());
+        let image = crate::utils::normalize_path(&vault.path().join("diagram.png").to_string_lossy());
+
+        let html = render_markdown_html(
+            "---\ntitle: x\n---\nSee [[Plan#Goals|the plan]] and [[Missing]].\n\n![[diagram.png]]\n\n```\n[[Plan]]\n```\n",
+            &vault_path,
+            vault.path(),
+            &FileIndex::build(&vault_path).unwrap(),
+            &AssetIndex::build(&vault_path).unwrap(),
+            LinkStrategy::ShortestPath,
+        );
+
+        assert!(!html.contains("title: x"));
+        assert!(html.contains(&format!(
+            "<a class=\"internal-link\" data-path=\"{plan}\" data-anchor=\"Goals\" href=\"#\">the plan</a>"
+        )));
+        assert!(html.contains(
+            "<a class=\"internal-link is-unresolved\" data-path=\"Missing\" href=\"#\">Missing</a>."
+        ));
+        assert!(html.contains(&format!("<img src=\"{image}\" alt=\"diagram.png\" />")));
+        assert!(html.contains("<pre><code>[[Plan]]\n</code></pre>"));
+    }
+
+    #[test]
+    fn renders_callouts_with_titles_and_leaves_plain_quotes_alone() {
+        let vault = TestVault::new().build();
+        let vault_path = vault.path().to_string_lossy().to_string();
+
+        let html = render_markdown_html(
+            "> [!warning] Mind & gap\n> Body with **bold**.\n\n> [!custom]\n\n> [!tip]-\n> Folded.\n\n> Just a quote.\n",
+            &vault_path,
+            vault.path(),
+            &FileIndex::build(&vault_path).unwrap(),
+            &AssetIndex::build(&vault_path).unwrap(),
+            LinkStrategy::ShortestPath,
+        );
+
+        assert!(html.contains(
+            "<div class=\"callout callout-warning\" data-callout=\"warning\">\n<div class=\"callout-title\">Mind &amp; gap</div>\n<div class=\"callout-content\">\n<p>Body with <strong>bold</strong>.</p>\n</div>\n</div>"
+        ));
+        assert!(html.contains(
+            "<div class=\"callout callout-generic\" data-callout=\"custom\">\n<div class=\"callout-title\">Custom</div>\n<div class=\"callout-content\">\n</div>\n</div>"
+        ));
+        assert!(html.contains("<div class=\"callout-title\">Tip</div>\n<div class=\"callout-content\">\n<p>Folded.</p>"));
+        assert!(html.contains("<blockquote>\n<p>Just a quote.</p>\n</blockquote>"));
+    }
+
+    #[test]
+    fn escapes_raw_html_from_the_note() {
+        let vault = TestVault::new().build();
+        let vault_path = vault.path().to_string_lossy().to_string();
+
+        let html = render_markdown_html(
+            "<script>alert(1)</script>\n\nSome <img src=x onerror=\"alert(2)\"> text.\n",
+            &vault_path,
+            vault.path(),
+            &FileIndex::build(&vault_path).unwrap(),
+            &AssetIndex::build(&vault_path).unwrap(),
+            LinkStrategy::ShortestPath,
+        );
+
+        assert!(!html.contains("<script>"));
+        assert!(!html.contains("<img"));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(html.contains("Some &lt;img src=x onerror=&quot;alert(2)&quot;&gt; text."));
+    }
+}
@@ -7,7 +7,7 @@ use crate::commands::indexer::run_sync_vault;
 use crate::error::TessellumError;
 use crate::grafeo_projection::ManagedGrafeoConnection;
 use crate::models::{AppState, SearchReadinessState, SearchReadinessStatus};
-use crate::utils::is_hidden_or_special;
+use crate::utils::IgnoreRules;
 use crate::utils::normalize_path;
 use walkdir::WalkDir;
 
@@ -111,6 +111,16 @@ pub struct SearchReadinessResponse {
 	pub last_error: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteContentMatch {
+	pub path: String,
+	pub relative_path: String,
+	/// Context around the first matches, with hits wrapped in `<mark>` tags.
+	pub excerpt: String,
+	/// Whole-token hits of the query terms, as the full-text index counts them.
+	pub match_count: u32,
+}
+
 struct CoherenceResult {
 	expected_markdown_count: usize,
 	indexed_markdown_count: usize,
@@ -440,6 +450,35 @@ pub async fn search_full_text(
 	})
 }
 
+/// Search note bodies through the SQLite full-text index.
+///
+/// Matching is case-insensitive and every word in `query` must appear.
+#[tauri::command]
+pub async fn search_note_contents(
+	state: State<'_, AppState>,
+	vault_path: String,
+	query: String,
+	limit: Option<u32>,
+) -> Result<Vec<NoteContentMatch>, TessellumError> {
+	crate::utils::validate_vault_root(&vault_path)?;
+	
+	let rows = state
+		.db
+		.search_note_contents(&query, limit.unwrap_or(25) as i64)
+		.await
+		.map_err(TessellumError::from)?;
+	
+	Ok(rows
+		.into_iter()
+		.map(|(path, excerpt, match_count)| NoteContentMatch {
+			relative_path: make_relative_path(&vault_path, &path),
+			path,
+			excerpt,
+			match_count: match_count as u32,
+		})
+		.collect())
+}
+
 #[tauri::command]
 pub async fn search_tags(
 	state: State<'_, AppState>,
@@ -498,13 +537,14 @@ async fn rebuild_search_index_internal(
 		guard.clear()?;
 		let mut docs = Vec::new();
 		let mut seen_paths = Vec::new();
+		let ignore = IgnoreRules::load(&vault_clone);
 		
-		for entry in WalkDir::new(&vault_clone).into_iter().filter_map(|e| e.ok()) {
+		for entry in WalkDir::new(&vault_clone)
+			.into_iter()
+			.filter_entry(|e| ignore.keeps(&vault_clone, e))
+			.filter_map(|e| e.ok())
+		{
 			let path = entry.path();
-			let rel_path = path.strip_prefix(&vault_clone).unwrap_or(path);
-			if is_hidden_or_special(rel_path) {
-				continue;
-			}
 			if !path.is_file() {
 				continue;
 			}
@@ -0,0 +1,46 @@
+use tauri::State;
+
+use crate::error::TessellumError;
+use crate::models::AppState;
+use crate::utils::config::{read_config, save_config, AppConfig};
+use crate::utils::validate_vault_root;
+
+/// Read the vault settings from `.tessellum/config.json`.
+///
+/// Missing or malformed files yield the defaults; nothing is written.
+#[tauri::command]
+pub async fn load_settings(vault_path: String) -> Result<AppConfig, TessellumError> {
+    validate_vault_root(&vault_path)?;
+    Ok(read_config(&vault_path))
+}
+
+/// Replace the vault settings in `.tessellum/config.json`.
+///
+/// Ignore patterns may have changed, so the cached file and asset indexes are
+/// dropped and rebuilt on next use.
+#[tauri::command]
+pub async fn save_settings(
+    state: State<'_, AppState>,
+    vault_path: String,
+    settings: AppConfig,
+) -> Result<(), TessellumError> {
+    validate_vault_root(&vault_path)?;
+    save_config(&vault_path, &settings)?;
+    *state.file_index.lock().await = None;
+    *state.asset_index.lock().await = None;
+    Ok(())
+}
+
+/// Change how much the backend logs to stderr until the app exits.
+///
+/// Accepts `off`, `error`, `warn`, `info`, `debug` or `trace`; the startup
+/// level comes from the `TESSELLUM_LOG` environment variable.
+#[tauri::command]
+pub async fn set_log_level(level: String) -> Result<(), TessellumError> {
+    let filter = level
+        .trim()
+        .parse::<log::LevelFilter>()
+        .map_err(|_| TessellumError::Validation(format!("Unknown log level '{}'", level)))?;
+    crate::logging::set_level(filter);
+    Ok(())
+}
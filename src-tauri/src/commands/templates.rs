@@ -5,8 +5,10 @@ use chrono::{DateTime, Local};
 use serde::Serialize;
 use tauri::State;
 
+use crate::commands::notes::{strip_md_suffix, unique_note_path};
 use crate::error::TessellumError;
 use crate::models::AppState;
+use crate::utils::config::load_or_init_config;
 use crate::utils::{normalize_path, sanitize_string, validate_path_in_vault};
 
 #[derive(Serialize)]
@@ -79,6 +81,11 @@ pub async fn list_templates(vault_path: String) -> Result<Vec<TemplateInfo>, Tes
 	Ok(templates)
 }
 
+/// Create a note in `target_dir` from a template and return its path.
+///
+/// `{{title}}`, `{{date}}`, `{{time}}`, `{{datetime}}` and `{{vault}}` are
+/// substituted; unknown placeholders are kept verbatim. Name collisions get
+/// the same numeric suffix as `create_note`.
 #[tauri::command]
 pub async fn create_note_from_template(
 	state: State<'_, AppState>,
@@ -95,30 +102,19 @@ pub async fn create_note_from_template(
 		.await
 		.map_err(TessellumError::from)?;
 	
-	let clean_title = sanitize_string(title);
+	let policy = load_or_init_config(&vault_path)?.filenames;
+	let clean_title = sanitize_string(title, &policy);
 	if clean_title.trim().is_empty() {
 		return Err(TessellumError::Validation(
 			"Title cannot be empty".to_string(),
 		));
 	}
 	
-	let mut filename = if clean_title.to_lowercase().ends_with(".md") {
-		clean_title.clone()
-	} else {
-		format!("{}.md", clean_title)
-	};
-	let mut file_path = Path::new(&target_dir).join(&filename);
-	let mut collision_index = 1;
-	
-	while file_path.exists() {
-		let stem = clean_title.strip_suffix(".md").unwrap_or(&clean_title);
-		filename = format!("{} ({}).md", stem, collision_index);
-		file_path = Path::new(&target_dir).join(&filename);
-		collision_index += 1;
-	}
+	let file_path = unique_note_path(Path::new(&target_dir), &clean_title);
+	let title = strip_md_suffix(&clean_title);
 	
 	let processed_content =
-		apply_placeholders(&template_content, &clean_title, &target_dir, Local::now());
+		apply_placeholders(&template_content, title, &vault_path, Local::now());
 	
 	tokio::fs::write(&file_path, &processed_content)
 		.await
@@ -132,10 +128,9 @@ pub async fn create_note_from_template(
 		.await
 		.unwrap_or_else(|e| log::warn!("Failed to index new file: {}", e));
 	
-	let mut idx_guard = state.file_index.lock().await;
-	*idx_guard = None;
-	let mut asset_guard = state.asset_index.lock().await;
-	*asset_guard = None;
+	if let Some(idx) = state.file_index.lock().await.as_mut() {
+		idx.insert(&file_path);
+	}
 	
 	Ok(path_str)
 }
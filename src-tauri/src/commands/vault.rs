@@ -1,18 +1,76 @@
-use std::path::Path;
-use std::time::UNIX_EPOCH;
+use std::path::{Path, PathBuf};
 use tauri_plugin_fs::FsExt;
 use walkdir::WalkDir;
 
+use crate::commands::links::rebase_relative_links;
 use crate::error::TessellumError;
 use crate::grafeo_projection::ManagedGrafeoConnection;
 use crate::models::FileMetadata;
 use crate::search::SearchDoc;
 use crate::trash::purge_expired_trash;
-use crate::utils::{extract_tags, is_hidden_or_special, sanitize_string, validate_path_in_vault};
+use crate::utils::config::load_or_init_config;
+use crate::utils::frontmatter::extract_first_h1;
+use crate::utils::{
+    extract_tags, sanitize_string, validate_path_in_vault, validate_vault_root, IgnoreRules,
+};
 
 const FEATURE_DEMO_FILENAME: &str = "FEATURE_DEMO.md";
 const FEATURE_DEMO_CONTENT: &str = include_str!("../../../FEATURE_DEMO.md");
 
+/// Build the matcher used to find wikilinks pointing at `old_stem`.
+///
+/// Matches `[[OldStem]]` and `[[.../OldStem]]` (with optional folder prefix ending
+/// in `/`) and an optional alias after a pipe. Uses `(?i)` for case-insensitive
+/// matching so case-only renames (e.g. "Note" → "note") are also rewritten.
+pub(crate) fn backlink_rewrite_regex(old_stem: &str) -> Result<regex::Regex, TessellumError> {
+    let escaped = regex::escape(old_stem);
+    let pattern = format!(r"(?i)(\\?)\[\[([^\]|]*?/)?({escaped})(#[^\]|]*)?(\|[^\]]+)?\]\]");
+    regex::Regex::new(&pattern)
+        .map_err(|e| TessellumError::Internal(format!("Link-rewrite regex error: {e}")))
+}
+
+/// Apply the backlink rewrite to `text`, leaving escaped links untouched.
+///
+/// `links_to_renamed` receives each matched link target (without anchor) and
+/// decides whether it really points at the renamed note; other matches, such
+/// as a same-named note in another folder, are left as they are.
+pub(crate) fn rewrite_links_in_text<'t>(
+    re: &regex::Regex,
+    text: &'t str,
+    new_stem: &str,
+    links_to_renamed: &dyn Fn(&str) -> bool,
+) -> std::borrow::Cow<'t, str> {
+    re.replace_all(text, |caps: &regex::Captures<'_>| {
+        // If preceded by a backslash, the link is escaped — leave it verbatim.
+        if caps.get(1).is_some_and(|m| m.as_str() == "\\") {
+            return caps[0].to_string();
+        }
+        let prefix = caps.get(2).map_or("", |m| m.as_str()); // e.g. "Folder/"
+        let anchor = caps.get(4).map_or("", |m| m.as_str());  // e.g. "#Section" or "#^block"
+        let alias = caps.get(5).map_or("", |m| m.as_str());   // e.g. "|Custom Label"
+        if !links_to_renamed(&format!("{prefix}{}", &caps[3])) {
+            return caps[0].to_string();
+        }
+        format!("[[{prefix}{new_stem}{anchor}{alias}]]")
+    })
+}
+
+/// Does a link target, written in the note at the given source, resolve to
+/// `old_path` under `strategy`? Bound to each source by the backlink rewrites.
+fn resolves_to_path<'a>(
+    file_index: &'a crate::models::FileIndex,
+    vault_path: &'a str,
+    old_path: &str,
+    strategy: crate::models::LinkStrategy,
+) -> impl Fn(&Path, &str) -> bool + 'a {
+    let old_path = crate::utils::normalize_path(old_path);
+    move |source, target| {
+        file_index
+            .resolve_with(vault_path, target, source, strategy)
+            .is_some_and(|path| crate::utils::normalize_path(&path.to_string_lossy()) == old_path)
+    }
+}
+
 /// Rewrite wikilinks from old_stem to new_stem in all files listed in `backlinks`.
 ///
 /// Handles three forms:
@@ -21,23 +79,19 @@ const FEATURE_DEMO_CONTENT: &str = include_str!("../../../FEATURE_DEMO.md");
 /// - `[[Folder/OldStem]]`     → `[[Folder/NewStem]]`
 /// - `[[Folder/OldStem|alias]]` → `[[Folder/NewStem|alias]]`
 ///
+/// A `#heading` or `#^block` anchor is carried over unchanged.
 /// Escaped links (`\[[OldStem]]`) are left unchanged.
 async fn rewrite_backlinks(
     backlinks: &[String],
     old_stem: &str,
     new_stem: &str,
+    links_to_renamed: &dyn Fn(&Path, &str) -> bool,
 ) -> Result<(), TessellumError> {
     if backlinks.is_empty() {
         return Ok(());
     }
 
-    // Match [[OldStem]] and [[.../OldStem]] (with optional folder prefix ending in /)
-    // and an optional alias after a pipe. Use (?i) for case-insensitive matching so
-    // case-only renames (e.g. "Note" → "note") are also rewritten.
-    let escaped = regex::escape(old_stem);
-    let pattern = format!(r"(?i)(\\?)\[\[([^\]|]*?/)?{escaped}(\|[^\]]+)?\]\]");
-    let re = regex::Regex::new(&pattern)
-        .map_err(|e| TessellumError::Internal(format!("Link-rewrite regex error: {e}")))?;
+    let re = backlink_rewrite_regex(old_stem)?;
 
     for source_path in backlinks {
         let content = match tokio::fs::read_to_string(source_path).await {
@@ -48,15 +102,8 @@ async fn rewrite_backlinks(
             }
         };
 
-        let new_content = re.replace_all(&content, |caps: &regex::Captures<'_>| {
-            // If preceded by a backslash, the link is escaped — leave it verbatim.
-            if caps.get(1).is_some_and(|m| m.as_str() == "\\") {
-                return caps[0].to_string();
-            }
-            let prefix = caps.get(2).map_or("", |m| m.as_str()); // e.g. "Folder/"
-            let alias = caps.get(3).map_or("", |m| m.as_str());   // e.g. "|Custom Label"
-            format!("[[{prefix}{new_stem}{alias}]]")
-        });
+        let links_here = |target: &str| links_to_renamed(Path::new(source_path), target);
+        let new_content = rewrite_links_in_text(&re, &content, new_stem, &links_here);
 
         if new_content != content
             && let Err(e) = tokio::fs::write(source_path, new_content.as_bytes()).await {
@@ -67,6 +114,70 @@ async fn rewrite_backlinks(
     Ok(())
 }
 
+/// A single line that a rename would rewrite.
+#[derive(Debug, serde::Serialize, PartialEq)]
+pub struct LinkLineChange {
+    /// 1-based line number within the source note.
+    pub line: usize,
+    pub old_text: String,
+    pub new_text: String,
+}
+
+/// All rewritten lines for one note that links to the renamed file.
+#[derive(Debug, serde::Serialize)]
+pub struct RenameLinkPreview {
+    pub source_path: String,
+    pub changes: Vec<LinkLineChange>,
+}
+
+/// Compute the line-level changes a backlink rewrite would make, without writing.
+async fn preview_backlink_rewrites(
+    backlinks: &[String],
+    old_stem: &str,
+    new_stem: &str,
+    links_to_renamed: &dyn Fn(&Path, &str) -> bool,
+) -> Result<Vec<RenameLinkPreview>, TessellumError> {
+    if backlinks.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let re = backlink_rewrite_regex(old_stem)?;
+    let mut previews = Vec::new();
+
+    for source_path in backlinks {
+        let content = match tokio::fs::read_to_string(source_path).await {
+            Ok(c) => c,
+            Err(e) => {
+                log::warn!("preview_backlink_rewrites: could not read '{source_path}': {e}");
+                continue;
+            }
+        };
+
+        let links_here = |target: &str| links_to_renamed(Path::new(source_path), target);
+        let changes: Vec<LinkLineChange> = content
+            .lines()
+            .enumerate()
+            .filter_map(|(index, line)| {
+                let rewritten = rewrite_links_in_text(&re, line, new_stem, &links_here);
+                (rewritten != line).then(|| LinkLineChange {
+                    line: index + 1,
+                    old_text: line.to_string(),
+                    new_text: rewritten.into_owned(),
+                })
+            })
+            .collect();
+
+        if !changes.is_empty() {
+            previews.push(RenameLinkPreview {
+                source_path: source_path.clone(),
+                changes,
+            });
+        }
+    }
+
+    Ok(previews)
+}
+
 fn derive_renamed_filename(old_path: &Path, clean_name: &str) -> String {
     if old_path.is_dir() {
         return clean_name.to_string();
@@ -85,8 +196,17 @@ fn derive_renamed_filename(old_path: &Path, clean_name: &str) -> String {
     clean_name.to_string()
 }
 
+/// Build the `FileMetadata` for a walked entry, or `None` if its metadata is unreadable.
+fn file_metadata_from_entry(entry: &walkdir::DirEntry) -> Option<FileMetadata> {
+    let meta = entry.metadata().ok()?;
+    Some(FileMetadata::from_fs(entry.path(), &meta))
+}
+
 /// Lists all files and directories within the specified vault path and retrieves their metadata.
 ///
+/// The walk runs on the blocking thread pool so large vaults do not stall the
+/// async runtime while the vault is opening.
+///
 /// # Arguments
 ///
 /// * `vault_path` - A `String` specifying the path of the directory to scan.
@@ -96,59 +216,112 @@ fn derive_renamed_filename(old_path: &Path, clean_name: &str) -> String {
 /// * `Ok(Vec<FileMetadata>)` containing a vector of `FileMetadata` structs.
 /// * `Err(String)` containing an error message if the vault path does not exist.
 #[tauri::command]
-pub fn list_files(vault_path: String) -> Result<Vec<FileMetadata>, TessellumError> {
+pub async fn list_files(vault_path: String) -> Result<Vec<FileMetadata>, TessellumError> {
+    tokio::task::spawn_blocking(move || collect_vault_files(&vault_path))
+        .await
+        .map_err(|e| TessellumError::Internal(e.to_string()))?
+}
+
+/// Blocking walk behind [`list_files`], shared with the synchronous tree commands.
+fn collect_vault_files(vault_path: &str) -> Result<Vec<FileMetadata>, TessellumError> {
     let mut files = Vec::new();
     
-    // Check if path exists
-    if !Path::new(&vault_path).exists() {
-        return Err(TessellumError::NotFound(
-            "Vault path does not exist".to_string(),
-        ));
-    }
+    // Check the vault root exists and is a directory
+    validate_vault_root(vault_path)?;
+    
+    // Ignore hidden files/dirs (.git, .trash, etc.) and .tessellumignore matches
+    let ignore = IgnoreRules::load(vault_path);
     
     // For each entry in the vault directory that does not give an error, add it to the list
-    for entry in WalkDir::new(&vault_path)
+    for entry in WalkDir::new(vault_path)
         .min_depth(1)
         .into_iter()
+        .filter_entry(|e| ignore.keeps(vault_path, e))
         .filter_map(|e| e.ok())
     {
-        // Get the file path
-        let path = entry.path();
-        let path_str = path.to_string_lossy().to_string();
-        
-        // Ignore hidden files/dirs (.git, .trash, etc.)
-        if is_hidden_or_special(path) {
-            continue;
-        }
-        
         // If able to get metadata, add it to the list
-        if let Ok(meta) = entry.metadata() {
-            // Get the last modified time in milliseconds
-            let modified_time = meta
-                .modified()
-                .unwrap_or(UNIX_EPOCH)
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_millis() as i64;
-            
-            // Push the file metadata to the list
-            files.push(FileMetadata {
-                path: crate::utils::normalize_path(&path_str),
-                filename: path
-                    .file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy()
-                    .to_string(),
-                is_dir: meta.is_dir(),
-                size: meta.len(),
-                last_modified: modified_time,
-            });
+        if let Some(metadata) = file_metadata_from_entry(&entry) {
+            files.push(metadata);
         }
     }
     
     Ok(files)
 }
 
+/// Match vault-relative paths against a glob pattern.
+///
+/// Matching is case-insensitive and `*` may cross folder boundaries, so
+/// `*meeting*` finds any path containing "meeting" while `Projects/**/*.md`
+/// stays anchored at the vault root. Patterns without a `/` are also tried
+/// against the bare file or folder name.
+fn glob_matches(pattern: &glob::Pattern, pattern_has_separator: bool, relative_path: &str) -> bool {
+    let options = glob::MatchOptions {
+        case_sensitive: false,
+        require_literal_separator: false,
+        require_literal_leading_dot: false,
+    };
+    if pattern.matches_with(relative_path, options) {
+        return true;
+    }
+    if pattern_has_separator {
+        return false;
+    }
+    let name = relative_path.rsplit('/').next().unwrap_or(relative_path);
+    pattern.matches_with(name, options)
+}
+
+/// Find files and folders whose vault-relative path matches a glob pattern.
+///
+/// # Arguments
+///
+/// * `vault_path` - The vault root to walk.
+/// * `glob` - A pattern such as `Projects/**/*.md` or `*meeting*`.
+///
+/// # Returns
+///
+/// * `Ok(Vec<FileMetadata>)` for every match, hidden and `.tessellumignore`d entries excluded.
+/// * `Err(TessellumError::Validation)` if the pattern is invalid.
+#[tauri::command]
+pub async fn find_paths(vault_path: String, glob: String) -> Result<Vec<FileMetadata>, TessellumError> {
+    validate_vault_root(&vault_path)?;
+    
+    let trimmed = glob.trim().trim_start_matches("./");
+    if trimmed.is_empty() {
+        return Err(TessellumError::Validation("Glob pattern cannot be empty".to_string()));
+    }
+    let normalized = crate::utils::normalize_path(trimmed);
+    let pattern = glob::Pattern::new(&normalized)
+        .map_err(|e| TessellumError::Validation(format!("Invalid glob pattern: {e}")))?;
+    let has_separator = normalized.contains('/');
+    
+    tokio::task::spawn_blocking(move || {
+        let vault_root = Path::new(&vault_path);
+        let ignore = IgnoreRules::load(&vault_path);
+        let mut matches = Vec::new();
+        
+        for entry in WalkDir::new(vault_root)
+            .min_depth(1)
+            .into_iter()
+            .filter_entry(|e| ignore.keeps(vault_root, e))
+            .filter_map(|e| e.ok())
+        {
+            let relative = entry.path().strip_prefix(vault_root).unwrap_or(entry.path());
+            let relative = crate::utils::normalize_path(&relative.to_string_lossy());
+            if !glob_matches(&pattern, has_separator, &relative) {
+                continue;
+            }
+            if let Some(metadata) = file_metadata_from_entry(&entry) {
+                matches.push(metadata);
+            }
+        }
+        
+        matches.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(matches)
+    })
+    .await
+    .map_err(|e| TessellumError::Internal(e.to_string()))?
+}
+
 #[tauri::command]
 pub async fn ensure_feature_demo_in_empty_vault(vault_path: String) -> Result<bool, TessellumError> {
     let vault = Path::new(&vault_path);
@@ -158,7 +331,7 @@ pub async fn ensure_feature_demo_in_empty_vault(vault_path: String) -> Result<bo
         ));
     }
 
-    if !list_files(vault_path.clone())?.is_empty() {
+    if !list_files(vault_path.clone()).await?.is_empty() {
         return Ok(false);
     }
 
@@ -191,6 +364,17 @@ pub async fn rename_file(
     vault_path: String,
     old_path: String,
     new_name: String,
+) -> Result<String, TessellumError> {
+    rename_file_inner(&state, vault_path, old_path, new_name).await
+}
+
+/// Shared implementation of [`rename_file`]: renames on disk, rewrites backlinks,
+/// and moves the DB and search entries to the new path.
+async fn rename_file_inner(
+    state: &crate::models::AppState,
+    vault_path: String,
+    old_path: String,
+    new_name: String,
 ) -> Result<String, TessellumError> {
     // Validate old_path is inside the vault (using canonicalize to prevent traversal)
     validate_path_in_vault(&old_path, &vault_path).map_err(TessellumError::Validation)?;
@@ -202,7 +386,8 @@ pub async fn rename_file(
         TessellumError::Validation("Invalid path: No parent directory".to_string())
     })?;
     
-    let clean_name = sanitize_string(new_name);
+    let policy = load_or_init_config(&vault_path)?.filenames;
+    let clean_name = sanitize_string(new_name, &policy);
     
     if clean_name.trim().is_empty() {
         return Err(TessellumError::Validation(
@@ -241,6 +426,12 @@ pub async fn rename_file(
     
     // Capture stems before the rename (path no longer exists on disk after)
     let old_stem = old.file_stem().and_then(|s| s.to_str()).map(str::to_string);
+    // Snapshot link resolution while the old path still exists.
+    let file_index = if is_file {
+        Some(state.current_file_index(&vault_path).await?)
+    } else {
+        None
+    };
     let new_stem = new_path
         .file_stem()
         .and_then(|s| s.to_str())
@@ -264,7 +455,11 @@ pub async fn rename_file(
                     .await
                     .map_err(TessellumError::from)?;
 
-                rewrite_backlinks(&backlinks, os, ns).await?;
+                if let Some(file_index) = &file_index {
+                    let strategy = crate::utils::config::read_config(&vault_path).link_resolution;
+                    let links_to_renamed = resolves_to_path(file_index, &vault_path, &old_path, strategy);
+                    rewrite_backlinks(&backlinks, os, ns, &links_to_renamed).await?;
+                }
             }
     
     // Update the DB index so backlinks and graph stay correct
@@ -280,11 +475,22 @@ pub async fn rename_file(
     // Note: Grafeo sync happens automatically via file watcher/write_file command
     // No need for full sync on individual file rename
 
-    // Invalidate the cache since path has changed
-    let mut idx_guard = state.file_index.lock().await;
-    *idx_guard = None;
-    let mut asset_guard = state.asset_index.lock().await;
-    *asset_guard = None;
+    let is_markdown = is_file
+        && old.extension().and_then(|ext| ext.to_str()) == Some("md")
+        && new_path.extension().and_then(|ext| ext.to_str()) == Some("md");
+    if is_markdown {
+        // Only a note moved; patch the cached index instead of rebuilding it
+        let mut idx_guard = state.file_index.lock().await;
+        if let Some(idx) = idx_guard.as_mut() {
+            idx.rename(&old_path, &new_path);
+        }
+    } else {
+        // Invalidate the cache since path has changed
+        let mut idx_guard = state.file_index.lock().await;
+        *idx_guard = None;
+        let mut asset_guard = state.asset_index.lock().await;
+        *asset_guard = None;
+    }
     
     if is_file {
         let search_index = state.search_index.clone();
@@ -321,90 +527,378 @@ pub async fn rename_file(
     Ok(new_path.to_string_lossy().to_string())
 }
 
+/// Pick a stem that does not collide with an existing note in `parent`.
+/// Appends ` (1)`, ` (2)`, ... like `create_note`; `current` never counts as a collision.
+fn unique_note_stem(parent: &Path, stem: &str, current: &Path) -> String {
+    let is_free = |candidate: &str| {
+        let path = parent.join(format!("{}.md", candidate));
+        !path.exists() || path == current
+    };
+    if is_free(stem) {
+        return stem.to_string();
+    }
+    let mut index = 1;
+    loop {
+        let candidate = format!("{} ({})", stem, index);
+        if is_free(&candidate) {
+            return candidate;
+        }
+        index += 1;
+    }
+}
+
+/// Rename a note so its filename matches its first `# Heading`.
+///
+/// # Returns
+/// - `Ok(String)`: The note's path after the rename (unchanged if it already matches).
+/// - `Err(TessellumError::Validation)`: If the note has no H1 heading.
 #[tauri::command]
-pub async fn move_items(
+pub async fn rename_to_heading(
     state: tauri::State<'_, crate::models::AppState>,
-    _grafeo_state: tauri::State<'_, ManagedGrafeoConnection>,
     vault_path: String,
-    item_paths: Vec<String>,
-    dest_dir: String,
-) -> Result<Vec<String>, TessellumError> {
-    if item_paths.is_empty() {
-        return Ok(Vec::new());
+    path: String,
+) -> Result<String, TessellumError> {
+    rename_to_heading_inner(&state, vault_path, path).await
+}
+
+async fn rename_to_heading_inner(
+    state: &crate::models::AppState,
+    vault_path: String,
+    path: String,
+) -> Result<String, TessellumError> {
+    validate_path_in_vault(&path, &vault_path).map_err(TessellumError::Validation)?;
+    
+    let content = tokio::fs::read_to_string(&path).await?;
+    let heading = extract_first_h1(&content).ok_or_else(|| {
+        TessellumError::Validation("Note has no H1 heading to rename from".to_string())
+    })?;
+    let policy = load_or_init_config(&vault_path)?.filenames;
+    let clean_heading = sanitize_string(heading, &policy);
+    if clean_heading.trim().is_empty() {
+        return Err(TessellumError::Validation(
+            "Invalid name: Heading has no usable characters".to_string(),
+        ));
     }
     
-    validate_path_in_vault(&dest_dir, &vault_path)
-        .map_err(TessellumError::Validation)?;
+    let current = Path::new(&path);
+    if current.file_stem().and_then(|s| s.to_str()) == Some(clean_heading.as_str()) {
+        return Ok(crate::utils::normalize_path(&path));
+    }
     
-    let dest_path = Path::new(&dest_dir);
-    let dest_meta = tokio::fs::metadata(dest_path).await.map_err(TessellumError::from)?;
-    if !dest_meta.is_dir() {
+    let parent = current.parent().ok_or_else(|| {
+        TessellumError::Validation("Invalid path: No parent directory".to_string())
+    })?;
+    let new_stem = unique_note_stem(parent, &clean_heading, current);
+    
+    // Pass the extension explicitly so dots in the heading are not read as one.
+    rename_file_inner(state, vault_path, path, format!("{}.md", new_stem)).await
+}
+
+/// Dry run of the backlink rewrite performed by [`rename_file`].
+///
+/// # Parameters
+/// - `vault_path`: The root vault path for security validation.
+/// - `old_path`: The current path of the note to be renamed.
+/// - `new_name`: The proposed new name for the note.
+///
+/// # Returns
+/// - `Ok(Vec<RenameLinkPreview>)`: For each linking note, the lines that would change.
+///   Empty when the rename would not trigger a rewrite.
+#[tauri::command]
+pub async fn preview_rename_link_updates(
+    state: tauri::State<'_, crate::models::AppState>,
+    vault_path: String,
+    old_path: String,
+    new_name: String,
+) -> Result<Vec<RenameLinkPreview>, TessellumError> {
+    validate_path_in_vault(&old_path, &vault_path).map_err(TessellumError::Validation)?;
+
+    let old = Path::new(&old_path);
+    if !old.is_file() {
+        return Ok(Vec::new());
+    }
+
+    let policy = load_or_init_config(&vault_path)?.filenames;
+    let clean_name = sanitize_string(new_name, &policy);
+    if clean_name.trim().is_empty() {
         return Err(TessellumError::Validation(
-            "Destination must be a folder".to_string(),
+            "Invalid name: Filename cannot be empty".to_string(),
         ));
     }
+
+    let final_filename = derive_renamed_filename(old, &clean_name);
+    let old_stem = old.file_stem().and_then(|s| s.to_str());
+    let new_stem = Path::new(&final_filename).file_stem().and_then(|s| s.to_str());
+
+    // Mirror rename_file: only a stem change triggers the rewrite.
+    let (Some(os), Some(ns)) = (old_stem, new_stem) else {
+        return Ok(Vec::new());
+    };
+    if os.eq_ignore_ascii_case(ns) {
+        return Ok(Vec::new());
+    }
+
+    let backlinks = state
+        .db
+        .get_backlinks(&old_path)
+        .await
+        .map_err(TessellumError::from)?;
+
+    let file_index = state.current_file_index(&vault_path).await?;
+    let strategy = crate::utils::config::read_config(&vault_path).link_resolution;
+    let links_to_renamed = resolves_to_path(&file_index, &vault_path, &old_path, strategy);
+    preview_backlink_rewrites(&backlinks, os, ns, &links_to_renamed).await
+}
+
+/// Outcome of moving one item in a `move_items` batch.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct MoveItemResult {
+    pub source: String,
+    pub destination: Option<String>,
+    pub error: Option<String>,
+}
+
+impl MoveItemResult {
+    fn failed(source: String, error: &str) -> Self {
+        Self {
+            source,
+            destination: None,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+/// Decide where each item goes. Items that cannot move get a failed result;
+/// the rest are returned as `(index into results, old path, new path)`.
+fn plan_moves(
+    item_paths: Vec<String>,
+    dest_path: &Path,
+) -> Result<(Vec<MoveItemResult>, Vec<(usize, String, String)>), TessellumError> {
+    let normalized_dest = crate::utils::normalize_path(&dest_path.to_string_lossy());
+    let normalized_items: Vec<String> = item_paths
+        .iter()
+        .map(|p| crate::utils::normalize_path(p))
+        .collect();
     
-    let normalized_dest = crate::utils::normalize_path(&dest_dir);
+    // Moving a folder into itself (or into anything it contains) would detach
+    // the subtree, so the whole batch is refused.
+    if normalized_items.iter().any(|item| {
+        normalized_dest == *item || normalized_dest.starts_with(&(item.clone() + "/"))
+    }) {
+        return Err(TessellumError::Validation(
+            "Cannot move a folder into itself".to_string(),
+        ));
+    }
     
-    let mut planned: Vec<(String, String)> = Vec::new();
+    let mut results = Vec::with_capacity(item_paths.len());
+    let mut planned = Vec::new();
     let mut seen_targets: std::collections::HashSet<String> = std::collections::HashSet::new();
     
-    for item_path in item_paths {
-        validate_path_in_vault(&item_path, &vault_path)
-            .map_err(TessellumError::Validation)?;
-        
-        let normalized_item = crate::utils::normalize_path(&item_path);
-        if normalized_dest == normalized_item
-            || normalized_dest.starts_with(&(normalized_item.clone() + "/"))
+    for (item_path, normalized_item) in item_paths.into_iter().zip(normalized_items.iter()) {
+        if normalized_items
+            .iter()
+            .any(|other| normalized_item.starts_with(&(other.clone() + "/")))
         {
-            return Err(TessellumError::Validation(
-                "Cannot move a folder into itself".to_string(),
+            results.push(MoveItemResult::failed(
+                item_path,
+                "Its parent folder is being moved as well",
             ));
+            continue;
         }
         
-        let old_path = Path::new(&item_path);
-        let file_name = old_path.file_name().ok_or_else(|| {
-            TessellumError::Validation("Invalid path: no filename".to_string())
-        })?;
+        let Some(file_name) = Path::new(&item_path).file_name() else {
+            results.push(MoveItemResult::failed(item_path, "Invalid path: no filename"));
+            continue;
+        };
         let new_path = dest_path.join(file_name);
-        
         let new_path_str = new_path.to_string_lossy().to_string();
         let normalized_new = crate::utils::normalize_path(&new_path_str);
-        if normalized_new == normalized_item {
+        
+        if normalized_new == *normalized_item {
+            results.push(MoveItemResult {
+                source: item_path,
+                destination: Some(new_path_str),
+                error: None,
+            });
             continue;
         }
         
-        if !seen_targets.insert(normalized_new.clone()) {
-            return Err(TessellumError::Validation(
-                "Multiple items share the same name in the destination".to_string(),
+        if !seen_targets.insert(normalized_new) {
+            results.push(MoveItemResult::failed(
+                item_path,
+                "Another item with the same name is being moved to the destination",
             ));
+            continue;
         }
         
         if new_path.exists() {
-            return Err(TessellumError::Validation(
-                "A file or folder with that name already exists in the destination".to_string(),
+            results.push(MoveItemResult::failed(
+                item_path,
+                "A file or folder with that name already exists in the destination",
             ));
+            continue;
         }
         
-        planned.push((item_path, new_path_str));
+        planned.push((results.len(), item_path.clone(), new_path_str));
+        results.push(MoveItemResult {
+            source: item_path,
+            destination: None,
+            error: None,
+        });
     }
     
-    for (old_path, new_path) in planned.iter() {
-        tokio::fs::rename(old_path, new_path)
-            .await
-            .map_err(TessellumError::from)?;
+    Ok((results, planned))
+}
+
+#[tauri::command]
+pub async fn move_items(
+    state: tauri::State<'_, crate::models::AppState>,
+    grafeo_state: tauri::State<'_, ManagedGrafeoConnection>,
+    vault_path: String,
+    item_paths: Vec<String>,
+    dest_dir: String,
+) -> Result<Vec<MoveItemResult>, TessellumError> {
+    move_items_inner(&state, &grafeo_state, vault_path, item_paths, dest_dir).await
+}
+
+/// Move one file or folder into `dest_folder`, keeping links in the index pointing at it.
+///
+/// Relative markdown links and image paths inside moved notes are rewritten
+/// so they still reach the same files; wikilinks need no change.
+/// Fails instead of overwriting when the destination already has an item with that name.
+/// Returns the new path.
+#[tauri::command]
+pub async fn move_item(
+    state: tauri::State<'_, crate::models::AppState>,
+    grafeo_state: tauri::State<'_, ManagedGrafeoConnection>,
+    vault_path: String,
+    item_path: String,
+    dest_folder: String,
+) -> Result<String, TessellumError> {
+    move_item_inner(&state, &grafeo_state, vault_path, item_path, dest_folder).await
+}
+
+async fn move_item_inner(
+    state: &crate::models::AppState,
+    grafeo_state: &ManagedGrafeoConnection,
+    vault_path: String,
+    item_path: String,
+    dest_folder: String,
+) -> Result<String, TessellumError> {
+    let result = move_items_inner(state, grafeo_state, vault_path, vec![item_path], dest_folder)
+        .await?
+        .pop()
+        .ok_or_else(|| TessellumError::Internal("Move produced no result".to_string()))?;
+    match (result.destination, result.error) {
+        (Some(destination), None) => Ok(destination),
+        (_, error) => Err(TessellumError::Validation(
+            error.unwrap_or_else(|| "Move failed".to_string()),
+        )),
+    }
+}
+
+/// Rebased contents for the notes a batch of moves relocated, as (note, content).
+///
+/// `moves` holds every (old, new) item of the batch, notes or folders, so a
+/// link between two items moved together keeps pointing at its target. Only
+/// notes whose links changed are returned; unreadable notes are logged and
+/// skipped. Nothing is written here.
+fn rebase_moved_notes(moves: &[(PathBuf, PathBuf)]) -> Vec<(String, String)> {
+    let mut rebased = Vec::new();
+    for (old_path, new_path) in moves {
+        let notes = WalkDir::new(new_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .filter(|p| p.extension().and_then(|ext| ext.to_str()) == Some("md"));
+        for note in notes {
+            let old_note = match note.strip_prefix(new_path) {
+                Ok(rel) if !rel.as_os_str().is_empty() => old_path.join(rel),
+                _ => old_path.clone(),
+            };
+            let content = match std::fs::read_to_string(&note) {
+                Ok(content) => content,
+                Err(e) => {
+                    log::warn!("Skipping link rebase for {}: {}", note.display(), e);
+                    continue;
+                }
+            };
+            if let Some(rewritten) = rebase_relative_links(&content, &old_note, &note, moves) {
+                rebased.push((note.to_string_lossy().to_string(), rewritten));
+            }
+        }
+    }
+    rebased
+}
+
+async fn move_items_inner(
+    state: &crate::models::AppState,
+    grafeo_state: &ManagedGrafeoConnection,
+    vault_path: String,
+    item_paths: Vec<String>,
+    dest_dir: String,
+) -> Result<Vec<MoveItemResult>, TessellumError> {
+    if item_paths.is_empty() {
+        return Ok(Vec::new());
     }
     
-    let db = state.db.clone();
-    for (old_path, new_path) in planned.iter() {
-        db
-            .update_file_path(old_path, new_path)
-            .await
-            .map_err(TessellumError::from)?;
-        db
-            .update_search_file_path(old_path, new_path)
-            .await
-            .map_err(TessellumError::from)?;
+    validate_path_in_vault(&dest_dir, &vault_path)
+        .map_err(TessellumError::Validation)?;
+    for item_path in &item_paths {
+        validate_path_in_vault(item_path, &vault_path)
+            .map_err(TessellumError::Validation)?;
+    }
+    
+    let dest_path = Path::new(&dest_dir);
+    let dest_meta = tokio::fs::metadata(dest_path).await.map_err(TessellumError::from)?;
+    if !dest_meta.is_dir() {
+        return Err(TessellumError::Validation(
+            "Destination must be a folder".to_string(),
+        ));
+    }
+    
+    let (mut results, planned_moves) = plan_moves(item_paths, dest_path)?;
+    
+    let mut planned: Vec<(String, String)> = Vec::with_capacity(planned_moves.len());
+    for (idx, old_path, new_path) in planned_moves {
+        match tokio::fs::rename(&old_path, &new_path).await {
+            Ok(()) => {
+                results[idx].destination = Some(new_path.clone());
+                planned.push((old_path, new_path));
+            }
+            Err(e) => results[idx].error = Some(e.to_string()),
+        }
+    }
+    
+    // Relative markdown links in moved notes are resolved from the old folder;
+    // the whole batch is known first so links between moved items stay put.
+    let moves: Vec<(PathBuf, PathBuf)> = planned
+        .iter()
+        .map(|(old_path, new_path)| (PathBuf::from(old_path), PathBuf::from(new_path)))
+        .collect();
+    let rebased = tauri::async_runtime::spawn_blocking(move || rebase_moved_notes(&moves))
+        .await
+        .map_err(|e| TessellumError::Internal(format!("Link rebase task failed: {e}")))?;
+
+    state
+        .db
+        .update_file_paths(&planned)
+        .await
+        .map_err(TessellumError::from)?;
+
+    *state.file_index.lock().await = None;
+    *state.asset_index.lock().await = None;
+
+    // Rebased notes go through the regular save path, so the watcher ignores
+    // the writes and their links are re-indexed from the new location.
+    for (note, content) in rebased {
+        if let Err(e) =
+            crate::commands::notes::write_and_index_note(state, grafeo_state, vault_path.clone(), note.clone(), content)
+                .await
+        {
+            log::warn!("Failed to rewrite links in {}: {}", note, e);
+        }
     }
 
     // Note: Grafeo sync happens automatically via file watcher/write_file command
@@ -444,12 +938,7 @@ pub async fn move_items(
         }
     });
     
-    let mut idx_guard = state.file_index.lock().await;
-    *idx_guard = None;
-    let mut asset_guard = state.asset_index.lock().await;
-    *asset_guard = None;
-    
-    Ok(planned.into_iter().map(|(_, new_path)| new_path).collect())
+    Ok(results)
 }
 use serde::Serialize;
 use std::collections::HashMap;
@@ -474,14 +963,18 @@ pub struct VaultSnapshot {
 /// so the frontend refresh path only crosses the IPC boundary once.
 #[tauri::command]
 pub fn list_vault_snapshot(vault_path: String) -> Result<VaultSnapshot, TessellumError> {
-    let files = list_files(vault_path)?;
+    let files = collect_vault_files(&vault_path)?;
     let tree = build_tree(files.clone());
     Ok(VaultSnapshot { files, tree })
 }
 
+/// Returns the vault as a nested tree for the sidebar.
+///
+/// Folders come before files at every level, each group sorted by name
+/// ignoring case. Uses the same ignore rules as `list_files`.
 #[tauri::command]
 pub fn list_files_tree(vault_path: String) -> Result<Vec<TreeNode>, TessellumError> {
-    Ok(build_tree(list_files(vault_path)?))
+    Ok(build_tree(collect_vault_files(&vault_path)?))
 }
 
 fn build_tree(files: Vec<FileMetadata>) -> Vec<TreeNode> {
@@ -529,7 +1022,10 @@ fn build_tree(files: Vec<FileMetadata>) -> Vec<TreeNode> {
     fn sort_nodes(nodes: &mut [TreeNode]) {
         nodes.sort_by(|a, b| {
             if a.is_dir == b.is_dir {
-                a.name.cmp(&b.name)
+                a.name
+                    .to_lowercase()
+                    .cmp(&b.name.to_lowercase())
+                    .then_with(|| a.name.cmp(&b.name))
             } else if a.is_dir {
                 std::cmp::Ordering::Less
             } else {
@@ -582,6 +1078,15 @@ fn spawn_trash_retention_cleanup(vault_path: std::path::PathBuf) {
 #[cfg(test)]
 mod tests {
     use super::derive_renamed_filename;
+    use super::{backlink_rewrite_regex, plan_moves, resolves_to_path, rewrite_links_in_text};
+    use super::MoveItemResult;
+    use super::{
+        extract_first_h1, glob_matches, list_files, list_files_tree, move_item_inner,
+        rename_to_heading_inner,
+    };
+    use crate::grafeo_projection::ManagedGrafeoConnection;
+    use crate::test_support::{TestAppState, TestVault};
+    use super::{preview_backlink_rewrites, LinkLineChange};
     use super::spawn_trash_retention_cleanup;
     use std::fs;
     use std::path::Path;
@@ -620,6 +1125,351 @@ mod tests {
         assert!(deleted, "expected startup cleanup to remove expired trash file");
     }
 
+    #[tokio::test]
+    async fn preview_backlink_rewrites_reports_changed_lines_without_writing() {
+        let temp = tempdir().unwrap();
+        let source = temp.path().join("Source.md");
+        let original = "intro\nsee [[Old]] and [[Folder/Old|alias]]\n\\[[Old]] stays\nunrelated [[Other]]\n";
+        fs::write(&source, original).unwrap();
+        let source_path = source.to_string_lossy().to_string();
+
+        let previews = preview_backlink_rewrites(&[source_path.clone()], "Old", "New", &|_, _| true)
+            .await
+            .unwrap();
+
+        assert_eq!(previews.len(), 1);
+        assert_eq!(previews[0].source_path, source_path);
+        assert_eq!(
+            previews[0].changes,
+            vec![LinkLineChange {
+                line: 2,
+                old_text: "see [[Old]] and [[Folder/Old|alias]]".to_string(),
+                new_text: "see [[New]] and [[Folder/New|alias]]".to_string(),
+            }]
+        );
+        assert_eq!(fs::read_to_string(&source).unwrap(), original);
+    }
+
+    #[tokio::test]
+    async fn list_files_rejects_bogus_vault_paths() {
+        let temp = tempdir().unwrap();
+        let file = temp.path().join("note.md");
+        fs::write(&file, "").unwrap();
+
+        let missing = list_files(temp.path().join("missing").to_string_lossy().to_string())
+            .await
+            .unwrap_err();
+        assert!(missing.to_string().contains("Vault path does not exist"));
+        let not_dir = list_files(file.to_string_lossy().to_string()).await.unwrap_err();
+        assert!(not_dir.to_string().contains("Vault path is not a directory"));
+    }
+
+    #[tokio::test]
+    async fn list_files_keeps_names_that_only_contain_git_or_trash() {
+        let vault = TestVault::new()
+            .with_markdown("my.github-notes.md", "")
+            .with_markdown("project.trashcan/Plan.md", "")
+            .with_markdown(".trash/Deleted.md", "")
+            .with_markdown(".git/HEAD.md", "")
+            .build();
+
+        let files = list_files(vault.path().to_string_lossy().to_string()).await.unwrap();
+        let mut names: Vec<&str> = files.iter().map(|f| f.filename.as_str()).collect();
+        names.sort();
+
+        assert_eq!(names, vec!["Plan.md", "my.github-notes.md", "project.trashcan"]);
+    }
+
+    #[tokio::test]
+    async fn list_files_fills_created_time() {
+        let vault = TestVault::new().with_markdown("Note.md", "").build();
+
+        let files = list_files(vault.path().to_string_lossy().to_string()).await.unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert!(files[0].created > 0);
+        assert!(files[0].created <= files[0].last_modified + 1000);
+    }
+
+    #[test]
+    fn list_files_tree_nests_folders_first_by_name() {
+        let vault = TestVault::new()
+            .with_markdown("zeta.md", "")
+            .with_markdown("Alpha.md", "")
+            .with_markdown("notes/b.md", "")
+            .with_markdown("Archive/Old/a.md", "")
+            .with_markdown(".trash/Deleted.md", "")
+            .build();
+
+        let tree = list_files_tree(vault.path().to_string_lossy().to_string()).unwrap();
+
+        let names = |nodes: &[super::TreeNode]| nodes.iter().map(|n| n.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&tree), vec!["Archive", "notes", "Alpha.md", "zeta.md"]);
+        assert_eq!(names(&tree[0].children), vec!["Old"]);
+        assert_eq!(names(&tree[0].children[0].children), vec!["a.md"]);
+        assert!(tree[0].children[0].is_dir);
+        assert_eq!(names(&tree[1].children), vec!["b.md"]);
+    }
+
+    #[test]
+    fn extract_first_h1_skips_frontmatter_code_and_lower_headings() {
+        let content = "---\ntitle: x\n---\n## Sub\n```\n# Not this\n```\n# My Great Idea #\n# Later";
+        assert_eq!(extract_first_h1(content).as_deref(), Some("My Great Idea"));
+        assert_eq!(extract_first_h1("No heading\n#tag"), None);
+    }
+
+    #[tokio::test]
+    async fn rename_to_heading_renames_note_and_handles_collisions() {
+        let vault = TestVault::new()
+            .with_markdown("Untitled.md", "# My Great Idea\nBody")
+            .with_markdown("Draft.md", "# My Great Idea\nSecond")
+            .with_markdown("Empty.md", "no heading")
+            .build();
+        let db_dir = tempdir().unwrap();
+        let db = crate::db::Database::init(db_dir.path().join("vault.sqlite").to_str().unwrap())
+            .await
+            .unwrap();
+        let state = TestAppState::with_db(db);
+        let vault_path = vault.path().to_string_lossy().to_string();
+
+        let renamed = rename_to_heading_inner(
+            &state,
+            vault_path.clone(),
+            vault.path().join("Untitled.md").to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+        assert!(renamed.ends_with("My Great Idea.md"));
+        assert!(vault.path().join("My Great Idea.md").exists());
+        assert!(!vault.path().join("Untitled.md").exists());
+
+        let second = rename_to_heading_inner(
+            &state,
+            vault_path.clone(),
+            vault.path().join("Draft.md").to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+        assert!(second.ends_with("My Great Idea (1).md"));
+
+        let err = rename_to_heading_inner(
+            &state,
+            vault_path,
+            vault.path().join("Empty.md").to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("no H1"));
+        assert!(vault.path().join("Empty.md").exists());
+    }
+
+    #[test]
+    fn glob_matches_anchored_paths_and_bare_names() {
+        let nested = glob::Pattern::new("Projects/**/*.md").unwrap();
+        assert!(glob_matches(&nested, true, "Projects/2026/Plan.md"));
+        assert!(!glob_matches(&nested, true, "Archive/Projects/Plan.md"));
+
+        let loose = glob::Pattern::new("*meeting*").unwrap();
+        assert!(glob_matches(&loose, false, "Work/Weekly Meeting.md"));
+        assert!(glob_matches(&loose, false, "Meetings"));
+        assert!(!glob_matches(&loose, false, "Work/Notes.md"));
+    }
+
+    #[tokio::test]
+    async fn move_item_updates_links_and_refuses_to_clobber() {
+        let vault = TestVault::new()
+            .with_markdown("Inbox/Note.md", "# Note")
+            .with_markdown("Source.md", "[[Note]]")
+            .with_markdown("Archive/Keep.md", "# Keep")
+            .with_markdown("Archive/Other.md", "# Other")
+            .with_markdown("Other.md", "# Root other")
+            .build();
+        let db_dir = tempdir().unwrap();
+        let db = crate::db::Database::init(db_dir.path().join("vault.sqlite").to_str().unwrap())
+            .await
+            .unwrap();
+        let path = |rel: &str| crate::utils::normalize_path(&vault.path().join(rel).to_string_lossy());
+        db.index_file(&path("Inbox/Note.md"), 1, 1, None, None, &[]).await.unwrap();
+        db.index_file(&path("Source.md"), 1, 1, None, None, &[path("Inbox/Note.md")])
+            .await
+            .unwrap();
+        let state = TestAppState::with_db(db);
+        let grafeo = ManagedGrafeoConnection::new(());
+        let vault_path = vault.path().to_string_lossy().to_string();
+
+        let moved = move_item_inner(&state, &grafeo, vault_path.clone(), path("Inbox/Note.md"), path("Archive"))
+            .await
+            .unwrap();
+
+        assert_eq!(crate::utils::normalize_path(&moved), path("Archive/Note.md"));
+        assert!(vault.path().join("Archive/Note.md").is_file());
+        assert_eq!(
+            state.db.get_outgoing_links(&path("Source.md")).await.unwrap(),
+            vec![path("Archive/Note.md")]
+        );
+
+        let clash = move_item_inner(&state, &grafeo, vault_path, path("Other.md"), path("Archive")).await;
+        assert!(clash.is_err());
+        assert_eq!(fs::read_to_string(vault.path().join("Archive/Other.md")).unwrap(), "# Other");
+        assert!(vault.path().join("Other.md").is_file());
+    }
+
+    #[tokio::test]
+    async fn move_item_down_a_level_rebases_relative_markdown_links() {
+        let vault = TestVault::new()
+            .with_markdown(
+                "Note.md",
+                "[sib](./Sibling.md) ![](./img.png) [deep](Folder/Deep.md#Intro)\n\
+                 [web](https://example.com/a.md) [[Sibling]] [top](#top) `[code](Sibling.md)`\n",
+            )
+            .with_markdown("Sibling.md", "# Sibling")
+            .with_markdown("Folder/Deep.md", "# Deep")
+            .with_markdown("Archive/Keep.md", "# Keep")
+            .build();
+        fs::write(vault.path().join("img.png"), "png").unwrap();
+        let state = TestAppState::new().await;
+        let grafeo = ManagedGrafeoConnection::new(());
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let path = |rel: &str| vault.path().join(rel).to_string_lossy().to_string();
+
+        move_item_inner(&state, &grafeo, vault_path, path("Note.md"), path("Archive")).await.unwrap();
+
+        assert_eq!(
+            fs::read_to_string(vault.path().join("Archive/Note.md")).unwrap(),
+            "[sib](../Sibling.md) ![](../img.png) [deep](../Folder/Deep.md#Intro)\n\
+             [web](https://example.com/a.md) [[Sibling]] [top](#top) `[code](Sibling.md)`\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn move_item_up_a_level_rebases_relative_markdown_links() {
+        let vault = TestVault::new()
+            .with_markdown(
+                "Archive/Deep/Note.md",
+                "[sib](Sibling.md) ![pic](../img%20one.png) [self](./Note.md)\n",
+            )
+            .with_markdown("Archive/Deep/Sibling.md", "# Sibling")
+            .build();
+        fs::write(vault.path().join("Archive/img one.png"), "png").unwrap();
+        let state = TestAppState::new().await;
+        let grafeo = ManagedGrafeoConnection::new(());
+        let grafeo = ManagedGrafeoConnection::new(());
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let path = |rel: &str| vault.path().join(rel).to_string_lossy().to_string();
+
+        move_item_inner(&state, &grafeo, vault_path, path("Archive/Deep/Note.md"), path("Archive"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(vault.path().join("Archive/Note.md")).unwrap(),
+            "[sib](Deep/Sibling.md) ![pic](img%20one.png) [self](./Note.md)\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn moving_linked_notes_together_keeps_links_between_them_and_reindexes() {
+        let vault = TestVault::new()
+            .with_markdown("A.md", "[b](B.md) [c](C.md)\n")
+            .with_markdown("B.md", "# B")
+            .with_markdown("C.md", "# C")
+            .with_markdown("Archive/Keep.md", "# Keep")
+            .build();
+        let state = TestAppState::new().await;
+        let grafeo = ManagedGrafeoConnection::new(());
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let path = |rel: &str| crate::utils::normalize_path(&vault.path().join(rel).to_string_lossy());
+
+        let results = super::move_items_inner(
+            &state,
+            &grafeo,
+            vault_path,
+            vec![path("A.md"), path("B.md")],
+            path("Archive"),
+        )
+        .await
+        .unwrap();
+
+        assert!(results.iter().all(|result| result.error.is_none()));
+        assert_eq!(
+            fs::read_to_string(vault.path().join("Archive/A.md")).unwrap(),
+            "[b](B.md) [c](../C.md)\n"
+        );
+        let mut links = state.db.get_outgoing_links(&path("Archive/A.md")).await.unwrap();
+        links.sort();
+        assert_eq!(links, vec![path("Archive/B.md"), path("C.md")]);
+    }
+
+    #[test]
+    fn rewrite_links_keeps_heading_and_block_anchors() {
+        let re = backlink_rewrite_regex("Old").unwrap();
+
+        let rewritten =
+            rewrite_links_in_text(&re, "[[Old#Intro|see]] and [[Dir/Old#^b1]]", "New", &|_| true);
+
+        assert_eq!(rewritten, "[[New#Intro|see]] and [[Dir/New#^b1]]");
+    }
+
+    #[test]
+    fn rewrite_links_skips_same_named_note_in_other_folder() {
+        let vault = TestVault::new()
+            .with_markdown("Old.md", "# Root")
+            .with_markdown("Projects/Old.md", "# Project")
+            .build();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let renamed = vault.path().join("Projects/Old.md").to_string_lossy().to_string();
+        let file_index = crate::models::FileIndex::build(&vault_path).unwrap();
+        let re = backlink_rewrite_regex("Old").unwrap();
+
+        let links_to_renamed =
+            resolves_to_path(&file_index, &vault_path, &renamed, crate::models::LinkStrategy::ShortestPath);
+        let source = vault.path().join("Index.md");
+        let rewritten = rewrite_links_in_text(
+            &re,
+            "[[Old]] and [[Projects/Old#Plan|plan]]",
+            "New",
+            &|target| links_to_renamed(&source, target),
+        );
+
+        assert_eq!(rewritten, "[[Old]] and [[Projects/New#Plan|plan]]");
+    }
+
+    #[test]
+    fn plan_moves_reports_collisions_per_item_and_rejects_self_moves() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        let dest = root.join("Dest");
+        fs::create_dir_all(root.join("Folder")).unwrap();
+        fs::create_dir_all(&dest).unwrap();
+        fs::write(dest.join("Taken.md"), "").unwrap();
+        let path = |rel: &str| root.join(rel).to_string_lossy().to_string();
+
+        let (results, planned) = plan_moves(
+            vec![path("Taken.md"), path("Folder"), path("Folder/Inner.md")],
+            &dest,
+        )
+        .unwrap();
+
+        assert_eq!(planned.len(), 1);
+        assert_eq!(planned[0].0, 1);
+        assert_eq!(planned[0].2, dest.join("Folder").to_string_lossy());
+        assert_eq!(
+            results[0],
+            MoveItemResult {
+                source: path("Taken.md"),
+                destination: None,
+                error: Some(
+                    "A file or folder with that name already exists in the destination".to_string()
+                ),
+            }
+        );
+        assert!(results[1].error.is_none());
+        assert!(results[2].error.is_some());
+
+        assert!(plan_moves(vec![path("Folder")], &root.join("Folder")).is_err());
+        assert!(plan_moves(vec![path("Dest")], &dest.join("Sub")).is_err());
+    }
+
     #[test]
     fn derive_renamed_filename_preserves_existing_extension_when_missing() {
         let media_old = Path::new("C:/vault/assets/cover.png");
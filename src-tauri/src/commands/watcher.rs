@@ -1,69 +1,332 @@
-use notify::{Config, Error, Event, RecommendedWatcher, RecursiveMode, Watcher};
-use std::path::Path;
-use std::sync::{Arc, Mutex};
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Config, Error, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
+use walkdir::WalkDir;
 
+use crate::db::Database;
 use crate::error::TessellumError;
-use crate::models::AppState;
+use crate::indexer::{read_frontmatter_aliases, VaultIndexer};
+use crate::models::{AppState, ExpectedChanges, FileChangeEvent, FileChangeKind, VaultFileChanges};
+use crate::search::SearchIndex;
+use crate::utils::IgnoreRules;
 
-/// Debounce window: ignore events within this duration of the last emit.
-const DEBOUNCE_MS: u64 = 200;
+/// Default debounce window when the frontend does not pass one.
+const DEFAULT_DEBOUNCE_MS: u64 = 300;
 
-fn should_emit_change(last_emit: &mut Instant, now: Instant) -> bool {
-    if now.duration_since(*last_emit) < Duration::from_millis(DEBOUNCE_MS) {
-        return false;
+/// Block until the next burst of watcher events and return it.
+///
+/// The burst starts with the first event received and collects everything that
+/// arrives within `window` of it. Returns `None` once the watcher is gone.
+fn collect_burst(rx: &Receiver<Event>, window: Duration) -> Option<Vec<Event>> {
+    let first = rx.recv().ok()?;
+    let deadline = Instant::now() + window;
+    let mut events = vec![first];
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        match rx.recv_timeout(remaining) {
+            Ok(event) => events.push(event),
+            Err(mpsc::RecvTimeoutError::Timeout) => break,
+            // Flush what we have; the next call reports the disconnect.
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    Some(events)
+}
+
+/// Which side of a rename a single-path notify event reports, if any.
+fn rename_side(event: &Event) -> Option<RenameMode> {
+    match event.kind {
+        EventKind::Modify(ModifyKind::Name(mode)) if event.paths.len() == 1 => match mode {
+            RenameMode::From | RenameMode::To => Some(mode),
+            // FSEvents and some backends don't say which side; the path that
+            // still exists is the destination.
+            RenameMode::Any | RenameMode::Other => Some(if event.paths[0].exists() {
+                RenameMode::To
+            } else {
+                RenameMode::From
+            }),
+            RenameMode::Both => None,
+        },
+        _ => None,
+    }
+}
+
+/// Join rename halves into one `RenameMode::Both` event with `[from, to]` paths.
+///
+/// inotify and Windows report a rename as separate "from" and "to" events;
+/// macOS reports two bare name changes. A "from" pairs with the next "to" in
+/// the burst, and when both carry a notify tracker id the ids must match.
+/// Unpaired halves pass through unchanged.
+fn pair_renames(events: &[Event]) -> Vec<Event> {
+    let mut paired = Vec::with_capacity(events.len());
+    let mut pending_from: Option<&Event> = None;
+
+    for event in events {
+        match rename_side(event) {
+            Some(RenameMode::From) => {
+                if let Some(unpaired) = pending_from.replace(event) {
+                    paired.push(unpaired.clone());
+                }
+            }
+            Some(RenameMode::To) => match pending_from.take() {
+                Some(from)
+                    if from.tracker().is_none()
+                        || event.tracker().is_none()
+                        || from.tracker() == event.tracker() =>
+                {
+                    paired.push(
+                        Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+                            .add_path(from.paths[0].clone())
+                            .add_path(event.paths[0].clone()),
+                    );
+                }
+                unmatched => {
+                    paired.extend(unmatched.cloned());
+                    paired.push(event.clone());
+                }
+            },
+            _ => paired.push(event.clone()),
+        }
+    }
+    paired.extend(pending_from.cloned());
+    paired
+}
+
+/// Map a burst of notify events onto frontend change events, dropping
+/// access-only events and repeats of the same change. Rename halves are
+/// joined first (see `pair_renames`).
+fn summarize_burst(events: &[Event]) -> Vec<FileChangeEvent> {
+    let mut changes: Vec<FileChangeEvent> = Vec::new();
+    for change in pair_renames(events).iter().filter_map(FileChangeEvent::from_notify) {
+        if !changes.contains(&change) {
+            changes.push(change);
+        }
+    }
+    changes
+}
+
+/// Move a renamed note's index rows to its new path instead of deleting and
+/// re-adding it, so links pointing at it survive the rename.
+async fn apply_rename(
+    db: &Database,
+    search_index: &tokio::sync::Mutex<SearchIndex>,
+    from: &str,
+    to: &str,
+) -> Result<(), String> {
+    let from = crate::utils::normalize_path(from);
+    let to = crate::utils::normalize_path(to);
+    db.update_file_path(&from, &to)
+        .await
+        .map_err(|e| format!("Failed to move note rows: {}", e))?;
+    db.update_search_file_path(&from, &to)
+        .await
+        .map_err(|e| format!("Failed to move search file: {}", e))?;
+    search_index.lock().await.delete_path(&from)?;
+    Ok(())
+}
+
+/// Notes and attachments inside `dir`, skipping entries excluded by
+/// `.tessellumignore`.
+fn files_under(vault_path: &str, dir: &Path) -> Vec<PathBuf> {
+    let ignore = IgnoreRules::load(vault_path);
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|e| ignore.keeps(vault_path, e))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .collect()
+}
+
+/// Drop everything indexed below `dir` with one prefix delete per table.
+async fn remove_folder_from_index(
+    db: &Database,
+    search_index: &tokio::sync::Mutex<SearchIndex>,
+    dir: &str,
+) -> Result<(), String> {
+    let prefix = format!("{}/", crate::utils::normalize_path(dir).trim_end_matches('/'));
+    db.delete_files_by_prefix(&prefix)
+        .await
+        .map_err(|e| format!("Failed to delete notes: {}", e))?;
+    let removed = db
+        .delete_search_files_by_prefix(&prefix)
+        .await
+        .map_err(|e| format!("Failed to delete search files: {}", e))?;
+    if !removed.is_empty() {
+        search_index.lock().await.index_batch(&[], &removed)?;
+    }
+    Ok(())
+}
+
+/// Incrementally re-index everything touched by a burst of changes.
+///
+/// A two-sided rename moves the index rows with `Database::update_file_path`
+/// first, which also carries everything below a renamed folder; the new paths
+/// are then re-indexed like any other change. Notify cannot always tell
+/// whether a path that is gone was a folder, so everything indexed below it is
+/// dropped too. The cached file and asset indexes are patched in place rather
+/// than rebuilt.
+async fn reindex_changed_paths(state: &AppState, vault_path: &str, changes: &[FileChangeEvent]) {
+    let db = state.db.as_ref();
+    let search_index = state.search_index.as_ref();
+    let ignore = IgnoreRules::load(vault_path);
+    let is_tracked = |path: &Path| {
+        let rel_path = path.strip_prefix(vault_path).unwrap_or(path);
+        !ignore.is_ignored(rel_path, false)
+    };
+
+    let mut gone: Vec<&str> = Vec::new();
+    let mut present: Vec<PathBuf> = Vec::new();
+    for change in changes {
+        if let (FileChangeKind::Rename, [from, to]) = (change.kind, change.paths.as_slice()) {
+            if let Err(e) = apply_rename(db, search_index, from, to).await {
+                log::warn!("Failed to move index entry {} -> {}: {}", from, to, e);
+            }
+            gone.push(from);
+            let to_path = Path::new(to);
+            if to_path.is_dir() {
+                // Search documents carry their path, so the moved notes are
+                // dropped under their old paths and re-indexed below.
+                let children = files_under(vault_path, to_path);
+                let stale: Vec<String> = children
+                    .iter()
+                    .filter_map(|child| child.strip_prefix(to_path).ok())
+                    .map(|rel| crate::utils::normalize_path(&Path::new(from).join(rel).to_string_lossy()))
+                    .collect();
+                if let Err(e) = search_index.lock().await.index_batch(&[], &stale) {
+                    log::warn!("Failed to drop search documents under {}: {}", from, e);
+                }
+                present.extend(children);
+            } else if is_tracked(to_path) {
+                present.push(to_path.to_path_buf());
+            }
+            continue;
+        }
+        for path in &change.paths {
+            let path_ref = Path::new(path);
+            if path_ref.is_dir() {
+                present.extend(files_under(vault_path, path_ref));
+            } else if path_ref.exists() {
+                if is_tracked(path_ref) {
+                    present.push(path_ref.to_path_buf());
+                }
+            } else if is_tracked(path_ref) {
+                gone.push(path);
+            }
+        }
+    }
+    gone.sort_unstable();
+    gone.dedup();
+    present.sort_unstable();
+    present.dedup();
+
+    if let Some(index) = state.file_index.lock().await.as_mut() {
+        for path in &gone {
+            index.remove(path);
+            index.remove_under(path);
+        }
+        for path in present.iter().filter(|path| path.extension().is_some_and(|ext| ext == "md")) {
+            index.insert(path);
+            index.set_aliases(path, &read_frontmatter_aliases(&path.to_string_lossy()));
+        }
+    }
+    if let Some(index) = state.asset_index.lock().await.as_mut() {
+        for path in &gone {
+            index.remove(path);
+            index.remove_under(path);
+        }
+        for path in &present {
+            index.insert(path);
+        }
+    }
+
+    for path in &gone {
+        if let Err(e) = remove_folder_from_index(db, search_index, path).await {
+            log::warn!("Failed to de-index {} after external change: {}", path, e);
+        }
+    }
+
+    let indexes = match state.current_file_index(vault_path).await {
+        Ok(file_index) => state.current_asset_index(vault_path).await.map(|assets| (file_index, assets)),
+        Err(e) => Err(e),
+    };
+    let (file_index, asset_index) = match indexes {
+        Ok(indexes) => indexes,
+        Err(e) => {
+            log::warn!("Skipping watcher re-index: {}", e);
+            return;
+        }
+    };
+    let touched = gone.iter().map(Path::new).chain(present.iter().map(PathBuf::as_path));
+    for path in touched {
+        let indexed =
+            VaultIndexer::index_path(db, search_index, vault_path, path, &file_index, &asset_index).await;
+        if let Err(e) = indexed {
+            log::warn!("Failed to re-index {} after external change: {}", path.display(), e);
+        }
     }
+}
 
-    *last_emit = now;
-    true
+/// True when every path in the event was just written by the app itself.
+fn is_self_triggered(expected: &Mutex<ExpectedChanges>, event: &Event, now: Instant) -> bool {
+    if event.paths.is_empty() {
+        return false;
+    }
+    let mut expected = expected.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    event
+        .paths
+        .iter()
+        .all(|path| expected.is_expected(&path.to_string_lossy(), now))
 }
 
 /// Watches a directory and emits a debounced event to the frontend whenever
 /// a file within the directory changes.
 ///
 /// This function initializes a file system watcher for the specified directory (`vault_path`) and listens for changes
-/// such as file creation, modification, or deletion. Upon detecting a change, the function emits a `file-changed`
-/// event to the frontend, debounced to prevent event flooding.
+/// such as file creation, modification, or deletion. Events are coalesced on a background thread: a burst of changes
+/// arriving within `debounce_ms` (default 300ms) of its first event produces a single `file-changed` event whose
+/// `VaultFileChanges` payload names the vault and lists the distinct `FileChangeEvent`s in the burst. Changed notes
+/// are re-indexed before the event is emitted, so edits made in external editors keep links, tags and search results
+/// current.
+///
+/// Several vaults can be watched at once. Calling this again for a vault that is already watched replaces its
+/// watcher; other vaults keep theirs.
 #[tauri::command]
 pub async fn watch_vault(
     vault_path: String,
+    debounce_ms: Option<u64>,
     handle: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<(), TessellumError> {
-    // Initialize or replace the watcher so vault switching and dev reloads
-    // do not keep stale watchers alive.
-    let mut watcher_guard = state.watcher.lock().await;
-    *watcher_guard = None;
-
-    let app_handle_clone = handle.clone();
-    let file_index_clone = state.file_index.clone();
-    let asset_index_clone = state.asset_index.clone();
+    // Replace this vault's watcher so dev reloads do not keep stale watchers alive.
+    let key = crate::utils::normalize_path(&vault_path);
+    let mut watchers = state.watchers.lock().await;
+    // Dropping the old watcher stops its events and ends its burst thread.
+    watchers.remove(&key);
+
+    let window = Duration::from_millis(debounce_ms.unwrap_or(DEFAULT_DEBOUNCE_MS));
+    let expected_changes = state.expected_changes.clone();
     let notify_config = Config::default();
-    let last_emit = Arc::new(Mutex::new(
-        Instant::now() - Duration::from_millis(DEBOUNCE_MS),
-    ));
+    let (tx, rx) = mpsc::channel::<Event>();
 
     let mut watcher = RecommendedWatcher::new(
         move |res: Result<Event, Error>| {
             match res {
-                Ok(_) => {
-                    // Debounce: only emit if enough time has passed
-                    let mut last = last_emit.lock().unwrap();
-                    let now = Instant::now();
-                    if should_emit_change(&mut last, now) {
-
-                        // Invalidate caches
-                        let fi = file_index_clone.clone();
-                        let ai = asset_index_clone.clone();
-                        tauri::async_runtime::spawn(async move {
-                            let mut guard = fi.lock().await;
-                            *guard = None;
-                            let mut asset_guard = ai.lock().await;
-                            *asset_guard = None;
-                        });
-                        let _ = app_handle_clone.emit("file-changed", ());
+                Ok(event) => {
+                    // Our own saves already updated the index and the editor buffer.
+                    if is_self_triggered(&expected_changes, &event, Instant::now()) {
+                        return;
                     }
+                    let _ = tx.send(event);
                 }
                 Err(e) => log::error!("watch error: {:?}", e),
             }
@@ -76,43 +339,243 @@ pub async fn watch_vault(
         .watch(Path::new(&vault_path), RecursiveMode::Recursive)
         .map_err(|e| TessellumError::Internal(e.to_string()))?;
 
-    *watcher_guard = Some(watcher);
+    // The sender lives in the watcher callback, so this thread exits once the
+    // watcher is dropped (unwatch or vault switch).
+    std::thread::spawn(move || {
+        while let Some(burst) = collect_burst(&rx, window) {
+            let changes = summarize_burst(&burst);
+            if changes.is_empty() {
+                continue;
+            }
+
+            // External edits bypass write_file, so bring the index up to date
+            // before telling the frontend to reload.
+            let handle = handle.clone();
+            let vault_path = vault_path.clone();
+            tauri::async_runtime::spawn(async move {
+                reindex_changed_paths(&handle.state::<AppState>(), &vault_path, &changes).await;
+                let _ = handle.emit("file-changed", VaultFileChanges { vault_path, changes });
+            });
+        }
+    });
+
+    watchers.insert(key, watcher);
 
     Ok(())
 }
 
+/// Drop the watcher for `vault_path`, or every watcher when it is `None`.
+async fn stop_watching(state: &AppState, vault_path: Option<&str>) {
+    let mut watchers = state.watchers.lock().await;
+    match vault_path {
+        Some(path) => {
+            watchers.remove(&crate::utils::normalize_path(path));
+        }
+        None => watchers.clear(),
+    }
+}
+
+/// Stop watching one vault, or all of them when no path is given.
+/// Safe to call for a vault that isn't watched.
 #[tauri::command]
-pub async fn unwatch_vault(state: State<'_, AppState>) -> Result<(), TessellumError> {
-    let mut watcher_guard = state.watcher.lock().await;
-    *watcher_guard = None;
+pub async fn unwatch_vault(
+    state: State<'_, AppState>,
+    vault_path: Option<String>,
+) -> Result<(), TessellumError> {
+    stop_watching(&state, vault_path.as_deref()).await;
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+    use std::sync::Mutex;
     use std::time::{Duration, Instant};
 
-    use super::should_emit_change;
+    use notify::event::{AccessKind, CreateKind, ModifyKind, RenameMode};
+    use notify::{Event, EventKind};
+
+    use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
+    use tempfile::tempdir;
+
+    use super::{
+        collect_burst, is_self_triggered, pair_renames, reindex_changed_paths, stop_watching, summarize_burst,
+    };
+    use crate::models::{ExpectedChanges, FileChangeEvent, FileChangeKind};
+    use crate::test_support::{TestAppState, TestVault};
 
     #[test]
-    fn emits_when_the_debounce_window_has_elapsed() {
-        let base = Instant::now();
-        let mut last_emit = base - Duration::from_millis(250);
+    fn coalesces_a_burst_of_events_into_one_batch() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        for name in ["a.md", "b.md", "a.md"] {
+            tx.send(Event::new(EventKind::Any).add_path(PathBuf::from(name))).unwrap();
+        }
 
-        let emitted = should_emit_change(&mut last_emit, base);
+        let burst = collect_burst(&rx, Duration::from_millis(50)).unwrap();
 
-        assert!(emitted);
-        assert_eq!(last_emit, base);
+        assert_eq!(burst.len(), 3);
+        assert!(rx.try_recv().is_err());
     }
 
     #[test]
-    fn suppresses_events_inside_the_debounce_window() {
+    fn stops_once_the_watcher_is_dropped() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        tx.send(Event::new(EventKind::Any)).unwrap();
+        drop(tx);
+
+        assert_eq!(collect_burst(&rx, Duration::from_millis(50)).map(|b| b.len()), Some(1));
+        assert!(collect_burst(&rx, Duration::from_millis(50)).is_none());
+    }
+
+    #[test]
+    fn suppresses_watcher_events_for_recent_self_writes() {
         let base = Instant::now();
-        let mut last_emit = base - Duration::from_millis(50);
+        let expected = Mutex::new(ExpectedChanges::default());
+        expected.lock().unwrap().expect("/vault/Note.md", base);
+
+        let self_write = Event::new(EventKind::Any).add_path(PathBuf::from("/vault/Note.md"));
+        let other_write = Event::new(EventKind::Any)
+            .add_path(PathBuf::from("/vault/Note.md"))
+            .add_path(PathBuf::from("/vault/Other.md"));
+
+        assert!(is_self_triggered(&expected, &self_write, base + Duration::from_millis(100)));
+        assert!(!is_self_triggered(&expected, &other_write, base + Duration::from_millis(100)));
+        assert!(!is_self_triggered(&expected, &self_write, base + Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn summarizes_bursts_into_typed_changes() {
+        let note = PathBuf::from("/vault/Note.md");
+        let burst = vec![
+            Event::new(EventKind::Create(CreateKind::Folder)).add_path(PathBuf::from("/vault/New")),
+            Event::new(EventKind::Access(AccessKind::Any)).add_path(note.clone()),
+            Event::new(EventKind::Modify(ModifyKind::Any)).add_path(note.clone()),
+            Event::new(EventKind::Modify(ModifyKind::Any)).add_path(note.clone()),
+            Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+                .add_path(note)
+                .add_path(PathBuf::from("/vault/Renamed.md")),
+        ];
+
+        let changes = summarize_burst(&burst);
+
+        assert_eq!(
+            changes,
+            vec![
+                FileChangeEvent {
+                    kind: FileChangeKind::Create,
+                    paths: vec!["/vault/New".to_string()],
+                    is_dir: true,
+                },
+                FileChangeEvent {
+                    kind: FileChangeKind::Modify,
+                    paths: vec!["/vault/Note.md".to_string()],
+                    is_dir: false,
+                },
+                FileChangeEvent {
+                    kind: FileChangeKind::Rename,
+                    paths: vec!["/vault/Note.md".to_string(), "/vault/Renamed.md".to_string()],
+                    is_dir: false,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn unwatch_drops_one_vault_or_all_of_them() {
+        let vaults = [tempdir().unwrap(), tempdir().unwrap()];
+        let state = TestAppState::new().await;
+        let paths: Vec<String> = vaults
+            .iter()
+            .map(|vault| crate::utils::normalize_path(&vault.path().to_string_lossy()))
+            .collect();
+        for (vault, path) in vaults.iter().zip(&paths) {
+            let mut watcher =
+                RecommendedWatcher::new(|_: notify::Result<Event>| {}, Config::default()).unwrap();
+            watcher.watch(vault.path(), RecursiveMode::Recursive).unwrap();
+            state.watchers.lock().await.insert(path.clone(), watcher);
+        }
+
+        stop_watching(&state, Some(&paths[0])).await;
+        assert_eq!(
+            state.watchers.lock().await.keys().collect::<Vec<_>>(),
+            vec![&paths[1]]
+        );
+
+        stop_watching(&state, None).await;
+        assert!(state.watchers.lock().await.is_empty());
+
+        // Unwatching a vault that isn't watched is a no-op.
+        stop_watching(&state, Some(&paths[0])).await;
+        assert!(state.watchers.lock().await.is_empty());
+    }
+
+    #[test]
+    fn pairs_split_rename_events_by_tracker() {
+        let rename = |mode| EventKind::Modify(ModifyKind::Name(mode));
+        let burst = vec![
+            Event::new(rename(RenameMode::From))
+                .add_path(PathBuf::from("/vault/Old.md"))
+                .set_tracker(7),
+            Event::new(rename(RenameMode::From))
+                .add_path(PathBuf::from("/vault/Gone.md"))
+                .set_tracker(8),
+            Event::new(rename(RenameMode::To))
+                .add_path(PathBuf::from("/vault/New.md"))
+                .set_tracker(8),
+            Event::new(rename(RenameMode::To)).add_path(PathBuf::from("/vault/Moved.md")),
+        ];
+
+        let events = pair_renames(&burst);
+
+        let summary: Vec<(EventKind, Vec<PathBuf>)> =
+            events.into_iter().map(|event| (event.kind, event.paths)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (rename(RenameMode::From), vec![PathBuf::from("/vault/Old.md")]),
+                (
+                    rename(RenameMode::Both),
+                    vec![PathBuf::from("/vault/Gone.md"), PathBuf::from("/vault/New.md")]
+                ),
+                (rename(RenameMode::To), vec![PathBuf::from("/vault/Moved.md")]),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn folder_removals_and_renames_update_the_index_in_place() {
+        let vault = TestVault::new()
+            .with_markdown("Projects/Alpha.md", "# Alpha")
+            .with_markdown("Projects Archive/Gamma.md", "# Gamma")
+            .with_markdown("Notes/Beta.md", "# Beta")
+            .build();
+        let vault_path = vault.path().to_string_lossy().to_string();
+        let path = |name: &str| crate::utils::normalize_path(&vault.path().join(name).to_string_lossy());
+        let state = TestAppState::new().await;
+        for name in ["Projects/Alpha.md", "Projects Archive/Gamma.md", "Notes/Beta.md"] {
+            state.db.index_file(&path(name), 1, 1, None, None, &[]).await.unwrap();
+        }
+        state.current_file_index(&vault_path).await.unwrap();
+
+        std::fs::remove_dir_all(vault.path().join("Projects")).unwrap();
+        std::fs::rename(vault.path().join("Notes"), vault.path().join("Journal")).unwrap();
+        let changes = [
+            FileChangeEvent { kind: FileChangeKind::Remove, paths: vec![path("Projects")], is_dir: true },
+            FileChangeEvent {
+                kind: FileChangeKind::Rename,
+                paths: vec![path("Notes"), path("Journal")],
+                is_dir: true,
+            },
+        ];
+        reindex_changed_paths(&state, &vault_path, &changes).await;
 
-        let emitted = should_emit_change(&mut last_emit, base);
+        assert!(!state.db.is_note_indexed(&path("Projects/Alpha.md")).await.unwrap());
+        assert!(state.db.is_note_indexed(&path("Projects Archive/Gamma.md")).await.unwrap());
+        assert!(!state.db.is_note_indexed(&path("Notes/Beta.md")).await.unwrap());
+        assert!(state.db.is_note_indexed(&path("Journal/Beta.md")).await.unwrap());
 
-        assert!(!emitted);
-        assert!(base.duration_since(last_emit) < Duration::from_millis(200));
+        let file_index = state.file_index.lock().await.clone().unwrap();
+        assert_eq!(file_index.resolve(&vault_path, "Alpha"), None);
+        assert_eq!(file_index.resolve(&vault_path, "Beta"), Some(vault.path().join("Journal/Beta.md")));
     }
 }
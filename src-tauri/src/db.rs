@@ -1,3 +1,4 @@
+use std::str::FromStr;
 use std::time::Duration;
 use sqlx::{Pool, Row};
 use sqlx::QueryBuilder;
@@ -12,6 +13,10 @@ pub struct Database {
 /// Initializes a new database connection pool and creates the necessary tables if they do not exist.
 impl Database {
     pub async fn init(db_path: &str) -> Result<Self, sqlx::Error> {
+        if db_path == ":memory:" {
+            return Self::init_memory().await;
+        }
+        
         let options = SqliteConnectOptions::new()
             .filename(db_path)
             .create_if_missing(true)
@@ -21,13 +26,37 @@ impl Database {
             // per transaction, which speeds up index writes noticeably.
             .synchronous(SqliteSynchronous::Normal)
             // Give SQLite write contention enough time to resolve.
-            .busy_timeout(Duration::from_secs(15));
+            .busy_timeout(Duration::from_secs(15))
+            // Per-connection setting: every pooled connection must cascade
+            // note deletions to links, tags and aliases.
+            .foreign_keys(true);
         
         let pool = SqlitePoolOptions::new()
             .max_connections(5)
             .connect_with(options)
             .await?;
         
+        Self::create_schema(pool).await
+    }
+    
+    /// Open an ephemeral database that lives only as long as this `Database`.
+    ///
+    /// An in-memory SQLite database disappears with its last connection, so the
+    /// pool holds exactly one connection and never lets it expire.
+    pub async fn init_memory() -> Result<Self, sqlx::Error> {
+        let options = SqliteConnectOptions::from_str("sqlite::memory:")?.foreign_keys(true);
+        let pool = SqlitePoolOptions::new()
+            .min_connections(1)
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await?;
+        
+        Self::create_schema(pool).await
+    }
+    
+    async fn create_schema(pool: Pool<Sqlite>) -> Result<Self, sqlx::Error> {
         // Create notes table
         sqlx::query(
             "CREATE TABLE IF NOT EXISTS notes (
@@ -49,6 +78,16 @@ impl Database {
             .execute(&pool)
             .await;
         
+        // Display title from frontmatter `title:` or the first H1; NULL falls back to the stem.
+        let _ = sqlx::query("ALTER TABLE notes ADD COLUMN title TEXT;")
+            .execute(&pool)
+            .await;
+        
+        // Date-range queries filter on modification time
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_notes_modified_at ON notes(modified_at);")
+            .execute(&pool)
+            .await?;
+        
         // Create tags table for normalized tags
         sqlx::query(
             "CREATE TABLE IF NOT EXISTS note_tags (
@@ -62,6 +101,33 @@ impl Database {
             .execute(&pool)
             .await?;
         
+        // Frontmatter `aliases:` that wikilinks may use in place of the filename
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS note_aliases (
+                path TEXT NOT NULL,
+                alias TEXT NOT NULL,
+                PRIMARY KEY (path, alias),
+                FOREIGN KEY(path) REFERENCES notes(path) ON DELETE CASCADE ON
+                 UPDATE CASCADE
+            );",
+        )
+            .execute(&pool)
+            .await?;
+        
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_note_aliases_alias ON note_aliases(alias);")
+            .execute(&pool)
+            .await?;
+        
+        // Notes the user opened, most recent open per path (Unix milliseconds)
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS recent_files (
+                path TEXT PRIMARY KEY,
+                opened_at INTEGER NOT NULL
+            );",
+        )
+            .execute(&pool)
+            .await?;
+        
         // Track all files indexed for search (markdown + non-markdown)
         sqlx::query(
             "CREATE TABLE IF NOT EXISTS search_files (
@@ -86,6 +152,8 @@ impl Database {
             "CREATE TABLE IF NOT EXISTS links (
                 source_path TEXT,
                 target_path TEXT,
+                link_field TEXT,
+                is_embed INTEGER NOT NULL DEFAULT 0,
                 PRIMARY KEY (source_path, target_path),
                 FOREIGN KEY(source_path) REFERENCES notes(path) ON DELETE CASCADE
             );",
@@ -93,13 +161,63 @@ impl Database {
             .execute(&pool)
             .await?;
         
+        // Frontmatter field a link was declared in (NULL for body links)
+        let _ = sqlx::query("ALTER TABLE links ADD COLUMN link_field TEXT;")
+            .execute(&pool)
+            .await;
+        
+        // Attachment embeds (`![[diagram.png]]`) target files outside `notes`
+        let _ = sqlx::query("ALTER TABLE links ADD COLUMN is_embed INTEGER NOT NULL DEFAULT 0;")
+            .execute(&pool)
+            .await;
+        
         // Create index for faster backlink queries
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_links_target ON links(target_path);")
             .execute(&pool)
             .await?;
         
-        // Enable foreign key enforcement (SQLite has it OFF by default)
-        sqlx::query("PRAGMA foreign_keys = ON;")
+        // Full-text index over note bodies, kept in step with `notes` by triggers
+        sqlx::query(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS notes_fts USING fts5(
+                path UNINDEXED,
+                body,
+                tokenize = 'unicode61'
+            );",
+        )
+            .execute(&pool)
+            .await?;
+        
+        sqlx::query(
+            "CREATE TRIGGER IF NOT EXISTS notes_fts_after_delete AFTER DELETE ON notes BEGIN
+                DELETE FROM notes_fts WHERE path = old.path;
+            END;",
+        )
+            .execute(&pool)
+            .await?;
+        
+        sqlx::query(
+            "CREATE TRIGGER IF NOT EXISTS notes_fts_after_rename AFTER UPDATE OF path ON notes BEGIN
+                UPDATE notes_fts SET path = new.path WHERE path = old.path;
+            END;",
+        )
+            .execute(&pool)
+            .await?;
+        
+        // Recently opened notes follow their note through renames and moves,
+        // and leave the list when the note is deleted or trashed
+        sqlx::query(
+            "CREATE TRIGGER IF NOT EXISTS recent_files_after_delete AFTER DELETE ON notes BEGIN
+                DELETE FROM recent_files WHERE path = old.path;
+            END;",
+        )
+            .execute(&pool)
+            .await?;
+        
+        sqlx::query(
+            "CREATE TRIGGER IF NOT EXISTS recent_files_after_rename AFTER UPDATE OF path ON notes BEGIN
+                UPDATE OR REPLACE recent_files SET path = new.path WHERE path = old.path;
+            END;",
+        )
             .execute(&pool)
             .await?;
         
@@ -114,6 +232,8 @@ impl Database {
     /// * `modified` - Unix timestamp of last modification
     /// * `size` - File size in bytes
     /// * `resolved_links` - Vector of FULL PATHS to target files (already resolved from wikilinks)
+    ///
+    /// Links are diffed against the stored set so unchanged rows are never rewritten.
     pub async fn index_file(
         &self,
         path: &str,
@@ -122,6 +242,36 @@ impl Database {
         frontmatter_json: Option<&str>,
         inline_tags_json: Option<&str>,
         resolved_links: &[String],
+    ) -> Result<(), sqlx::Error> {
+        self.index_file_with_link_fields(
+            path,
+            modified,
+            size,
+            frontmatter_json,
+            inline_tags_json,
+            resolved_links,
+            &[],
+            &[],
+        )
+            .await
+    }
+    
+    /// Like `index_file`, tagging links declared in frontmatter with their field.
+    ///
+    /// `link_fields` holds (target_path, field) pairs; targets not listed there
+    /// are stored as plain body links. `embeds` holds attachment paths embedded
+    /// with `![[...]]`, stored as links flagged `is_embed`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn index_file_with_link_fields(
+        &self,
+        path: &str,
+        modified: i64,
+        size: u64,
+        frontmatter_json: Option<&str>,
+        inline_tags_json: Option<&str>,
+        resolved_links: &[String],
+        link_fields: &[(String, String)],
+        embeds: &[String],
     ) -> Result<(), sqlx::Error> {
         // Insert or update the note metadata
         sqlx::query(
@@ -140,34 +290,144 @@ impl Database {
             .execute(&self.pool)
             .await?;
         
-        // Update links in a transaction
+        // Update links in a transaction, touching only rows that actually changed
         let mut tx = self.pool.begin().await?;
         
-        // Delete old links from this source
-        sqlx::query("DELETE FROM links WHERE source_path = ?")
-            .bind(path)
-            .execute(&mut *tx)
-            .await?;
+        let existing: std::collections::HashMap<String, (Option<String>, bool)> =
+            sqlx::query_as::<_, (String, Option<String>, bool)>(
+                "SELECT target_path, link_field, is_embed FROM links WHERE source_path = ?",
+            )
+                .bind(path)
+                .fetch_all(&mut *tx)
+                .await?
+                .into_iter()
+                .map(|(target, field, is_embed)| (target, (field, is_embed)))
+                .collect();
         
         // Deduplicate links - a note can have multiple wikilinks to the same target,
         // but we only store one link relationship per source-target pair
-        let mut unique_links: Vec<&String> = resolved_links.iter().collect();
-        unique_links.sort();
-        unique_links.dedup();
+        let current = link_rows(resolved_links, link_fields, embeds);
         
-        // Insert new resolved links (deduplicated)
-        for target_path in unique_links {
-            sqlx::query("INSERT INTO links (source_path, target_path) VALUES (?, ?)")
+        // Delete links that disappeared from this source
+        for target_path in existing
+            .keys()
+            .filter(|target| !current.iter().any(|(t, _, _)| *t == target.as_str()))
+        {
+            sqlx::query("DELETE FROM links WHERE source_path = ? AND target_path = ?")
                 .bind(path)
                 .bind(target_path)
                 .execute(&mut *tx)
                 .await?;
         }
         
+        // Insert newly added links and retag kept ones whose field or kind changed
+        for (target_path, link_field, is_embed) in current {
+            match existing.get(target_path) {
+                None => {
+                    sqlx::query(
+                        "INSERT INTO links (source_path, target_path, link_field, is_embed) VALUES (?, ?, ?, ?)",
+                    )
+                        .bind(path)
+                        .bind(target_path)
+                        .bind(link_field)
+                        .bind(is_embed)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                Some((stored_field, stored_embed))
+                    if stored_field.as_deref() != link_field || *stored_embed != is_embed =>
+                {
+                    sqlx::query(
+                        "UPDATE links SET link_field = ?, is_embed = ? WHERE source_path = ? AND target_path = ?",
+                    )
+                        .bind(link_field)
+                        .bind(is_embed)
+                        .bind(path)
+                        .bind(target_path)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                Some(_) => {}
+            }
+        }
+        
+        tx.commit().await?;
+        Ok(())
+    }
+    
+    /// Store the searchable body text of notes, replacing any previous text.
+    pub async fn replace_note_bodies(&self, bodies: &[(&str, &str)]) -> Result<(), sqlx::Error> {
+        if bodies.is_empty() {
+            return Ok(());
+        }
+        
+        let mut tx = self.pool.begin().await?;
+        for (path, body) in bodies {
+            sqlx::query("DELETE FROM notes_fts WHERE path = ?")
+                .bind(path)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("INSERT INTO notes_fts (path, body) VALUES (?, ?)")
+                .bind(path)
+                .bind(body)
+                .execute(&mut *tx)
+                .await?;
+        }
         tx.commit().await?;
         Ok(())
     }
     
+    /// Full-text search over note bodies.
+    ///
+    /// Every whitespace-separated term must appear (AND), matched
+    /// case-insensitively. Returns (path, excerpt, match_count) tuples, best
+    /// match first; matches in the excerpt are wrapped in `<mark>` tags.
+    /// `match_count` counts whole-token hits as the FTS tokenizer sees them,
+    /// so `cat` does not count inside `concatenate`.
+    pub async fn search_note_contents(
+        &self,
+        query: &str,
+        limit: i64,
+    ) -> Result<Vec<(String, String, i64)>, sqlx::Error> {
+        let terms: Vec<String> = query
+            .split_whitespace()
+            .map(|term| term.to_lowercase())
+            .collect();
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        
+        // Quote each term so user input is never parsed as FTS5 query syntax.
+        let fts_query = terms
+            .iter()
+            .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" ");
+        
+        // `highlight` marks every hit in the body using the index's own
+        // tokenization; the markers are only counted, never shown.
+        let rows = sqlx::query_as::<_, (String, String, String)>(
+            "SELECT path, snippet(notes_fts, 1, '<mark>', '</mark>', '…', 16),
+                    highlight(notes_fts, 1, char(1), char(2))
+             FROM notes_fts
+             WHERE notes_fts MATCH ?
+             ORDER BY rank
+             LIMIT ?",
+        )
+            .bind(&fts_query)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+        
+        Ok(rows
+            .into_iter()
+            .map(|(path, excerpt, marked)| {
+                let count = marked.matches('\u{1}').count();
+                (path, excerpt, count as i64)
+            })
+            .collect())
+    }
+    
     /// Replace tags for a file (normalized tags).
     pub async fn set_note_tags(&self, path: &str, tags: &[String]) -> Result<(), sqlx::Error> {
         let mut tx = self.pool.begin().await?;
@@ -189,6 +449,87 @@ impl Database {
         Ok(())
     }
     
+    /// Replace the frontmatter aliases recorded for a note.
+    pub async fn set_note_aliases(&self, path: &str, aliases: &[String]) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        
+        sqlx::query("DELETE FROM note_aliases WHERE path = ?")
+            .bind(path)
+            .execute(&mut *tx)
+            .await?;
+        
+        for alias in aliases {
+            sqlx::query("INSERT OR IGNORE INTO note_aliases (path, alias) VALUES (?, ?)")
+                .bind(path)
+                .bind(alias)
+                .execute(&mut *tx)
+                .await?;
+        }
+        
+        tx.commit().await?;
+        Ok(())
+    }
+    
+    /// Get the paths of notes declaring `alias`, compared case-insensitively.
+    pub async fn get_paths_by_alias(&self, alias: &str) -> Result<Vec<String>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, (String,)>(
+            "SELECT DISTINCT path FROM note_aliases WHERE alias = ? COLLATE NOCASE ORDER BY path",
+        )
+            .bind(alias)
+            .fetch_all(&self.pool)
+            .await?;
+        
+        Ok(rows.into_iter().map(|(path,)| path).collect())
+    }
+    
+    /// Every recorded alias as (path, alias) pairs, for seeding the in-memory
+    /// link index without reading the notes again.
+    pub async fn get_all_note_aliases(&self) -> Result<Vec<(String, String)>, sqlx::Error> {
+        sqlx::query_as::<_, (String, String)>("SELECT path, alias FROM note_aliases ORDER BY path")
+            .fetch_all(&self.pool)
+            .await
+    }
+    
+    /// Most recent opens kept in `recent_files`; older entries are dropped.
+    const MAX_RECENT_FILES: i64 = 100;
+    
+    /// Record that `path` was opened at `opened_at` (Unix milliseconds).
+    ///
+    /// Reopening a note moves it to the front instead of adding a row.
+    pub async fn record_open(&self, path: &str, opened_at: i64) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        
+        sqlx::query(
+            "INSERT INTO recent_files (path, opened_at) VALUES (?, ?)
+             ON CONFLICT(path) DO UPDATE SET opened_at = excluded.opened_at",
+        )
+            .bind(path)
+            .bind(opened_at)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            "DELETE FROM recent_files WHERE path NOT IN (
+                 SELECT path FROM recent_files ORDER BY opened_at DESC, path ASC LIMIT ?
+             )",
+        )
+            .bind(Self::MAX_RECENT_FILES)
+            .execute(&mut *tx)
+            .await?;
+        
+        tx.commit().await?;
+        Ok(())
+    }
+    
+    /// Get the most recently opened notes as (path, opened_at), newest first.
+    pub async fn get_recent_files(&self, limit: i64) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        sqlx::query_as::<_, (String, i64)>(
+            "SELECT path, opened_at FROM recent_files ORDER BY opened_at DESC, path ASC LIMIT ?",
+        )
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+    }
+    
     /// Get all outgoing links from a specific file.
     ///
     /// Returns a vector of full paths to files that this file links to.
@@ -208,6 +549,28 @@ impl Database {
             .collect())
     }
     
+    /// Get the outgoing link targets of a file with whether each is an indexed note.
+    ///
+    /// Attachment embeds are left out, as in `get_broken_links`.
+    /// Returns a vector of (target_path, exists) tuples.
+    pub async fn get_outgoing_link_status(&self, source_path: &str) -> Result<Vec<(String, bool)>, sqlx::Error> {
+        let denormalized = source_path.replace('/', "\\");
+        let rows = sqlx::query_as::<_, (String, bool)>(
+            "SELECT DISTINCT target_path, target_path IN (SELECT path FROM notes) FROM links
+             WHERE (source_path = ? OR source_path = ?) AND is_embed = 0
+             ORDER BY target_path",
+        )
+            .bind(source_path)
+            .bind(&denormalized)
+            .fetch_all(&self.pool)
+            .await?;
+        
+        Ok(rows
+            .into_iter()
+            .map(|(path, exists)| (crate::utils::normalize_path(&path), exists))
+            .collect())
+    }
+    
     /// Get all backlinks to a specific file.
     ///
     /// Returns a vector of full paths to files that link to this file.
@@ -227,6 +590,66 @@ impl Database {
             .collect())
     }
     
+    /// Rank other notes by how many link neighbors they share with `path`.
+    ///
+    /// A shared neighbor is either a common outgoing target (bibliographic
+    /// coupling) or a common backlink source (co-citation). When
+    /// `exclude_linked` is set, notes that `path` links to directly are skipped.
+    /// Returns (path, shared_count) tuples, highest count first.
+    pub async fn get_related(
+        &self,
+        path: &str,
+        limit: i64,
+        exclude_linked: bool,
+    ) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        sqlx::query_as::<_, (String, i64)>(
+            "SELECT n.path, COUNT(*) AS shared
+             FROM (
+                 SELECT other.source_path AS candidate
+                 FROM links mine
+                 JOIN links other ON other.target_path = mine.target_path
+                 WHERE mine.source_path = ?
+                 UNION ALL
+                 SELECT other.target_path AS candidate
+                 FROM links mine
+                 JOIN links other ON other.source_path = mine.source_path
+                 WHERE mine.target_path = ?
+             ) neighbors
+             JOIN notes n ON n.path = neighbors.candidate
+             WHERE n.path != ?
+               AND (? = 0 OR n.path NOT IN (SELECT target_path FROM links WHERE source_path = ?))
+             GROUP BY n.path
+             ORDER BY shared DESC, n.path ASC
+             LIMIT ?",
+        )
+            .bind(path)
+            .bind(path)
+            .bind(path)
+            .bind(exclude_linked)
+            .bind(path)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+    }
+    
+    /// Notes with the most backlinks, for spotting hub notes.
+    ///
+    /// Attachment embeds and links to missing notes are not counted.
+    /// Returns (path, backlink_count) tuples, highest count first.
+    pub async fn get_most_linked(&self, limit: i64) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        sqlx::query_as::<_, (String, i64)>(
+            "SELECT target_path, COUNT(*) AS backlinks
+             FROM links
+             WHERE is_embed = 0 AND target_path IN (SELECT path FROM notes)
+             GROUP BY target_path
+             ORDER BY backlinks DESC, target_path ASC
+             LIMIT ?",
+        )
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+    }
+    
     /// Get all links in the vault (for graph visualization).
     ///
     /// Returns a vector of (source_path, target_path) tuples.
@@ -239,6 +662,23 @@ impl Database {
         Ok(rows)
     }
     
+    /// Get links a note declares in frontmatter fields.
+    ///
+    /// Returns (target_path, field) tuples sorted by field, then target.
+    pub async fn get_frontmatter_links(
+        &self,
+        source_path: &str,
+    ) -> Result<Vec<(String, String)>, sqlx::Error> {
+        sqlx::query_as::<_, (String, String)>(
+            "SELECT target_path, link_field FROM links
+             WHERE source_path = ? AND link_field IS NOT NULL
+             ORDER BY link_field, target_path",
+        )
+            .bind(source_path)
+            .fetch_all(&self.pool)
+            .await
+    }
+    
     /// Update links when a file is renamed/moved.
     ///
     /// This updates both:
@@ -257,12 +697,45 @@ impl Database {
             .execute(&mut *tx)
             .await?;
         
+        Self::apply_file_path_update(&mut tx, old_path, new_path).await?;
+        
+        tx.commit().await?;
+        Ok(())
+    }
+    
+    /// Move many files/folders in one transaction, updating notes, links and
+    /// search_files together so a batch move is never half-applied in the index.
+    pub async fn update_file_paths(&self, moves: &[(String, String)]) -> Result<(), sqlx::Error> {
+        if moves.is_empty() {
+            return Ok(());
+        }
+        
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("PRAGMA defer_foreign_keys = ON")
+            .execute(&mut *tx)
+            .await?;
+        
+        for (old_path, new_path) in moves {
+            Self::apply_file_path_update(&mut tx, old_path, new_path).await?;
+            Self::apply_search_file_path_update(&mut tx, old_path, new_path).await?;
+        }
+        
+        tx.commit().await?;
+        Ok(())
+    }
+    
+    /// Rewrite note and link paths for a single rename inside an open transaction.
+    async fn apply_file_path_update(
+        conn: &mut sqlx::SqliteConnection,
+        old_path: &str,
+        new_path: &str,
+    ) -> Result<(), sqlx::Error> {
         // 1. Update the record for the file/folder itself
         // Use OR REPLACE for notes PK in case of orphaned DB entries
         sqlx::query("UPDATE OR REPLACE notes SET path = ? WHERE path = ?")
             .bind(new_path)
             .bind(old_path)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
         
         // 2. If this is a folder rename, update all child notes
@@ -275,7 +748,7 @@ impl Database {
             .bind(new_path)
             .bind(old_path)
             .bind(&old_prefix)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
         
         // 3. Update links where this file/folder is the source
@@ -283,7 +756,7 @@ impl Database {
         sqlx::query("UPDATE OR IGNORE links SET source_path = ? WHERE source_path = ?")
             .bind(new_path)
             .bind(old_path)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
         
         // Handles children if folder
@@ -294,14 +767,14 @@ impl Database {
             .bind(new_path)
             .bind(old_path)
             .bind(&old_prefix)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
         
         // Cleanup merged source links (ones that didn't update because of conflicts)
         sqlx::query("DELETE FROM links WHERE source_path = ? OR source_path LIKE ?")
             .bind(old_path)
             .bind(&old_prefix)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
         
         // 4. Update links where this file/folder is the target (backlinks)
@@ -309,7 +782,7 @@ impl Database {
         sqlx::query("UPDATE OR IGNORE links SET target_path = ? WHERE target_path = ?")
             .bind(new_path)
             .bind(old_path)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
         
         // Handles children if folder
@@ -320,17 +793,16 @@ impl Database {
             .bind(new_path)
             .bind(old_path)
             .bind(&old_prefix)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
         
         // Cleanup merged target links
         sqlx::query("DELETE FROM links WHERE target_path = ? OR target_path LIKE ?")
             .bind(old_path)
             .bind(&old_prefix)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
         
-        tx.commit().await?;
         Ok(())
     }
     
@@ -364,7 +836,8 @@ impl Database {
             "SELECT path FROM notes
              WHERE path NOT IN (SELECT DISTINCT source_path FROM links)
              AND path NOT IN (SELECT DISTINCT target_path FROM links)
-             AND replace(path, '/', '\\') NOT IN (SELECT DISTINCT target_path FROM links)",
+             AND replace(path, '/', '\\') NOT IN (SELECT DISTINCT target_path FROM links)
+             ORDER BY path",
         )
             .fetch_all(&self.pool)
             .await?;
@@ -374,11 +847,13 @@ impl Database {
     
     /// Get broken links (links pointing to non-existent files).
     ///
+    /// Attachment embeds point outside `notes` by design and are never broken.
     /// Returns a vector of (source_path, broken_target_path) tuples.
     pub async fn get_broken_links(&self) -> Result<Vec<(String, String)>, sqlx::Error> {
         let rows = sqlx::query_as::<_, (String, String)>(
             "SELECT source_path, target_path FROM links
-             WHERE target_path NOT IN (SELECT path FROM notes)",
+             WHERE target_path NOT IN (SELECT path FROM notes) AND is_embed = 0
+             ORDER BY source_path, target_path",
         )
             .fetch_all(&self.pool)
             .await?;
@@ -397,6 +872,105 @@ impl Database {
         Ok(rows)
     }
     
+    /// All indexed notes as (path, modified_at, title).
+    ///
+    /// `title` is `None` for rows written without one, e.g. freshly created
+    /// empty notes; callers fall back to the file stem.
+    pub async fn get_all_notes_with_titles(
+        &self,
+    ) -> Result<Vec<(String, i64, Option<String>)>, sqlx::Error> {
+        sqlx::query_as::<_, (String, i64, Option<String>)>(
+            "SELECT path, modified_at, title FROM notes",
+        )
+            .fetch_all(&self.pool)
+            .await
+    }
+    
+    /// Notes whose title starts with `prefix`, as (path, title) pairs. The
+    /// match ignores ASCII case; `%`, `_` and `\` in the prefix are literal.
+    pub async fn get_notes_with_title_prefix(
+        &self,
+        prefix: &str,
+    ) -> Result<Vec<(String, String)>, sqlx::Error> {
+        let escaped = prefix
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        sqlx::query_as::<_, (String, String)>(
+            "SELECT path, title FROM notes WHERE title LIKE ? ESCAPE '\\' ORDER BY path",
+        )
+            .bind(format!("{}%", escaped))
+            .fetch_all(&self.pool)
+            .await
+    }
+    
+    /// Last open time (Unix milliseconds, 0 if never opened) and backlink
+    /// count for each indexed note in `paths`, as (path, opened_at, backlinks).
+    /// Embeds are not counted, matching [`Self::get_most_linked`].
+    pub async fn get_note_ranks(&self, paths: &[String]) -> Result<Vec<(String, i64, i64)>, sqlx::Error> {
+        let mut ranks = Vec::with_capacity(paths.len());
+        for chunk in paths.chunks(Self::ROW_BATCH_SIZE) {
+            let mut query = QueryBuilder::<Sqlite>::new(
+                "SELECT n.path,
+                        COALESCE((SELECT opened_at FROM recent_files r WHERE r.path = n.path), 0),
+                        (SELECT COUNT(*) FROM links l WHERE l.target_path = n.path AND l.is_embed = 0)
+                 FROM notes n
+                 WHERE n.path IN (",
+            );
+            let mut separated = query.separated(", ");
+            for path in chunk {
+                separated.push_bind(path);
+            }
+            separated.push_unseparated(")");
+            ranks.extend(query.build_query_as::<(String, i64, i64)>().fetch_all(&self.pool).await?);
+        }
+        Ok(ranks)
+    }
+    
+    /// Store the display title of an already indexed note.
+    pub async fn set_note_title(&self, path: &str, title: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE notes SET title = ? WHERE path = ?")
+            .bind(title)
+            .bind(path)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+    
+    /// Whether a note is recorded in the index.
+    pub async fn is_note_indexed(&self, path: &str) -> Result<bool, sqlx::Error> {
+        let row = sqlx::query_as::<_, (i64,)>("SELECT 1 FROM notes WHERE path = ?")
+            .bind(path)
+            .fetch_optional(&self.pool)
+            .await?;
+        
+        Ok(row.is_some())
+    }
+    
+    /// Get notes whose `modified_at` falls within `[start_ts, end_ts]`.
+    ///
+    /// Timestamps are Unix seconds. Returns (path, modified_at) tuples, newest
+    /// first. An inverted range yields no rows.
+    pub async fn get_notes_modified_between(
+        &self,
+        start_ts: i64,
+        end_ts: i64,
+    ) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        if start_ts > end_ts {
+            return Ok(Vec::new());
+        }
+
+        sqlx::query_as::<_, (String, i64)>(
+            "SELECT path, modified_at FROM notes
+             WHERE modified_at BETWEEN ? AND ?
+             ORDER BY modified_at DESC, path ASC",
+        )
+            .bind(start_ts)
+            .bind(end_ts)
+            .fetch_all(&self.pool)
+            .await
+    }
+
     /// Get all indexed search files (markdown and non-markdown).
     /// Returns `(path, modified_at, is_markdown, file_size)` for every indexed
     /// search file in a single query, so sync passes never need per-file lookups.
@@ -418,6 +992,62 @@ impl Database {
         Ok(count)
     }
 
+    /// Count notes whose body text is in the full-text index.
+    pub async fn count_note_bodies(&self) -> Result<i64, sqlx::Error> {
+        let (count,) = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM notes_fts")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count)
+    }
+
+    /// Size of the database in bytes, as SQLite's page count times page size.
+    async fn database_size(conn: &mut sqlx::SqliteConnection) -> Result<u64, sqlx::Error> {
+        let (size,) = sqlx::query_as::<_, (i64,)>(
+            "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+        )
+            .fetch_one(&mut *conn)
+            .await?;
+        Ok(size.max(0) as u64)
+    }
+
+    /// Rebuild the database file to reclaim space left by deleted rows, then
+    /// refresh the query planner statistics with `PRAGMA optimize`.
+    ///
+    /// Everything runs on one pooled connection holding no open transaction,
+    /// so other pool users just wait on SQLite's busy timeout instead of
+    /// deadlocking. The WAL is truncated afterwards so the freed space goes
+    /// back to the filesystem. Returns (size_before, size_after) in bytes.
+    pub async fn vacuum(&self) -> Result<(u64, u64), sqlx::Error> {
+        let mut conn = self.pool.acquire().await?;
+        let before = Self::database_size(&mut conn).await?;
+        
+        sqlx::query("VACUUM").execute(&mut *conn).await?;
+        sqlx::query("PRAGMA optimize").execute(&mut *conn).await?;
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)").execute(&mut *conn).await?;
+        
+        let after = Self::database_size(&mut conn).await?;
+        Ok((before, after))
+    }
+
+    /// Drop every table derived from the vault and create them again, empty.
+    ///
+    /// Notes, links, tags, aliases, note bodies and the tracked search files
+    /// all go, so the next full sync indexes the vault from scratch. Recently
+    /// opened notes are kept since they can't be rebuilt from disk.
+    pub async fn reset_index(&self) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        // Children first so dropping `notes` never trips a foreign key.
+        for table in ["links", "note_tags", "note_aliases", "notes_fts", "notes", "search_files"] {
+            sqlx::query(&format!("DROP TABLE IF EXISTS {table}"))
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        
+        Self::create_schema(self.pool.clone()).await?;
+        Ok(())
+    }
+
     /// Execute a pre-built dataview query and return rows.
     pub async fn run_dataview_query(
         &self,
@@ -485,6 +1115,9 @@ impl Database {
     }
 
     /// Replace many markdown file projections in one transaction.
+    ///
+    /// Existing links and tags of the given notes are dropped, then every table
+    /// is written with multi-row statements of up to [`Self::ROW_BATCH_SIZE`] rows.
     pub async fn replace_markdown_batch(
         &self,
         entries: &[IndexedMarkdownFile],
@@ -495,75 +1128,23 @@ impl Database {
 
         let mut tx = self.pool.begin().await?;
 
-        for entry in entries {
-            let inline_tags_json = if entry.inline_tags.is_empty() {
-                None
-            } else {
-                Some(
-                    serde_json::to_string(&entry.inline_tags)
-                        .map_err(|error| sqlx::Error::Protocol(error.to_string()))?,
-                )
-            };
-
-            sqlx::query(
-                "INSERT INTO notes (path, modified_at, size, frontmatter, inline_tags) VALUES (?, ?, ?, ?, ?)
-                 ON CONFLICT(path) DO UPDATE SET modified_at = ?, size = ?, frontmatter = ?, inline_tags = ?",
-            )
-            .bind(&entry.path)
-            .bind(entry.modified)
-            .bind(entry.size as i64)
-            .bind(entry.frontmatter_json.as_deref())
-            .bind(inline_tags_json.as_deref())
-            .bind(entry.modified)
-            .bind(entry.size as i64)
-            .bind(entry.frontmatter_json.as_deref())
-            .bind(inline_tags_json.as_deref())
-            .execute(&mut *tx)
-            .await?;
-
-            sqlx::query("DELETE FROM links WHERE source_path = ?")
-                .bind(&entry.path)
-                .execute(&mut *tx)
-                .await?;
-
-            let mut unique_links = entry.resolved_links.iter().collect::<Vec<_>>();
-            unique_links.sort();
-            unique_links.dedup();
-
-            for target_path in unique_links {
-                sqlx::query("INSERT INTO links (source_path, target_path) VALUES (?, ?)")
-                    .bind(&entry.path)
-                    .bind(target_path)
-                    .execute(&mut *tx)
-                    .await?;
-            }
-
-            sqlx::query("DELETE FROM note_tags WHERE path = ?")
-                .bind(&entry.path)
-                .execute(&mut *tx)
-                .await?;
-
-            for tag in &entry.inline_tags {
-                sqlx::query("INSERT OR IGNORE INTO note_tags (path, tag) VALUES (?, ?)")
-                    .bind(&entry.path)
-                    .bind(tag)
-                    .execute(&mut *tx)
-                    .await?;
+        for chunk in entries.chunks(Self::ROW_BATCH_SIZE) {
+            for (table, column) in
+                [("links", "source_path"), ("note_tags", "path"), ("note_aliases", "path")]
+            {
+                let mut delete_query =
+                    QueryBuilder::<Sqlite>::new(format!("DELETE FROM {} WHERE {} IN (", table, column));
+                let mut separated = delete_query.separated(", ");
+                for entry in chunk {
+                    separated.push_bind(&entry.path);
+                }
+                separated.push_unseparated(")");
+                delete_query.build().execute(&mut *tx).await?;
             }
-
-            sqlx::query(
-                "INSERT INTO search_files (path, modified_at, file_size, is_markdown) VALUES (?, ?, ?, 1)
-                 ON CONFLICT(path) DO UPDATE SET modified_at = ?, file_size = ?, is_markdown = 1",
-            )
-            .bind(&entry.path)
-            .bind(entry.modified)
-            .bind(entry.size as i64)
-            .bind(entry.modified)
-            .bind(entry.size as i64)
-            .execute(&mut *tx)
-            .await?;
         }
 
+        Self::write_markdown_rows(&mut tx, entries, true).await?;
+
         tx.commit().await?;
         Ok(())
     }
@@ -577,10 +1158,26 @@ impl Database {
             return Ok(());
         }
 
-        const ROW_BATCH_SIZE: usize = 500;
         let mut tx = self.pool.begin().await?;
+        Self::write_markdown_rows(&mut tx, entries, false).await?;
+        tx.commit().await?;
+        Ok(())
+    }
 
-        for chunk in entries.chunks(ROW_BATCH_SIZE) {
+    /// Rows per multi-row INSERT; keeps bound parameters well under SQLite's limit.
+    const ROW_BATCH_SIZE: usize = 500;
+
+    /// Write note, search file, tag, alias and link rows with multi-row INSERTs.
+    ///
+    /// With `upsert`, note and search file rows that already exist are updated
+    /// in place. Links, tags and aliases are always plain inserts, so callers replacing
+    /// notes must delete the old rows first.
+    async fn write_markdown_rows(
+        tx: &mut sqlx::Transaction<'_, Sqlite>,
+        entries: &[IndexedMarkdownFile],
+        upsert: bool,
+    ) -> Result<(), sqlx::Error> {
+        for chunk in entries.chunks(Self::ROW_BATCH_SIZE) {
             let inline_tags_json = chunk
                 .iter()
                 .map(|entry| {
@@ -595,7 +1192,7 @@ impl Database {
                 .collect::<Result<Vec<_>, _>>()?;
 
             let mut notes_query = QueryBuilder::<Sqlite>::new(
-                "INSERT INTO notes (path, modified_at, size, frontmatter, inline_tags) ",
+                "INSERT INTO notes (path, modified_at, size, frontmatter, inline_tags, title) ",
             );
             notes_query.push_values(
                 chunk.iter().zip(inline_tags_json.iter()),
@@ -605,10 +1202,18 @@ impl Database {
                         .push_bind(entry.modified)
                         .push_bind(entry.size as i64)
                         .push_bind(entry.frontmatter_json.as_deref())
-                        .push_bind(inline_tags.as_deref());
+                        .push_bind(inline_tags.as_deref())
+                        .push_bind(&entry.title);
                 },
             );
-            notes_query.build().execute(&mut *tx).await?;
+            if upsert {
+                notes_query.push(
+                    " ON CONFLICT(path) DO UPDATE SET modified_at = excluded.modified_at,
+                     size = excluded.size, frontmatter = excluded.frontmatter,
+                     inline_tags = excluded.inline_tags, title = excluded.title",
+                );
+            }
+            notes_query.build().execute(&mut **tx).await?;
 
             let mut search_query = QueryBuilder::<Sqlite>::new(
                 "INSERT INTO search_files (path, modified_at, file_size, is_markdown) ",
@@ -620,7 +1225,13 @@ impl Database {
                     .push_bind(entry.size as i64)
                     .push_bind(1);
             });
-            search_query.build().execute(&mut *tx).await?;
+            if upsert {
+                search_query.push(
+                    " ON CONFLICT(path) DO UPDATE SET modified_at = excluded.modified_at,
+                     file_size = excluded.file_size, is_markdown = 1",
+                );
+            }
+            search_query.build().execute(&mut **tx).await?;
         }
 
         let tag_rows = entries
@@ -633,37 +1244,60 @@ impl Database {
                     .collect::<Vec<_>>()
             })
             .collect::<Vec<_>>();
-        for chunk in tag_rows.chunks(ROW_BATCH_SIZE) {
+        for chunk in tag_rows.chunks(Self::ROW_BATCH_SIZE) {
             let mut tags_query =
                 QueryBuilder::<Sqlite>::new("INSERT OR IGNORE INTO note_tags (path, tag) ");
             tags_query.push_values(chunk.iter(), |mut builder, (path, tag)| {
                 builder.push_bind(path).push_bind(tag);
             });
-            tags_query.build().execute(&mut *tx).await?;
+            tags_query.build().execute(&mut **tx).await?;
         }
 
-        let link_rows = entries
+        let alias_rows = entries
             .iter()
             .flat_map(|entry| {
-                let mut unique_links = entry.resolved_links.iter().collect::<Vec<_>>();
-                unique_links.sort();
-                unique_links.dedup();
-                unique_links
-                    .into_iter()
-                    .map(|target| (entry.path.as_str(), target.as_str()))
+                entry
+                    .aliases
+                    .iter()
+                    .map(|alias| (entry.path.as_str(), alias.as_str()))
                     .collect::<Vec<_>>()
             })
             .collect::<Vec<_>>();
-        for chunk in link_rows.chunks(ROW_BATCH_SIZE) {
-            let mut links_query =
-                QueryBuilder::<Sqlite>::new("INSERT INTO links (source_path, target_path) ");
-            links_query.push_values(chunk.iter(), |mut builder, (source, target)| {
-                builder.push_bind(source).push_bind(target);
+        for chunk in alias_rows.chunks(Self::ROW_BATCH_SIZE) {
+            let mut aliases_query =
+                QueryBuilder::<Sqlite>::new("INSERT OR IGNORE INTO note_aliases (path, alias) ");
+            aliases_query.push_values(chunk.iter(), |mut builder, (path, alias)| {
+                builder.push_bind(path).push_bind(alias);
             });
-            links_query.build().execute(&mut *tx).await?;
+            aliases_query.build().execute(&mut **tx).await?;
+        }
+
+        let all_link_rows = entries
+            .iter()
+            .flat_map(|entry| {
+                link_rows(&entry.resolved_links, &entry.link_fields, &entry.embeds)
+                    .into_iter()
+                    .map(|(target, field, is_embed)| (entry.path.as_str(), target, field, is_embed))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for chunk in all_link_rows.chunks(Self::ROW_BATCH_SIZE) {
+            let mut links_query = QueryBuilder::<Sqlite>::new(
+                "INSERT INTO links (source_path, target_path, link_field, is_embed) ",
+            );
+            links_query.push_values(
+                chunk.iter(),
+                |mut builder, (source, target, field, is_embed)| {
+                    builder
+                        .push_bind(source)
+                        .push_bind(target)
+                        .push_bind(field)
+                        .push_bind(is_embed);
+                },
+            );
+            links_query.build().execute(&mut **tx).await?;
         }
 
-        tx.commit().await?;
         Ok(())
     }
 
@@ -717,6 +1351,24 @@ impl Database {
         Ok(deleted)
     }
     
+    /// Delete the search files whose path starts with `prefix` and return the
+    /// paths removed, so their search documents can be dropped too.
+    pub async fn delete_search_files_by_prefix(&self, prefix: &str) -> Result<Vec<String>, sqlx::Error> {
+        let pattern = format!("{}%", prefix);
+        let mut tx = self.pool.begin().await?;
+        let rows = sqlx::query_as::<_, (String,)>("SELECT path FROM search_files WHERE path LIKE ?")
+            .bind(&pattern)
+            .fetch_all(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM search_files WHERE path LIKE ?")
+            .bind(&pattern)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        
+        Ok(rows.into_iter().map(|(path,)| path).collect())
+    }
+    
     /// Update search file paths when a file or folder is renamed/moved.
     pub async fn update_search_file_path(
         &self,
@@ -724,11 +1376,21 @@ impl Database {
         new_path: &str,
     ) -> Result<(), sqlx::Error> {
         let mut tx = self.pool.begin().await?;
-        
+        Self::apply_search_file_path_update(&mut tx, old_path, new_path).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+    
+    /// Rewrite search_files paths for a single rename inside an open transaction.
+    async fn apply_search_file_path_update(
+        conn: &mut sqlx::SqliteConnection,
+        old_path: &str,
+        new_path: &str,
+    ) -> Result<(), sqlx::Error> {
         sqlx::query("UPDATE OR REPLACE search_files SET path = ? WHERE path = ?")
             .bind(new_path)
             .bind(old_path)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
         
         let old_prefix = format!("{}/%", old_path.replace('\\', "/"));
@@ -739,16 +1401,15 @@ impl Database {
             .bind(new_path)
             .bind(old_path)
             .bind(&old_prefix)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
         
         sqlx::query("DELETE FROM search_files WHERE path = ? OR path LIKE ?")
             .bind(old_path)
             .bind(&old_prefix)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
         
-        tx.commit().await?;
         Ok(())
     }
     
@@ -1047,10 +1708,26 @@ impl Database {
         Ok(sorted_keys)
     }
     
+    /// Get every `(path, tag)` pair from the normalized note_tags table.
+    pub async fn get_note_tag_pairs(&self) -> Result<Vec<(String, String)>, sqlx::Error> {
+        sqlx::query_as::<_, (String, String)>("SELECT path, tag FROM note_tags ORDER BY tag, path")
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Count notes per tag, most used first (ties by tag name).
+    pub async fn get_tag_counts(&self) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        sqlx::query_as::<_, (String, i64)>(
+            "SELECT tag, COUNT(DISTINCT path) AS uses FROM note_tags GROUP BY tag ORDER BY uses DESC, tag ASC",
+        )
+            .fetch_all(&self.pool)
+            .await
+    }
+    
     /// Get all note paths that have a given tag (checked in note_tags table).
     pub async fn get_notes_with_tag(&self, tag: &str) -> Result<Vec<String>, sqlx::Error> {
         let rows = sqlx::query_as::<_, (String,)>(
-            "SELECT path FROM note_tags WHERE tag = ?",
+            "SELECT path FROM note_tags WHERE tag = ? ORDER BY path",
         )
         .bind(tag)
         .fetch_all(&self.pool)
@@ -1100,17 +1777,173 @@ impl Database {
     }
 }
 
+/// Unique (target_path, link_field, is_embed) rows for a note, sorted by target.
+/// A target declared in frontmatter keeps the first field it appeared under,
+/// and is only an embed when every reference to it is one.
+fn link_rows<'a>(
+    resolved_links: &'a [String],
+    link_fields: &'a [(String, String)],
+    embeds: &'a [String],
+) -> Vec<(&'a str, Option<&'a str>, bool)> {
+    let mut rows: Vec<(&str, Option<&str>, bool)> = resolved_links
+        .iter()
+        .map(|target| (target.as_str(), None, false))
+        .chain(
+            link_fields
+                .iter()
+                .map(|(target, field)| (target.as_str(), Some(field.as_str()), false)),
+        )
+        .chain(embeds.iter().map(|target| (target.as_str(), None, true)))
+        .collect();
+    rows.sort_by(|a, b| a.0.cmp(b.0));
+    let mut unique: Vec<(&str, Option<&str>, bool)> = Vec::with_capacity(rows.len());
+    for (target, field, is_embed) in rows {
+        match unique.last_mut() {
+            Some(last) if last.0 == target => {
+                if last.1.is_none() {
+                    last.1 = field;
+                }
+                last.2 &= is_embed;
+            }
+            _ => unique.push((target, field, is_embed)),
+        }
+    }
+    unique
+}
+
 #[cfg(test)]
 mod tests {
     use tempfile::tempdir;
 
     use super::Database;
+    use crate::models::IndexedMarkdownFile;
 
     async fn open_test_db() -> Database {
+        Database::init_memory().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn every_pooled_connection_enforces_foreign_keys_in_wal_mode() {
         let dir = tempdir().unwrap();
-        Database::init(dir.path().join("test.sqlite").to_str().unwrap())
+        let db = Database::init(dir.path().join("index.db").to_str().unwrap()).await.unwrap();
+
+        // Hold several connections at once so they can't all be the same one.
+        let mut conns = Vec::new();
+        for _ in 0..3 {
+            conns.push(db.pool.acquire().await.unwrap());
+        }
+        for conn in &mut conns {
+            let (foreign_keys,) = sqlx::query_as::<_, (i64,)>("PRAGMA foreign_keys")
+                .fetch_one(&mut **conn)
+                .await
+                .unwrap();
+            let (journal_mode,) = sqlx::query_as::<_, (String,)>("PRAGMA journal_mode")
+                .fetch_one(&mut **conn)
+                .await
+                .unwrap();
+            assert_eq!(foreign_keys, 1);
+            assert_eq!(journal_mode, "wal");
+        }
+        drop(conns);
+
+        db.index_file("Vault/Alpha.md", 1, 10, None, None, &["Vault/Beta.md".to_string()])
             .await
-            .unwrap()
+            .unwrap();
+        db.set_note_tags("Vault/Alpha.md", &["project".to_string()]).await.unwrap();
+        db.delete_file("Vault/Alpha.md").await.unwrap();
+
+        assert!(db.get_all_links().await.unwrap().is_empty());
+        assert!(db.get_file_tags("Vault/Alpha.md").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reset_index_empties_derived_tables_but_keeps_recent_files() {
+        let db = open_test_db().await;
+        db.index_file("Vault/Alpha.md", 1, 10, None, None, &["Vault/Beta.md".to_string()])
+            .await
+            .unwrap();
+        db.set_note_tags("Vault/Alpha.md", &["project".to_string()]).await.unwrap();
+        db.upsert_search_file("Vault/Alpha.md", 1, 10, true).await.unwrap();
+        db.record_open("Vault/Alpha.md", 5).await.unwrap();
+
+        db.reset_index().await.unwrap();
+
+        assert!(db.get_all_indexed_files().await.unwrap().is_empty());
+        assert!(db.get_all_links().await.unwrap().is_empty());
+        assert!(db.get_all_tags().await.unwrap().is_empty());
+        assert!(db.get_all_search_files().await.unwrap().is_empty());
+        assert_eq!(db.get_recent_files(10).await.unwrap(), vec![("Vault/Alpha.md".to_string(), 5)]);
+
+        // The recreated schema accepts new rows.
+        db.index_file("Vault/Alpha.md", 2, 10, None, None, &[]).await.unwrap();
+        assert_eq!(db.get_all_indexed_files().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn vacuum_reclaims_space_left_by_deleted_notes() {
+        let db = open_test_db().await;
+        let frontmatter = format!("{{\"summary\":\"{}\"}}", "x".repeat(4096));
+        let paths: Vec<String> = (0..200).map(|i| format!("Vault/Note {i}.md")).collect();
+        for path in &paths {
+            db.index_file(path, 1, 10, Some(&frontmatter), None, &[]).await.unwrap();
+        }
+        db.batch_delete_files(&paths).await.unwrap();
+
+        let (before, after) = db.vacuum().await.unwrap();
+
+        assert!(after < before, "expected {after} < {before}");
+        assert!(db.get_all_indexed_files().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn file_backed_database_persists_across_reopen() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.sqlite");
+        let db = Database::init(db_path.to_str().unwrap()).await.unwrap();
+        db.index_file("Vault/Alpha.md", 10, 100, None, None, &[]).await.unwrap();
+        drop(db);
+
+        let reopened = Database::init(db_path.to_str().unwrap()).await.unwrap();
+        let files = reopened.get_all_indexed_files().await.unwrap();
+        assert_eq!(files, vec![("Vault/Alpha.md".to_string(), 10)]);
+    }
+
+    #[tokio::test]
+    async fn memory_database_survives_transactions_and_stays_private() {
+        let db = Database::init(":memory:").await.unwrap();
+        db.index_file("Vault/Alpha.md", 10, 100, None, None, &["Vault/Beta.md".to_string()])
+            .await
+            .unwrap();
+        db.update_file_path("Vault/Alpha.md", "Vault/Gamma.md").await.unwrap();
+
+        let outgoing = db.get_outgoing_links("Vault/Gamma.md").await.unwrap();
+        assert_eq!(outgoing, vec!["Vault/Beta.md"]);
+
+        let other = Database::init_memory().await.unwrap();
+        assert!(other.get_all_indexed_files().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn attachment_embeds_are_backlinks_but_never_broken() {
+        let db = open_test_db().await;
+        db.index_file_with_link_fields(
+            "Vault/Alpha.md",
+            1,
+            10,
+            None,
+            None,
+            &["Vault/Missing.md".to_string()],
+            &[],
+            &["Vault/diagram.png".to_string()],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(db.get_backlinks("Vault/diagram.png").await.unwrap(), vec!["Vault/Alpha.md"]);
+        assert_eq!(
+            db.get_broken_links().await.unwrap(),
+            vec![("Vault/Alpha.md".to_string(), "Vault/Missing.md".to_string())]
+        );
     }
 
     #[tokio::test]
@@ -1139,6 +1972,117 @@ mod tests {
         assert_eq!(backlinks, vec!["Vault/Alpha.md"]);
     }
 
+    #[tokio::test]
+    async fn searches_note_bodies_with_and_semantics() {
+        let db = open_test_db().await;
+        db.index_file("Vault/Alpha.md", 1, 1, None, None, &[]).await.unwrap();
+        db.index_file("Vault/Beta.md", 1, 1, None, None, &[]).await.unwrap();
+        db.replace_note_bodies(&[
+            ("Vault/Alpha.md", "Rust ownership rules. Ownership moves values, not coownership."),
+            ("Vault/Beta.md", "Ownership in C++ is manual."),
+        ])
+        .await
+        .unwrap();
+
+        let hits = db.search_note_contents("OWNERSHIP rust", 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0, "Vault/Alpha.md");
+        assert!(hits[0].1.contains("<mark>"));
+        // `coownership` is a different token and is not counted.
+        assert_eq!(hits[0].2, 3);
+
+        db.update_file_path("Vault/Alpha.md", "Vault/Renamed.md").await.unwrap();
+        db.delete_file("Vault/Beta.md").await.unwrap();
+        let hits = db.search_note_contents("ownership", 10).await.unwrap();
+        let paths: Vec<&str> = hits.iter().map(|(path, ..)| path.as_str()).collect();
+        assert_eq!(paths, vec!["Vault/Renamed.md"]);
+        assert!(db.search_note_contents("  ", 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn ranks_related_notes_by_shared_link_neighbors() {
+        let db = open_test_db().await;
+        let links = |targets: &[&str]| targets.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+        db.index_file("A.md", 1, 1, None, None, &links(&["X.md", "Y.md", "B.md"])).await.unwrap();
+        db.index_file("B.md", 1, 1, None, None, &links(&["X.md", "Y.md"])).await.unwrap();
+        db.index_file("C.md", 1, 1, None, None, &links(&["X.md"])).await.unwrap();
+        db.index_file("X.md", 1, 1, None, None, &[]).await.unwrap();
+        db.index_file("Y.md", 1, 1, None, None, &[]).await.unwrap();
+
+        let related = db.get_related("A.md", 10, false).await.unwrap();
+        assert_eq!(
+            related,
+            vec![("B.md".to_string(), 2), ("C.md".to_string(), 1)]
+        );
+
+        let unlinked = db.get_related("A.md", 10, true).await.unwrap();
+        assert_eq!(unlinked, vec![("C.md".to_string(), 1)]);
+
+        // X and Y are both cited by A and B.
+        let cocited = db.get_related("X.md", 1, false).await.unwrap();
+        assert_eq!(cocited, vec![("Y.md".to_string(), 2)]);
+    }
+
+    #[tokio::test]
+    async fn lists_notes_modified_within_range_in_time_order() {
+        let db = open_test_db().await;
+        db.index_file("Vault/Late.md", 300, 10, None, None, &[]).await.unwrap();
+        db.index_file("Vault/Early.md", 100, 10, None, None, &[]).await.unwrap();
+        db.index_file("Vault/Middle.md", 200, 10, None, None, &[]).await.unwrap();
+        db.index_file("Vault/Outside.md", 500, 10, None, None, &[]).await.unwrap();
+
+        let matches = db.get_notes_modified_between(100, 300).await.unwrap();
+        assert_eq!(
+            matches,
+            vec![
+                ("Vault/Late.md".to_string(), 300),
+                ("Vault/Middle.md".to_string(), 200),
+                ("Vault/Early.md".to_string(), 100),
+            ]
+        );
+
+        let inverted = db.get_notes_modified_between(300, 100).await.unwrap();
+        assert!(inverted.is_empty());
+    }
+
+    #[tokio::test]
+    async fn reindexing_only_rewrites_changed_links() {
+        let db = open_test_db().await;
+        let links = ["Vault/B.md".to_string(), "Vault/C.md".to_string()];
+        db.index_file("Vault/A.md", 10, 100, None, None, &links).await.unwrap();
+
+        let link_rowids = |db: &Database| {
+            let pool = db.pool.clone();
+            async move {
+                sqlx::query_as::<_, (String, i64)>(
+                    "SELECT target_path, rowid FROM links WHERE source_path = 'Vault/A.md' ORDER BY target_path",
+                )
+                .fetch_all(&pool)
+                .await
+                .unwrap()
+            }
+        };
+        let before = link_rowids(&db).await;
+
+        db.index_file("Vault/A.md", 20, 100, None, None, &links).await.unwrap();
+        assert_eq!(link_rowids(&db).await, before);
+
+        db.index_file(
+            "Vault/A.md",
+            30,
+            100,
+            None,
+            None,
+            &["Vault/B.md".to_string(), "Vault/D.md".to_string()],
+        )
+        .await
+        .unwrap();
+        let after = link_rowids(&db).await;
+        assert_eq!(after[0], before[0]);
+        assert_eq!(after.len(), 2);
+        assert_eq!(after[1].0, "Vault/D.md");
+    }
+
     #[tokio::test]
     async fn updates_file_paths_for_folder_renames() {
         let db = open_test_db().await;
@@ -1162,6 +2106,36 @@ mod tests {
         assert_eq!(backlinks, vec!["Vault/Renamed/Alpha.md"]);
     }
 
+    #[tokio::test]
+    async fn batch_path_updates_move_notes_links_and_search_rows_together() {
+        let db = open_test_db().await;
+        db.index_file("Vault/A.md", 10, 100, None, None, &["Vault/Dir/B.md".to_string()])
+            .await
+            .unwrap();
+        db.index_file("Vault/Dir/B.md", 20, 120, None, None, &[]).await.unwrap();
+        db.upsert_search_file("Vault/A.md", 10, 100, true).await.unwrap();
+        db.upsert_search_file("Vault/Dir/B.md", 20, 120, true).await.unwrap();
+
+        db.update_file_paths(&[
+            ("Vault/A.md".to_string(), "Vault/Dest/A.md".to_string()),
+            ("Vault/Dir".to_string(), "Vault/Dest/Dir".to_string()),
+        ])
+        .await
+        .unwrap();
+
+        let outgoing = db.get_outgoing_links("Vault/Dest/A.md").await.unwrap();
+        assert_eq!(outgoing, vec!["Vault/Dest/Dir/B.md"]);
+        let mut search_paths: Vec<String> = db
+            .get_all_search_files()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(path, ..)| path)
+            .collect();
+        search_paths.sort();
+        assert_eq!(search_paths, vec!["Vault/Dest/A.md", "Vault/Dest/Dir/B.md"]);
+    }
+
     #[tokio::test]
     async fn searches_tags_and_collects_frontmatter_metadata() {
         let db = open_test_db().await;
@@ -1212,8 +2186,165 @@ mod tests {
         let tags = db.get_all_tags().await.unwrap();
         assert_eq!(tags, vec!["alpha", "inline", "project", "team"]);
 
+        let counts = db.get_tag_counts().await.unwrap();
+        assert_eq!(
+            counts,
+            vec![
+                ("project".to_string(), 2),
+                ("alpha".to_string(), 1),
+                ("team".to_string(), 1),
+            ]
+        );
+
         let keys = db.get_all_property_keys().await.unwrap();
         assert_eq!(keys, vec!["owner", "status", "tags"]);
     }
-}
 
+    #[tokio::test]
+    async fn recent_files_keep_the_latest_open_per_path() {
+        let db = open_test_db().await;
+        db.record_open("Vault/A.md", 100).await.unwrap();
+        db.record_open("Vault/B.md", 200).await.unwrap();
+        db.record_open("Vault/A.md", 300).await.unwrap();
+
+        assert_eq!(
+            db.get_recent_files(10).await.unwrap(),
+            vec![("Vault/A.md".to_string(), 300), ("Vault/B.md".to_string(), 200)]
+        );
+        assert_eq!(db.get_recent_files(1).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn recent_files_follow_renamed_notes_and_drop_deleted_ones() {
+        let db = open_test_db().await;
+        for path in ["Vault/A.md", "Vault/Projects/B.md", "Vault/C.md"] {
+            db.index_file(path, 1, 1, None, None, &[]).await.unwrap();
+        }
+        db.record_open("Vault/A.md", 100).await.unwrap();
+        db.record_open("Vault/Projects/B.md", 200).await.unwrap();
+        db.record_open("Vault/C.md", 300).await.unwrap();
+
+        db.update_file_path("Vault/A.md", "Vault/Renamed.md").await.unwrap();
+        db.update_file_path("Vault/Projects", "Vault/Archive").await.unwrap();
+        db.delete_file("Vault/C.md").await.unwrap();
+
+        assert_eq!(
+            db.get_recent_files(10).await.unwrap(),
+            vec![
+                ("Vault/Archive/B.md".to_string(), 200),
+                ("Vault/Renamed.md".to_string(), 100),
+            ]
+        );
+
+        db.delete_files_by_prefix("Vault/Archive/").await.unwrap();
+        assert_eq!(
+            db.get_recent_files(10).await.unwrap(),
+            vec![("Vault/Renamed.md".to_string(), 100)]
+        );
+    }
+
+    #[tokio::test]
+    async fn ranks_notes_by_backlink_count() {
+        let db = open_test_db().await;
+        let links = |targets: &[&str]| targets.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+        db.index_file("A.md", 1, 1, None, None, &links(&["Hub.md", "B.md", "Missing.md"])).await.unwrap();
+        db.index_file("B.md", 1, 1, None, None, &links(&["Hub.md"])).await.unwrap();
+        db.index_file("C.md", 1, 1, None, None, &links(&["Hub.md", "B.md", "Missing.md"])).await.unwrap();
+        db.index_file("Hub.md", 1, 1, None, None, &[]).await.unwrap();
+
+        assert_eq!(
+            db.get_most_linked(10).await.unwrap(),
+            vec![("Hub.md".to_string(), 3), ("B.md".to_string(), 2)]
+        );
+        assert_eq!(db.get_most_linked(1).await.unwrap().len(), 1);
+
+        db.record_open("B.md", 50).await.unwrap();
+        let mut ranks = db
+            .get_note_ranks(&links(&["B.md", "Hub.md", "Missing.md"]))
+            .await
+            .unwrap();
+        ranks.sort();
+        assert_eq!(ranks, vec![("B.md".to_string(), 50, 2), ("Hub.md".to_string(), 0, 3)]);
+    }
+
+    #[tokio::test]
+    async fn finds_titles_by_literal_prefix_ignoring_case() {
+        let db = open_test_db().await;
+        for (path, title) in [("a.md", "Plan_B"), ("b.md", "PlanXB"), ("c.md", "planning"), ("d.md", "Other")] {
+            db.index_file(path, 1, 1, None, None, &[]).await.unwrap();
+            db.set_note_title(path, title).await.unwrap();
+        }
+
+        let titles = |rows: Vec<(String, String)>| rows.into_iter().map(|(_, title)| title).collect::<Vec<_>>();
+        assert_eq!(titles(db.get_notes_with_title_prefix("PLAN").await.unwrap()), vec!["Plan_B", "PlanXB", "planning"]);
+        assert_eq!(titles(db.get_notes_with_title_prefix("plan_").await.unwrap()), vec!["Plan_B"]);
+    }
+
+    #[tokio::test]
+    async fn looks_up_aliases_case_insensitively_and_follows_renames() {
+        let db = open_test_db().await;
+        db.index_file("Vault/User Authentication.md", 1, 10, None, None, &[]).await.unwrap();
+        db.set_note_aliases("Vault/User Authentication.md", &["Auth".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(db.get_paths_by_alias("auth").await.unwrap(), vec!["Vault/User Authentication.md"]);
+
+        db.update_file_path("Vault/User Authentication.md", "Vault/Auth.md")
+            .await
+            .unwrap();
+        assert_eq!(db.get_paths_by_alias("Auth").await.unwrap(), vec!["Vault/Auth.md"]);
+        assert_eq!(
+            db.get_all_note_aliases().await.unwrap(),
+            vec![("Vault/Auth.md".to_string(), "Auth".to_string())]
+        );
+
+        db.delete_file("Vault/Auth.md").await.unwrap();
+        assert!(db.get_paths_by_alias("Auth").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn replace_markdown_batch_swaps_links_and_tags_in_bulk() {
+        let db = open_test_db().await;
+        let entry = |path: &str, modified: i64, links: &[&str], tags: &[&str]| IndexedMarkdownFile {
+            path: path.to_string(),
+            modified,
+            size: 10,
+            frontmatter_json: None,
+            title: None,
+            aliases: Vec::new(),
+            inline_tags: tags.iter().map(|t| t.to_string()).collect(),
+            resolved_links: links.iter().map(|l| l.to_string()).collect(),
+            link_fields: Vec::new(),
+            embeds: Vec::new(),
+        };
+        db.insert_markdown_batch_initial(&[
+            entry("Vault/A.md", 1, &["Vault/B.md"], &["old"]),
+            entry("Vault/B.md", 1, &[], &[]),
+        ])
+        .await
+        .unwrap();
+
+        db.replace_markdown_batch(&[
+            entry("Vault/A.md", 2, &["Vault/C.md"], &["new"]),
+            entry("Vault/C.md", 2, &["Vault/A.md"], &[]),
+        ])
+        .await
+        .unwrap();
+
+        assert_eq!(db.get_outgoing_links("Vault/A.md").await.unwrap(), vec!["Vault/C.md"]);
+        assert_eq!(db.get_backlinks("Vault/A.md").await.unwrap(), vec!["Vault/C.md"]);
+        assert_eq!(db.get_file_tags("Vault/A.md").await.unwrap(), vec!["new"]);
+        assert!(db.get_notes_with_tag("old").await.unwrap().is_empty());
+        let mut files = db.get_all_indexed_files().await.unwrap();
+        files.sort();
+        assert_eq!(
+            files,
+            vec![
+                ("Vault/A.md".to_string(), 2),
+                ("Vault/B.md".to_string(), 1),
+                ("Vault/C.md".to_string(), 2),
+            ]
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use serde::Serialize;
+
 #[derive(Debug, thiserror::Error)]
 pub enum TessellumError {
 	#[error("Database error: {0}")]
@@ -17,3 +19,55 @@ impl From<TessellumError> for tauri::ipc::InvokeError {
 		tauri::ipc::InvokeError::from(e.to_string())
 	}
 }
+
+/// Cause of a `FileError`, so the UI can offer the matching recovery action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum FileErrorKind {
+	NotFound,
+	PermissionDenied,
+	InvalidUtf8,
+	Other,
+}
+
+/// Error returned by `read_file` and `write_file`.
+///
+/// Serialized as `{ "kind": "NotFound", "message": "..." }`, unlike
+/// `TessellumError`, which reaches the frontend as a plain string.
+#[derive(Debug, Serialize, thiserror::Error)]
+#[error("{message}")]
+pub struct FileError {
+	pub kind: FileErrorKind,
+	pub message: String,
+}
+
+impl FileError {
+	pub fn new(kind: FileErrorKind, message: impl Into<String>) -> Self {
+		Self {
+			kind,
+			message: message.into(),
+		}
+	}
+}
+
+impl From<std::io::Error> for FileError {
+	fn from(e: std::io::Error) -> Self {
+		let kind = match e.kind() {
+			std::io::ErrorKind::NotFound => FileErrorKind::NotFound,
+			std::io::ErrorKind::PermissionDenied => FileErrorKind::PermissionDenied,
+			// `read_to_string` reports bytes that are not UTF-8 as invalid data.
+			std::io::ErrorKind::InvalidData => FileErrorKind::InvalidUtf8,
+			_ => FileErrorKind::Other,
+		};
+		Self::new(kind, e.to_string())
+	}
+}
+
+impl From<TessellumError> for FileError {
+	fn from(e: TessellumError) -> Self {
+		match e {
+			TessellumError::Io(io) => io.into(),
+			TessellumError::NotFound(_) => Self::new(FileErrorKind::NotFound, e.to_string()),
+			other => Self::new(FileErrorKind::Other, other.to_string()),
+		}
+	}
+}
@@ -1,23 +1,66 @@
 use std::collections::HashMap;
 use std::fs;
+use std::io::{BufRead, BufReader};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Instant, UNIX_EPOCH};
+use serde::Serialize;
 use walkdir::WalkDir;
 
-use crate::commands::extract_wikilinks;
+use crate::commands::{extract_markdown_links, extract_wikilinks, partition_attachment_embeds};
 use crate::db::Database;
-use crate::models::{FileIndex, IndexedMarkdownFile, IndexedSearchFile};
+use crate::models::{AssetIndex, FileIndex, IndexedMarkdownFile, IndexedSearchFile, LinkStrategy};
 use crate::search::SearchDoc;
 use crate::search::SearchIndex;
-use crate::utils::{extract_tags, is_hidden_or_special};
+use crate::utils::config::{read_config, IndexingConfig};
+use crate::utils::{extract_tags, IgnoreRules};
 
 /// Statistics about the indexing operation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct IndexStats {
     pub files_indexed: usize,
     pub files_deleted: usize,
     pub files_skipped: usize,
     pub duration_ms: u128,
+    /// `true` when the sync stopped early because cancellation was requested.
+    pub cancelled: bool,
+}
+
+/// Modification time in whole seconds since the Unix epoch, as stored in the index.
+fn modified_secs(metadata: &fs::Metadata) -> i64 {
+    metadata
+        .modified()
+        .unwrap_or(UNIX_EPOCH)
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+/// Longest frontmatter block scanned for aliases, in lines.
+const MAX_FRONTMATTER_LINES: usize = 200;
+
+/// Aliases declared in the frontmatter of the note at `path`.
+///
+/// Only the frontmatter block is read. Missing or unreadable files have no
+/// aliases.
+pub(crate) fn read_frontmatter_aliases(path: &str) -> Vec<String> {
+    let Ok(file) = fs::File::open(path) else {
+        return Vec::new();
+    };
+    let mut lines = BufReader::new(file).lines().map_while(Result::ok);
+    match lines.next() {
+        Some(first) if first.trim_start_matches('\u{FEFF}').trim_end() == "---" => {}
+        _ => return Vec::new(),
+    }
+    let mut yaml = String::new();
+    for line in lines.take(MAX_FRONTMATTER_LINES) {
+        if line.trim_end() == "---" {
+            return crate::utils::frontmatter::extract_aliases(&yaml);
+        }
+        yaml.push_str(&line);
+        yaml.push('\n');
+    }
+    Vec::new()
 }
 
 /// Vault indexer for syncing database with filesystem.
@@ -31,12 +74,43 @@ impl VaultIndexer {
     /// 2. Compares with the database to find new/modified/deleted files
     /// 3. Indexes new and modified files
     /// 4. Removes deleted files from the database
+    ///
+    /// Uses the default [`IndexingConfig`]; see [`Self::full_sync_with_config`].
     pub async fn full_sync(
         db: &Database,
         search_index: std::sync::Arc<tokio::sync::Mutex<SearchIndex>>,
         vault_path: &str,
+    ) -> Result<IndexStats, String> {
+        Self::full_sync_with_config(
+            db,
+            search_index,
+            vault_path,
+            &IndexingConfig::default(),
+            &AtomicBool::new(false),
+        )
+        .await
+    }
+
+    /// Perform a full sync using the vault's indexing tunables.
+    ///
+    /// `index_concurrency` bounds the threads that read and parse notes, and
+    /// `index_batch_size` bounds how many notes are written per transaction.
+    ///
+    /// `cancel` is checked between files and between batches. Once set, the
+    /// sync stops after the current batch commits and reports partial counts
+    /// with `cancelled: true`; unprocessed files are picked up next sync.
+    pub async fn full_sync_with_config(
+        db: &Database,
+        search_index: std::sync::Arc<tokio::sync::Mutex<SearchIndex>>,
+        vault_path: &str,
+        config: &IndexingConfig,
+        cancel: &AtomicBool,
     ) -> Result<IndexStats, String> {
         let start = Instant::now();
+        let concurrency = config.concurrency();
+        let batch_size = config.batch_size();
+        let link_sources = config.link_sources();
+        let link_strategy = read_config(vault_path).link_resolution;
         
         let mut files_indexed = 0;
         let mut files_deleted = 0;
@@ -45,7 +119,7 @@ impl VaultIndexer {
         log::info!("Starting vault sync for: {}", vault_path);
         
         // 1. Get all files from filesystem with their modified times
-        let fs_files = Self::collect_filesystem_files(vault_path)?;
+        let fs_files = Self::collect_filesystem_files(vault_path, &link_sources)?;
         log::debug!("Found {} files in filesystem", fs_files.len());
         
         // 2. Get all indexed search files from database
@@ -59,55 +133,59 @@ impl VaultIndexer {
         log::debug!("Found {} files in database", db_files.len());
         let is_initial_sync = db_files.is_empty();
         
-        // 3. Build file index for link resolution
-        let file_index = FileIndex::from_markdown_paths(
+        // Databases created before note bodies were indexed have an empty
+        // full-text table; re-read every note once to fill it.
+        let backfill_bodies = !is_initial_sync
+            && db
+                .count_note_bodies()
+                .await
+                .map_err(|e| format!("Failed to count note contents: {}", e))?
+                == 0;
+        
+        // 3. Build file index for link resolution (only real notes are link targets)
+        let mut file_index = FileIndex::from_markdown_paths(
             fs_files
-                .iter()
-                .filter(|(_, (_, _, is_markdown))| *is_markdown)
-                .map(|(path, _)| path.as_str()),
+                .keys()
+                .filter(|path| path.ends_with(".md"))
+                .map(|path| path.as_str()),
         );
+        let asset_index = AssetIndex::from_paths(fs_files.keys().map(|path| path.as_str()));
         
         // 4. Process each filesystem file
-        let mut docs_to_index: Vec<SearchDoc> = Vec::new();
+        let mut other_docs: Vec<SearchDoc> = Vec::new();
+        let mut markdown_docs: Vec<SearchDoc> = Vec::new();
         let mut markdown_updates = Vec::new();
         let mut other_file_updates = Vec::new();
+        let mut pending_markdown: Vec<(&str, i64, u64)> = Vec::new();
         
         for (path, (modified_time, size, is_markdown)) in &fs_files {
+            if cancel.load(Ordering::Relaxed) {
+                break;
+            }
             let needs_index = match db_files.get(path) {
                 None => true, // New file
                 // Re-index if mtime changed OR if mtime is equal but size changed
                 // (handles same-second edits that only touch frontmatter).
-                Some((db_modified, _, db_size)) => {
+                // Also re-index when a file starts or stops being a link source
+                // because the configured extensions changed.
+                Some((db_modified, db_is_markdown, db_size)) => {
                     *modified_time > *db_modified
                         || (*modified_time == *db_modified && *db_size != *size as i64)
+                        || *db_is_markdown != *is_markdown
+                        || (backfill_bodies && *is_markdown)
                 }
             };
             
             if needs_index {
                 if *is_markdown {
-                    match Self::prepare_markdown_index_update(
-                        vault_path,
-                        path,
-                        *modified_time,
-                        *size,
-                        &file_index,
-                    ) {
-                        Ok((indexed_file, search_doc)) => {
-                            markdown_updates.push(indexed_file);
-                            docs_to_index.push(search_doc);
-                            files_indexed += 1;
-                        }
-                        Err(e) => {
-                            log::warn!("Failed to index {}: {}", path, e);
-                        }
-                    }
+                    pending_markdown.push((path.as_str(), *modified_time, *size));
                 } else {
                     let title = Path::new(path)
                         .file_name()
                         .unwrap_or_default()
                         .to_string_lossy()
                         .to_string();
-                    docs_to_index.push(SearchDoc {
+                    other_docs.push(SearchDoc {
                         path: path.clone(),
                         title,
                         body: String::new(),
@@ -119,55 +197,129 @@ impl VaultIndexer {
                         size: *size,
                         is_markdown: false,
                     });
-                    files_indexed += 1;
                 }
             } else {
                 files_skipped += 1;
             }
         }
 
-        if is_initial_sync {
-            db.insert_markdown_batch_initial(&markdown_updates)
-                .await
-                .map_err(|e| format!("Failed to insert markdown files: {}", e))?;
-        } else {
-            db.replace_markdown_batch(&markdown_updates)
-                .await
-                .map_err(|e| format!("Failed to update markdown files: {}", e))?;
-        }
-        db.upsert_search_files_batch(&other_file_updates)
+        // Aliases of unchanged notes come from the database; notes about to be
+        // re-read get theirs from disk first, so links to a new alias resolve
+        // in this same pass.
+        let stored_aliases = db
+            .get_all_note_aliases()
             .await
-            .map_err(|e| format!("Failed to update search files: {}", e))?;
-        
-        // 5. Find and delete files that no longer exist
-        let fs_paths: std::collections::HashSet<&String> = fs_files.keys().collect();
-        let deleted_paths: Vec<String> = db_files
-            .keys()
-            .filter(|p| !fs_paths.contains(p))
-            .cloned()
-            .collect();
-        
-        if !deleted_paths.is_empty() {
-            log::debug!("Removing {} deleted files from index", deleted_paths.len());
-            let mut markdown_deleted: Vec<String> = Vec::new();
-            for path in &deleted_paths {
-                if let Some((_, is_md, _)) = db_files.get(path)
-                    && *is_md {
-                        markdown_deleted.push(path.clone());
-                    }
+            .map_err(|e| format!("Failed to load note aliases: {}", e))?;
+        file_index.load_aliases(stored_aliases.into_iter().filter(|(path, _)| fs_files.contains_key(path)));
+        for (path, _, _) in &pending_markdown {
+            file_index.set_aliases(path, &read_frontmatter_aliases(path));
+        }
+
+        for (path, result) in Self::prepare_markdown_updates(
+            vault_path,
+            &pending_markdown,
+            &file_index,
+            &asset_index,
+            link_strategy,
+            concurrency,
+            cancel,
+        ) {
+            match result {
+                Ok((indexed_file, search_doc)) => {
+                    markdown_updates.push(indexed_file);
+                    markdown_docs.push(search_doc);
+                }
+                Err(e) => {
+                    log::warn!("Failed to index {}: {}", path, e);
+                }
+            }
+        }
+
+        // Each batch is its own transaction, so stopping between batches
+        // never leaves a half-written batch behind.
+        let mut markdown_written = 0;
+        for (chunk, docs) in markdown_updates
+            .chunks(batch_size)
+            .zip(markdown_docs.chunks(batch_size))
+        {
+            if cancel.load(Ordering::Relaxed) {
+                break;
             }
-            if !markdown_deleted.is_empty() {
-                files_deleted = db
-                    .batch_delete_files(&markdown_deleted)
+            if is_initial_sync {
+                db.insert_markdown_batch_initial(chunk)
+                    .await
+                    .map_err(|e| format!("Failed to insert markdown files: {}", e))?;
+            } else {
+                db.replace_markdown_batch(chunk)
                     .await
-                    .map_err(|e| format!("Failed to delete files: {}", e))?;
+                    .map_err(|e| format!("Failed to update markdown files: {}", e))?;
             }
-            db.delete_search_files(&deleted_paths)
+            let bodies: Vec<(&str, &str)> = docs
+                .iter()
+                .map(|doc| (doc.path.as_str(), doc.body.as_str()))
+                .collect();
+            db.replace_note_bodies(&bodies)
                 .await
-                .map_err(|e| format!("Failed to delete search files: {}", e))?;
+                .map_err(|e| format!("Failed to update note contents: {}", e))?;
+            markdown_written += chunk.len();
+        }
+        files_indexed += markdown_written;
+        markdown_docs.truncate(markdown_written);
+
+        let cancelled = cancel.load(Ordering::Relaxed);
+        let mut docs_to_index = markdown_docs;
+        let mut deleted_paths: Vec<String> = Vec::new();
+
+        if !cancelled {
+            db.upsert_search_files_batch(&other_file_updates)
+                .await
+                .map_err(|e| format!("Failed to update search files: {}", e))?;
+            files_indexed += other_file_updates.len();
+            docs_to_index.extend(other_docs);
+            
+            // Files that are no longer link sources keep their search entry but
+            // must drop their note row and outgoing links.
+            let demoted_paths: Vec<String> = other_file_updates
+                .iter()
+                .filter(|file| db_files.get(&file.path).is_some_and(|(_, is_md, _)| *is_md))
+                .map(|file| file.path.clone())
+                .collect();
+            if !demoted_paths.is_empty() {
+                db.batch_delete_files(&demoted_paths)
+                    .await
+                    .map_err(|e| format!("Failed to delete demoted link sources: {}", e))?;
+            }
+            
+            // 5. Find and delete files that no longer exist
+            let fs_paths: std::collections::HashSet<&String> = fs_files.keys().collect();
+            deleted_paths = db_files
+                .keys()
+                .filter(|p| !fs_paths.contains(p))
+                .cloned()
+                .collect();
+            
+            if !deleted_paths.is_empty() {
+                log::debug!("Removing {} deleted files from index", deleted_paths.len());
+                let mut markdown_deleted: Vec<String> = Vec::new();
+                for path in &deleted_paths {
+                    if let Some((_, is_md, _)) = db_files.get(path)
+                        && *is_md {
+                            markdown_deleted.push(path.clone());
+                        }
+                }
+                if !markdown_deleted.is_empty() {
+                    files_deleted = db
+                        .batch_delete_files(&markdown_deleted)
+                        .await
+                        .map_err(|e| format!("Failed to delete files: {}", e))?;
+                }
+                db.delete_search_files(&deleted_paths)
+                    .await
+                    .map_err(|e| format!("Failed to delete search files: {}", e))?;
+            }
         }
         
-        // Update search index in batch
+        // Update search index in batch (only for what reached the database)
         if !docs_to_index.is_empty() || !deleted_paths.is_empty() {
             let guard = search_index.lock().await;
             if is_initial_sync {
@@ -184,7 +336,8 @@ impl VaultIndexer {
         let duration_ms = start.elapsed().as_millis();
         
         log::info!(
-            "Vault sync complete in {}ms: {} indexed, {} skipped, {} deleted",
+            "Vault sync {} in {}ms: {} indexed, {} skipped, {} deleted",
+            if cancelled { "cancelled" } else { "complete" },
             duration_ms,
             files_indexed,
             files_skipped,
@@ -196,12 +349,78 @@ impl VaultIndexer {
             files_deleted,
             files_skipped,
             duration_ms,
+            cancelled,
         })
     }
     
+    /// Re-index a single note after an external change.
+    ///
+    /// An existing `.md` file is re-read and its note row, links, tags, body
+    /// and search document replaced. A path that no longer exists is removed
+    /// from the index via [`Database::delete_file`]. Hidden paths and other
+    /// files are ignored. Returns `true` when the index was touched.
+    pub async fn index_path(
+        db: &Database,
+        search_index: &tokio::sync::Mutex<SearchIndex>,
+        vault_path: &str,
+        path: &Path,
+        file_index: &FileIndex,
+        asset_index: &AssetIndex,
+    ) -> Result<bool, String> {
+        let rel_path = path.strip_prefix(vault_path).unwrap_or(path);
+        if IgnoreRules::load(vault_path).is_ignored(rel_path, false)
+            || path.extension().and_then(|s| s.to_str()) != Some("md")
+        {
+            return Ok(false);
+        }
+        let normalized = crate::utils::normalize_path(&path.to_string_lossy());
+
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) if metadata.is_file() => metadata,
+            Ok(_) => return Ok(false),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                db.delete_file(&normalized)
+                    .await
+                    .map_err(|e| format!("Failed to delete file: {}", e))?;
+                db.delete_search_files(std::slice::from_ref(&normalized))
+                    .await
+                    .map_err(|e| format!("Failed to delete search file: {}", e))?;
+                search_index.lock().await.delete_path(&normalized)?;
+                return Ok(true);
+            }
+            Err(e) => return Err(format!("Failed to read metadata: {}", e)),
+        };
+
+        let (indexed_file, search_doc) = Self::prepare_markdown_index_update(
+            vault_path,
+            &path.to_string_lossy(),
+            modified_secs(&metadata),
+            metadata.len(),
+            file_index,
+            asset_index,
+            read_config(vault_path).link_resolution,
+        )?;
+        db.replace_markdown_batch(std::slice::from_ref(&indexed_file))
+            .await
+            .map_err(|e| format!("Failed to update markdown file: {}", e))?;
+        db.replace_note_bodies(&[(search_doc.path.as_str(), search_doc.body.as_str())])
+            .await
+            .map_err(|e| format!("Failed to update note contents: {}", e))?;
+        search_index
+            .lock()
+            .await
+            .index_batch(std::slice::from_ref(&search_doc), &[])?;
+
+        Ok(true)
+    }
+
     /// Collect all files from the filesystem with their modified times.
+    ///
+    /// The boolean marks link sources: `.md` notes plus any extension listed in
+    /// `link_sources`, whose contents are parsed for wikilinks.
     fn collect_filesystem_files(
         vault_path: &str,
+        link_sources: &[String],
     ) -> Result<HashMap<String, (i64, u64, bool)>, String> {
         let mut files = HashMap::new();
         
@@ -209,31 +428,27 @@ impl VaultIndexer {
             return Err("Vault path does not exist".to_string());
         }
         
-        for entry in WalkDir::new(vault_path).into_iter().filter_map(|e| e.ok()) {
+        // Skip hidden files/dirs (.git, .trash, etc.) and .tessellumignore matches
+        let ignore = IgnoreRules::load(vault_path);
+        for entry in WalkDir::new(vault_path)
+            .into_iter()
+            .filter_entry(|e| ignore.keeps(vault_path, e))
+            .filter_map(|e| e.ok())
+        {
             let path = entry.path();
 
-            // Skip hidden files/dirs (.git, .trash, etc.)
-            let rel_path = path.strip_prefix(vault_path).unwrap_or(path);
-            if is_hidden_or_special(rel_path) {
-                continue;
-            }
-
             if !entry.file_type().is_file() {
                 continue;
             }
 
-            let is_markdown = path.extension().and_then(|s| s.to_str()) == Some("md");
+            let is_markdown = path
+                .extension()
+                .and_then(|s| s.to_str())
+                .is_some_and(|ext| ext == "md" || link_sources.iter().any(|source| source.eq_ignore_ascii_case(ext)));
             if let Ok(metadata) = entry.metadata() {
-                let modified_time = metadata
-                    .modified()
-                    .unwrap_or(UNIX_EPOCH)
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_secs() as i64;
-
                 files.insert(
                     crate::utils::normalize_path(&path.to_string_lossy()),
-                    (modified_time, metadata.len(), is_markdown),
+                    (modified_secs(&metadata), metadata.len(), is_markdown),
                 );
             }
         }
@@ -241,6 +456,62 @@ impl VaultIndexer {
         Ok(files)
     }
     
+    /// Read and parse pending markdown files on up to `concurrency` threads.
+    ///
+    /// Results keep the input order so batches are written deterministically.
+    fn prepare_markdown_updates<'a>(
+        vault_path: &str,
+        pending: &[(&'a str, i64, u64)],
+        file_index: &FileIndex,
+        asset_index: &AssetIndex,
+        link_strategy: LinkStrategy,
+        concurrency: usize,
+        cancel: &AtomicBool,
+    ) -> Vec<(&'a str, Result<(IndexedMarkdownFile, SearchDoc), String>)> {
+        if pending.is_empty() {
+            return Vec::new();
+        }
+
+        let chunk_size = pending.len().div_ceil(concurrency.max(1));
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = pending
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .take_while(|_| !cancel.load(Ordering::Relaxed))
+                            .map(|(path, modified, size)| {
+                                (
+                                    *path,
+                                    Self::prepare_markdown_index_update(
+                                        vault_path,
+                                        path,
+                                        *modified,
+                                        *size,
+                                        file_index,
+                                        asset_index,
+                                        link_strategy,
+                                    ),
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| {
+                    worker.join().unwrap_or_else(|_| {
+                        log::warn!("Indexing worker panicked; its files will be retried next sync");
+                        Vec::new()
+                    })
+                })
+                .collect()
+        })
+    }
+
     /// Prepare the database and search projections for a single markdown file.
     fn prepare_markdown_index_update(
         vault_path: &str,
@@ -248,34 +519,65 @@ impl VaultIndexer {
         modified: i64,
         size: u64,
         file_index: &FileIndex,
+        asset_index: &AssetIndex,
+        link_strategy: LinkStrategy,
     ) -> Result<(IndexedMarkdownFile, SearchDoc), String> {
-        // Read file content
-        let content =
-            fs::read_to_string(file_path).map_err(|e| format!("Failed to read file: {}", e))?;
+        // Read file content. Notes that are not valid UTF-8 (e.g. latin-1
+        // imports) are indexed with the bad bytes replaced rather than skipped.
+        let bytes = fs::read(file_path).map_err(|e| format!("Failed to read file: {}", e))?;
+        let content = match String::from_utf8(bytes) {
+            Ok(content) => content,
+            Err(e) => {
+                log::warn!("{} is not valid UTF-8; indexing it lossily", file_path);
+                String::from_utf8_lossy(e.as_bytes()).into_owned()
+            }
+        };
         
         // Parse frontmatter
         let mut frontmatter_json_str = None;
         let mut body_content = content.as_str();
+        let mut frontmatter_links = Vec::new();
+        let mut aliases = Vec::new();
         
         if let Some((yaml, _)) = crate::utils::frontmatter::parse_frontmatter(&content) {
             body_content = crate::utils::frontmatter::strip_frontmatter(&content);
             if let Ok(json) = crate::utils::frontmatter::frontmatter_to_json(&yaml) {
                 frontmatter_json_str = Some(json);
             }
+            frontmatter_links = crate::utils::frontmatter::extract_frontmatter_links(&yaml);
+            aliases = crate::utils::frontmatter::extract_aliases(&yaml);
         }
         
         let inline_tags = extract_tags(&content);
         
-        let wikilinks = extract_wikilinks(body_content);
+        let resolve = |target: &str| {
+            crate::utils::normalize_path(
+                &file_index
+                    .resolve_or_default_with(vault_path, target, Path::new(file_path), link_strategy)
+                    .to_string_lossy(),
+            )
+        };
+        // `[[#Heading]]` points inside this note and is not a graph edge.
+        let wikilinks: Vec<_> = extract_wikilinks(body_content)
+            .into_iter()
+            .filter(|link| !link.target.is_empty())
+            .collect();
+        let (wikilinks, embeds) = partition_attachment_embeds(
+            wikilinks,
+            file_index,
+            asset_index,
+            vault_path,
+            Path::new(file_path),
+            link_strategy,
+        );
         let resolved_links: Vec<String> = wikilinks
             .iter()
-            .map(|link| {
-                crate::utils::normalize_path(
-                    &file_index
-                        .resolve_or_default(vault_path, &link.target)
-                        .to_string_lossy(),
-                )
-            })
+            .map(|link| resolve(&link.target))
+            .chain(extract_markdown_links(body_content, file_path, vault_path))
+            .collect();
+        let link_fields: Vec<(String, String)> = frontmatter_links
+            .into_iter()
+            .map(|(field, target)| (resolve(&target), field))
             .collect();
         
         let normalized_path = crate::utils::normalize_path(file_path);
@@ -293,8 +595,12 @@ impl VaultIndexer {
                 modified,
                 size,
                 frontmatter_json: frontmatter_json_str,
+                title: Some(crate::utils::frontmatter::note_title(&content, file_path)),
+                aliases,
                 inline_tags: inline_tags.clone(),
                 resolved_links,
+                link_fields,
+                embeds,
             },
             SearchDoc {
                 path: normalized_path,
@@ -308,15 +614,14 @@ impl VaultIndexer {
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
 
     use tempfile::tempdir;
-    use tokio::sync::Mutex;
 
     use super::VaultIndexer;
     use crate::db::Database;
-    use crate::search::SearchIndex;
-    use crate::test_support::TestVault;
+    use crate::test_support::{TestSearchIndex, TestVault};
+    use crate::utils::config::IndexingConfig;
 
     #[test]
     fn collects_filesystem_files_skipping_hidden_entries() {
@@ -329,7 +634,7 @@ mod tests {
         let image_path = crate::utils::normalize_path(&vault.path().join("image.png").to_string_lossy());
         let hidden_path = crate::utils::normalize_path(&vault.path().join(".trash/Hidden.md").to_string_lossy());
 
-        let files = VaultIndexer::collect_filesystem_files(vault.path().to_str().unwrap()).unwrap();
+        let files = VaultIndexer::collect_filesystem_files(vault.path().to_str().unwrap(), &[]).unwrap();
 
         assert!(files.contains_key(&note_path));
         assert!(files.contains_key(&image_path));
@@ -348,8 +653,7 @@ mod tests {
         let db = Database::init(db_dir.path().join("indexer.sqlite").to_str().unwrap())
             .await
             .unwrap();
-        let search_dir = tempdir().unwrap();
-        let search_index = Arc::new(Mutex::new(SearchIndex::open_or_create(&search_dir.path().join("search-index")).unwrap()));
+        let search_index = TestSearchIndex::new();
 
         let first = VaultIndexer::full_sync(
             &db,
@@ -365,7 +669,7 @@ mod tests {
 
         let second = VaultIndexer::full_sync(
             &db,
-            search_index,
+            search_index.clone(),
             vault.path().to_str().unwrap(),
         )
         .await
@@ -376,4 +680,254 @@ mod tests {
         assert_eq!(indexed.len(), 1);
         assert_eq!(indexed[0].0, crate::utils::normalize_path(&vault.path().join("Inbox/Alpha.md").to_string_lossy()));
     }
+
+    #[tokio::test]
+    async fn full_sync_indexes_notes_that_are_not_valid_utf8() {
+        let vault = TestVault::new().with_markdown("Target.md", "# Target").build();
+        let latin1 = vault.path().join("Caf\u{e9}.md");
+        std::fs::write(&latin1, b"Caf\xe9 notes [[Target]]").unwrap();
+        let db = Database::init_memory().await.unwrap();
+        let search_index = TestSearchIndex::new();
+
+        let stats = VaultIndexer::full_sync(&db, search_index.clone(), vault.path().to_str().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(stats.files_indexed, 2);
+        let source = crate::utils::normalize_path(&latin1.to_string_lossy());
+        let target = crate::utils::normalize_path(&vault.path().join("Target.md").to_string_lossy());
+        assert_eq!(db.get_outgoing_links(&source).await.unwrap(), vec![target]);
+    }
+
+    #[tokio::test]
+    async fn full_sync_indexes_links_from_configured_text_extensions() {
+        let vault = TestVault::new()
+            .with_markdown("Note.md", "# Note")
+            .build();
+        std::fs::write(
+            vault.path().join("Board.canvas"),
+            r#"{"nodes":[{"type":"text","text":"See [[Note]]"}]}"#,
+        )
+        .unwrap();
+        std::fs::write(vault.path().join("Photo.png"), "[[Note]]").unwrap();
+        let db_dir = tempdir().unwrap();
+        let db = Database::init(db_dir.path().join("indexer.sqlite").to_str().unwrap())
+            .await
+            .unwrap();
+        let search_index = TestSearchIndex::new();
+        let config = IndexingConfig {
+            link_source_extensions: vec![".Canvas".to_string(), "png".to_string()],
+            ..IndexingConfig::default()
+        };
+
+        VaultIndexer::full_sync_with_config(
+            &db,
+            search_index.clone(),
+            vault.path().to_str().unwrap(),
+            &config,
+            &AtomicBool::new(false),
+        )
+        .await
+        .unwrap();
+
+        let note_path = crate::utils::normalize_path(&vault.path().join("Note.md").to_string_lossy());
+        let canvas_path = crate::utils::normalize_path(&vault.path().join("Board.canvas").to_string_lossy());
+        assert_eq!(db.get_backlinks(&note_path).await.unwrap(), vec![canvas_path]);
+    }
+
+    #[tokio::test]
+    async fn full_sync_tags_frontmatter_links_with_their_field() {
+        let vault = TestVault::new()
+            .with_markdown(
+                "Hub.md",
+                "---\nrelated:\n  - \"[[Alpha]]\"\n  - \"[[Beta]]\"\n---\nSee [[Alpha]] and [[Gamma]]",
+            )
+            .with_markdown("Alpha.md", "# Alpha")
+            .with_markdown("Beta.md", "# Beta")
+            .with_markdown("Gamma.md", "# Gamma")
+            .build();
+        let db_dir = tempdir().unwrap();
+        let db = Database::init(db_dir.path().join("indexer.sqlite").to_str().unwrap())
+            .await
+            .unwrap();
+        let search_index = TestSearchIndex::new();
+
+        VaultIndexer::full_sync(&db, search_index.clone(), vault.path().to_str().unwrap())
+            .await
+            .unwrap();
+
+        let path = |name: &str| crate::utils::normalize_path(&vault.path().join(name).to_string_lossy());
+        assert_eq!(
+            db.get_frontmatter_links(&path("Hub.md")).await.unwrap(),
+            vec![
+                (path("Alpha.md"), "related".to_string()),
+                (path("Beta.md"), "related".to_string()),
+            ]
+        );
+        let mut outgoing = db.get_outgoing_links(&path("Hub.md")).await.unwrap();
+        outgoing.sort();
+        assert_eq!(outgoing, vec![path("Alpha.md"), path("Beta.md"), path("Gamma.md")]);
+    }
+
+    #[tokio::test]
+    async fn full_sync_clamps_out_of_range_indexing_config() {
+        let vault = TestVault::new()
+            .with_markdown("Alpha.md", "[[Beta]]")
+            .with_markdown("Beta.md", "[[Gamma]]")
+            .with_markdown("Gamma.md", "# Gamma")
+            .build();
+        let db_dir = tempdir().unwrap();
+        let db = Database::init(db_dir.path().join("indexer.sqlite").to_str().unwrap())
+            .await
+            .unwrap();
+        let search_index = TestSearchIndex::new();
+        let config = IndexingConfig {
+            index_concurrency: 0,
+            index_batch_size: usize::MAX,
+        };
+        assert_eq!(config.concurrency(), 1);
+        assert_eq!(config.batch_size(), IndexingConfig::BATCH_SIZE_RANGE.1);
+
+        let stats = VaultIndexer::full_sync_with_config(
+            &db,
+            search_index.clone(),
+            vault.path().to_str().unwrap(),
+            &config,
+            &AtomicBool::new(false),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(stats.files_indexed, 3);
+        assert_eq!(db.get_all_links().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn parallel_full_sync_still_skips_unmodified_notes() {
+        let mut builder = TestVault::new();
+        for i in 0..12 {
+            builder = builder.with_markdown(&format!("Note{i}.md"), &format!("[[Note{}]]", (i + 1) % 12));
+        }
+        let vault = builder.build();
+        let db_dir = tempdir().unwrap();
+        let db = Database::init(db_dir.path().join("indexer.sqlite").to_str().unwrap())
+            .await
+            .unwrap();
+        let search_index = TestSearchIndex::new();
+        let config = IndexingConfig {
+            index_concurrency: 4,
+            ..IndexingConfig::default()
+        };
+        let sync = || {
+            VaultIndexer::full_sync_with_config(
+                &db,
+                search_index.clone(),
+                vault.path().to_str().unwrap(),
+                &config,
+                &AtomicBool::new(false),
+            )
+        };
+
+        let first = sync().await.unwrap();
+        assert_eq!(first.files_indexed, 12);
+        assert_eq!(db.get_all_links().await.unwrap().len(), 12);
+
+        let second = sync().await.unwrap();
+        assert_eq!(second.files_indexed, 0);
+        assert_eq!(second.files_skipped, 12);
+
+        std::fs::write(vault.path().join("Note3.md"), "no links anymore").unwrap();
+        let third = sync().await.unwrap();
+        assert_eq!(third.files_indexed, 1);
+        assert_eq!(db.get_all_links().await.unwrap().len(), 11);
+    }
+
+    #[tokio::test]
+    async fn full_sync_indexes_names_that_only_contain_git_or_trash() {
+        let vault = TestVault::new()
+            .with_markdown("my.github-notes.md", "[[Plan]]")
+            .with_markdown("project.trashcan/Plan.md", "# Plan")
+            .with_markdown(".trash/Deleted.md", "# Deleted")
+            .build();
+        let db = Database::init_memory().await.unwrap();
+        let search_index = TestSearchIndex::new();
+
+        let stats = VaultIndexer::full_sync(&db, search_index.clone(), vault.path().to_str().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(stats.files_indexed, 2);
+        let plan = crate::utils::normalize_path(&vault.path().join("project.trashcan/Plan.md").to_string_lossy());
+        let notes = crate::utils::normalize_path(&vault.path().join("my.github-notes.md").to_string_lossy());
+        assert_eq!(db.get_backlinks(&plan).await.unwrap(), vec![notes]);
+    }
+
+    #[tokio::test]
+    async fn full_sync_stops_cleanly_when_cancelled() {
+        let vault = TestVault::new()
+            .with_markdown("Alpha.md", "[[Beta]]")
+            .with_markdown("Beta.md", "# Beta")
+            .build();
+        let db_dir = tempdir().unwrap();
+        let db = Database::init(db_dir.path().join("indexer.sqlite").to_str().unwrap())
+            .await
+            .unwrap();
+        let search_index = TestSearchIndex::new();
+
+        let cancelled = VaultIndexer::full_sync_with_config(
+            &db,
+            search_index.clone(),
+            vault.path().to_str().unwrap(),
+            &IndexingConfig::default(),
+            &AtomicBool::new(true),
+        )
+        .await
+        .unwrap();
+        assert!(cancelled.cancelled);
+        assert_eq!(cancelled.files_indexed, 0);
+        assert!(db.get_all_indexed_files().await.unwrap().is_empty());
+
+        let resumed = VaultIndexer::full_sync(&db, search_index.clone(), vault.path().to_str().unwrap())
+            .await
+            .unwrap();
+        assert!(!resumed.cancelled);
+        assert_eq!(resumed.files_indexed, 2);
+    }
+
+    #[tokio::test]
+    async fn index_path_picks_up_external_edits_and_deletions() {
+        let vault = TestVault::new()
+            .with_markdown("Alpha.md", "# Alpha")
+            .with_markdown("Beta.md", "# Beta")
+            .build();
+        let vault_path = vault.path().to_str().unwrap();
+        let db = Database::init_memory().await.unwrap();
+        let search_index = TestSearchIndex::new();
+        VaultIndexer::full_sync(&db, search_index.clone(), vault_path).await.unwrap();
+        let alpha = vault.path().join("Alpha.md");
+        let beta = vault.path().join("Beta.md");
+        let alpha_key = crate::utils::normalize_path(&alpha.to_string_lossy());
+        let beta_key = crate::utils::normalize_path(&beta.to_string_lossy());
+        let file_index = crate::models::FileIndex::build(vault_path).unwrap();
+        let asset_index = crate::models::AssetIndex::build(vault_path).unwrap();
+
+        std::fs::write(&alpha, "# Alpha\nNow links [[Beta]]").unwrap();
+        assert!(VaultIndexer::index_path(&db, &search_index, vault_path, &alpha, &file_index, &asset_index)
+            .await
+            .unwrap());
+        assert_eq!(db.get_backlinks(&beta_key).await.unwrap(), vec![alpha_key.clone()]);
+
+        std::fs::remove_file(&alpha).unwrap();
+        assert!(VaultIndexer::index_path(&db, &search_index, vault_path, &alpha, &file_index, &asset_index)
+            .await
+            .unwrap());
+        let indexed = db.get_all_indexed_files().await.unwrap();
+        assert_eq!(indexed.len(), 1);
+        assert_eq!(indexed[0].0, beta_key);
+
+        let image = vault.path().join("image.png");
+        assert!(!VaultIndexer::index_path(&db, &search_index, vault_path, &image, &file_index, &asset_index)
+            .await
+            .unwrap());
+    }
 }
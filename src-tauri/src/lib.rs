@@ -3,6 +3,7 @@ mod db;
 pub mod error;
 mod indexer;
 mod grafeo_projection;
+mod logging;
 mod search;
 mod sync;
 mod trash;
@@ -19,6 +20,7 @@ pub use models::*;
 pub use db::Database;
 pub use indexer::{IndexStats, VaultIndexer};
 pub use search::{SearchDoc, SearchFields, SearchIndex};
+pub use utils::config::IndexingConfig;
 pub use test_support::{TestVault, TestVaultBuilder};
 
 fn startup_error(stage: &str, message: impl Into<String>) -> std::io::Error {
@@ -44,6 +46,7 @@ fn append_startup_log(message: &str) {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    logging::init();
     tauri::Builder::default()
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_opener::init())
@@ -144,25 +147,38 @@ pub fn run() {
             if let Err(err) = &setup_result {
                 let message = format!("{err}");
                 append_startup_log(&message);
-                eprintln!("{message}");
+                log::error!("{message}");
             }
 
             setup_result.map_err(|e| e.into())
         })
         .invoke_handler(tauri::generate_handler![
             commands::notes::create_note,
+            commands::notes::create_note_in,
+            commands::notes::open_note,
+            commands::notes::append_to_note,
+            commands::notes::duplicate_note,
             commands::notes::get_or_create_daily_note,
             commands::notes::trash_item,
             commands::notes::trash_items,
             commands::notes::list_trash_items,
             commands::notes::restore_trash_item,
             commands::notes::delete_trash_item_permanently,
+            commands::notes::empty_trash,
+            commands::notes::empty_trash_older_than,
             commands::notes::read_file,
+            commands::notes::read_file_lossy,
             commands::notes::write_file,
+            commands::notes::write_file_fast,
+            commands::notes::write_file_with_links,
             commands::notes::search_notes,
             commands::templates::list_templates,
             commands::templates::create_note_from_template,
+            commands::settings::load_settings,
+            commands::settings::save_settings,
+            commands::settings::set_log_level,
             commands::vault::list_files,
+            commands::vault::find_paths,
             commands::vault::list_files_tree,
             commands::vault::list_vault_snapshot,
             commands::vault::ensure_feature_demo_in_empty_vault,
@@ -171,26 +187,60 @@ pub fn run() {
             commands::watcher::watch_vault,
             commands::watcher::unwatch_vault,
             commands::vault::rename_file,
+            commands::vault::preview_rename_link_updates,
+            commands::vault::rename_to_heading,
+            commands::vault::move_item,
             commands::vault::move_items,
             commands::folders::create_folder,
+            commands::folders::get_folder_size,
             commands::links::get_backlinks,
+            commands::links::get_backlinks_with_context,
             commands::links::get_outgoing_links,
+            commands::links::get_outgoing_links_detailed,
+            commands::links::get_links_status,
+            commands::links::suggest_links,
+            commands::links::get_unlinked_mentions,
+            commands::links::link_mention,
             commands::links::get_all_links,
+            commands::links::get_related_notes,
+            commands::links::get_most_linked,
+            commands::links::get_vault_health,
+            commands::links::find_duplicate_names,
             commands::links::resolve_wikilink,
             commands::assets::resolve_asset,
             commands::assets::save_asset,
+            commands::assets::save_attachment,
             commands::notes::get_all_notes,
             commands::notes::get_all_tags,
+            commands::notes::get_tag_counts,
             commands::notes::get_file_tags,
+            commands::notes::get_notes_by_tag,
+            commands::notes::get_tag_hierarchy,
+            commands::notes::rename_tag,
             commands::notes::get_all_property_keys,
+            commands::notes::get_notes_modified_between,
+            commands::notes::get_note_metadata,
+            commands::notes::get_note_stats,
+            commands::notes::get_vault_stats,
+            commands::notes::record_open,
+            commands::notes::get_recent_files,
             commands::pdf_export::export_markdown_pdf,
             commands::indexer::sync_vault,
+            commands::indexer::cancel_sync,
+            commands::indexer::index_file_command,
             commands::indexer::get_index_status,
+            commands::indexer::vacuum_index,
+            commands::indexer::rebuild_index,
             commands::dataview::execute_dataview_query,
             commands::graph::get_graph_data,
+            commands::graph::get_graph_data_filtered,
+            commands::graph::get_local_graph,
+            commands::graph::find_cycles,
+            commands::graph::path_between,
             commands::graph::execute_graph_query,
             commands::vault::set_vault_path,
             commands::search::search_full_text,
+            commands::search::search_note_contents,
             commands::search::search_tags,
             commands::search::rebuild_search_index,
             commands::search::ensure_search_ready,
@@ -213,8 +263,15 @@ pub fn run() {
             commands::history::get_note_snapshot,
             commands::history::pin_snapshot,
             commands::history::unpin_snapshot,
+            commands::history::restore_note_snapshot,
+            commands::history::list_versions,
+            commands::history::restore_version,
             commands::publish::publish_vault,
+            commands::render::render_markdown,
             commands::export::export_note_docx,
+            commands::export::export_note_html,
+            commands::export::export_vault_zip,
+            commands::export::import_folder,
             commands::export::import_from_url,
             commands::semantic::semantic_search,
             commands::semantic::get_link_suggestions,
@@ -236,7 +293,6 @@ pub fn run() {
             let message = format!("error while running tauri application: {}", e);
             log::error!("{message}");
             append_startup_log(&message);
-            eprintln!("{message}");
         });
 }
 
@@ -247,7 +303,7 @@ mod tests {
     use tempfile::tempdir;
     
     use commands::extract_wikilinks;
-    use models::{AssetIndex, FileIndex};
+    use models::{AssetIndex, FileIndex, LinkStrategy};
     
     #[test]
     fn test_extract_wikilinks() {
@@ -295,6 +351,146 @@ mod tests {
         assert!(resolved.unwrap().to_string_lossy().contains("subfolder"));
     }
 
+    #[test]
+    fn test_file_index_breaks_equal_depth_ties_by_path() {
+        let vault_path = "/vault";
+        // Insertion order stands in for walk order; both orders must agree.
+        let forward = FileIndex::from_markdown_paths(["/vault/b/Plan.md", "/vault/a/Plan.md"]);
+        let backward = FileIndex::from_markdown_paths(["/vault/a/Plan.md", "/vault/b/Plan.md"]);
+        
+        for index in [forward, backward] {
+            assert_eq!(
+                index.resolve(vault_path, "Plan"),
+                Some(std::path::PathBuf::from("/vault/a/Plan.md"))
+            );
+        }
+    }
+
+    #[test]
+    fn test_file_index_reports_duplicate_names() {
+        let index = FileIndex::from_markdown_paths([
+            "/vault/b/Plan.md",
+            "/vault/Plan.md",
+            "/vault/a/Plan.md",
+            "/vault/Solo.md",
+        ]);
+        
+        assert_eq!(
+            index.duplicate_names(),
+            vec![(
+                "Plan".to_string(),
+                vec![
+                    std::path::PathBuf::from("/vault/Plan.md"),
+                    std::path::PathBuf::from("/vault/a/Plan.md"),
+                    std::path::PathBuf::from("/vault/b/Plan.md"),
+                ],
+            )]
+        );
+    }
+
+    #[test]
+    fn test_file_index_insert_and_remove() {
+        let vault_path = "/vault";
+        let mut index = FileIndex::from_markdown_paths(["/vault/Note1.md"]);
+        
+        index.insert("/vault/deep/Note2.md");
+        index.insert("/vault/deep/Note2.md");
+        assert_eq!(
+            index.resolve(vault_path, "Note2"),
+            Some(std::path::PathBuf::from("/vault/deep/Note2.md"))
+        );
+        
+        index.remove("/vault/deep/Note2.md");
+        assert_eq!(index.resolve(vault_path, "Note2"), None);
+        assert_eq!(index.resolve(vault_path, "Note2.md"), None);
+        assert!(index.resolve(vault_path, "Note1").is_some());
+    }
+
+    #[test]
+    fn test_file_index_resolves_frontmatter_aliases() {
+        let dir = tempdir().unwrap();
+        let vault_path = dir.path().to_str().unwrap();
+        
+        fs::write(
+            dir.path().join("User Authentication.md"),
+            "---\naliases: [Auth, Login]\n---\n# Auth\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("Login.md"), "# Login page").unwrap();
+        
+        let mut index = FileIndex::build(vault_path).unwrap();
+        // Building never reads note contents; aliases are registered separately.
+        assert_eq!(index.resolve(vault_path, "Auth"), None);
+        
+        index.set_aliases(
+            dir.path().join("User Authentication.md"),
+            &["Auth".to_string(), "Login".to_string()],
+        );
+        assert_eq!(
+            index.resolve(vault_path, "Auth"),
+            Some(dir.path().join("User Authentication.md"))
+        );
+        // Aliases match case-insensitively, like `note_aliases` lookups.
+        assert_eq!(
+            index.resolve(vault_path, "auth"),
+            Some(dir.path().join("User Authentication.md"))
+        );
+        // A real filename wins over an alias.
+        assert_eq!(index.resolve(vault_path, "Login"), Some(dir.path().join("Login.md")));
+        
+        index.rename(dir.path().join("User Authentication.md"), dir.path().join("Auth Flow.md"));
+        assert_eq!(index.resolve(vault_path, "Auth"), Some(dir.path().join("Auth Flow.md")));
+        
+        index.set_aliases(dir.path().join("Auth Flow.md"), &[]);
+        assert_eq!(index.resolve(vault_path, "Auth"), None);
+    }
+
+    #[test]
+    fn test_file_index_link_strategies() {
+        let dir = tempdir().unwrap();
+        let vault_path = dir.path().to_str().unwrap();
+        fs::create_dir_all(dir.path().join("Projects/Web")).unwrap();
+        fs::write(dir.path().join("Plan.md"), "# Root plan").unwrap();
+        fs::write(dir.path().join("Projects/Plan.md"), "# Projects plan").unwrap();
+        fs::write(dir.path().join("Projects/Web/Spec.md"), "# Spec").unwrap();
+        let source = dir.path().join("Projects/Web/Source.md");
+        let index = FileIndex::build(vault_path).unwrap();
+        let resolve = |target: &str, strategy: LinkStrategy| {
+            index.resolve_with(vault_path, target, &source, strategy)
+        };
+
+        assert_eq!(resolve("Plan", LinkStrategy::ShortestPath), Some(dir.path().join("Plan.md")));
+        assert_eq!(resolve("Spec", LinkStrategy::ShortestPath), Some(dir.path().join("Projects/Web/Spec.md")));
+
+        assert_eq!(resolve("Spec", LinkStrategy::AbsolutePath), None);
+        assert_eq!(
+            resolve("Projects/Web/Spec", LinkStrategy::AbsolutePath),
+            Some(dir.path().join("Projects/Web/Spec.md"))
+        );
+
+        assert_eq!(
+            resolve("../Plan", LinkStrategy::RelativeToSource),
+            Some(dir.path().join("Projects/Plan.md"))
+        );
+        // Falls back to the shortest-path rules when nothing sits next to the source.
+        assert_eq!(resolve("Plan", LinkStrategy::RelativeToSource), Some(dir.path().join("Plan.md")));
+        assert_eq!(
+            index.resolve_or_default_with(vault_path, "Missing", &source, LinkStrategy::AbsolutePath),
+            dir.path().join("Missing.md")
+        );
+
+        // Only indexed notes resolve, so ignored files on disk stay hidden.
+        let partial = FileIndex::from_markdown_paths([dir.path().join("Plan.md")]);
+        assert_eq!(
+            partial.resolve_with(vault_path, "Projects/Web/Spec", &source, LinkStrategy::AbsolutePath),
+            None
+        );
+        assert_eq!(
+            partial.resolve_with(vault_path, "../Plan", &source, LinkStrategy::RelativeToSource),
+            None
+        );
+    }
+
     #[test]
     fn test_asset_index_resolution() {
         let dir = tempdir().unwrap();
@@ -0,0 +1,78 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// Environment variable holding the log verbosity: `off`, `error`, `warn`,
+/// `info`, `debug` or `trace`.
+pub const LOG_LEVEL_ENV: &str = "TESSELLUM_LOG";
+
+/// Dependencies such as sqlx log every query at `info`, so records from other
+/// crates are only shown from this level up, whatever the verbosity.
+const DEPENDENCY_LEVEL: Level = Level::Warn;
+
+/// Writes log records to stderr, filtered by the verbosity set at startup or
+/// through `set_level`.
+struct StderrLogger;
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// Whether a record from `target` at `level` passes the verbosity `max`.
+fn allows(target: &str, level: Level, max: LevelFilter) -> bool {
+    if level > max {
+        return false;
+    }
+    target.starts_with("tessellum") || level <= DEPENDENCY_LEVEL
+}
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        allows(metadata.target(), metadata.level(), log::max_level())
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Verbosity used when `TESSELLUM_LOG` is unset or invalid: warnings only in
+/// release builds, debug output in development.
+fn default_level() -> LevelFilter {
+    if cfg!(debug_assertions) {
+        LevelFilter::Debug
+    } else {
+        LevelFilter::Warn
+    }
+}
+
+/// Install the logger. Calling it again only re-reads the verbosity.
+pub fn init() {
+    let level = std::env::var(LOG_LEVEL_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or_else(default_level);
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(level);
+}
+
+/// Change the verbosity while the app is running.
+pub fn set_level(level: LevelFilter) {
+    log::set_max_level(level);
+}
+
+#[cfg(test)]
+mod tests {
+    use log::{Level, LevelFilter};
+
+    use super::allows;
+
+    #[test]
+    fn filters_own_records_by_verbosity_and_dependencies_to_warnings() {
+        assert!(allows("tessellum_lib::db", Level::Debug, LevelFilter::Debug));
+        assert!(!allows("tessellum_lib::db", Level::Debug, LevelFilter::Warn));
+        assert!(!allows("sqlx::query", Level::Info, LevelFilter::Trace));
+        assert!(allows("sqlx::query", Level::Warn, LevelFilter::Trace));
+        assert!(!allows("sqlx::query", Level::Error, LevelFilter::Off));
+    }
+}
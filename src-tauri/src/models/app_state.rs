@@ -1,9 +1,12 @@
 use notify::RecommendedWatcher;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 
 use crate::db::Database;
+use crate::error::TessellumError;
 use crate::models::{AssetIndex, FileIndex};
 use crate::search::SearchIndex;
 
@@ -36,14 +39,98 @@ impl Default for SearchReadinessState {
     }
 }
 
+/// Notes saved through `write_file_fast` whose index entries are not yet up to date.
+///
+/// Dirty notes are grouped by vault, since several vaults can be open and
+/// watched at once. Every save bumps `generation`; the background flush only
+/// runs once the generation has stayed unchanged for a full debounce window.
+#[derive(Debug, Default)]
+pub struct PendingIndexState {
+    pub dirty: HashMap<String, HashSet<String>>,
+    pub generation: u64,
+    pub flush_scheduled: bool,
+}
+
+impl PendingIndexState {
+    /// Record a saved note. Returns `true` when the caller must schedule a flush.
+    pub fn mark_dirty(&mut self, vault_path: &str, path: &str) -> bool {
+        self.dirty
+            .entry(vault_path.to_string())
+            .or_default()
+            .insert(path.to_string());
+        self.generation = self.generation.wrapping_add(1);
+        if self.flush_scheduled {
+            return false;
+        }
+        self.flush_scheduled = true;
+        true
+    }
+
+    /// Drop a note that was indexed some other way, e.g. by an explicit save.
+    pub fn forget(&mut self, vault_path: &str, path: &str) {
+        if let Some(paths) = self.dirty.get_mut(vault_path) {
+            paths.remove(path);
+            if paths.is_empty() {
+                self.dirty.remove(vault_path);
+            }
+        }
+    }
+
+    /// Drain the dirty sets if no save happened since `seen_generation`, as
+    /// (vault_path, paths) batches sorted by vault and path.
+    ///
+    /// Returns `None` while saves are still arriving, leaving the flush scheduled.
+    pub fn take_settled(&mut self, seen_generation: u64) -> Option<Vec<(String, Vec<String>)>> {
+        if self.generation != seen_generation {
+            return None;
+        }
+        self.flush_scheduled = false;
+        let mut batches: Vec<(String, Vec<String>)> = self
+            .dirty
+            .drain()
+            .map(|(vault_path, paths)| {
+                let mut paths: Vec<String> = paths.into_iter().collect();
+                paths.sort();
+                (vault_path, paths)
+            })
+            .collect();
+        batches.sort();
+        Some(batches)
+    }
+}
+
+/// How long a self-write keeps suppressing watcher events for its path.
+pub const EXPECTED_CHANGE_WINDOW: Duration = Duration::from_secs(2);
+
+/// Paths the app has just written itself, so the watcher can ignore the echo.
+///
+/// Entries are not consumed on match because one save produces several
+/// filesystem events; they simply expire after `EXPECTED_CHANGE_WINDOW`.
+#[derive(Debug, Default)]
+pub struct ExpectedChanges {
+    entries: HashMap<String, Instant>,
+}
+
+impl ExpectedChanges {
+    pub fn expect(&mut self, path: &str, now: Instant) {
+        self.entries
+            .insert(crate::utils::normalize_path(path), now + EXPECTED_CHANGE_WINDOW);
+    }
+
+    /// Whether `path` was written by the app within the window. Drops expired entries.
+    pub fn is_expected(&mut self, path: &str, now: Instant) -> bool {
+        self.entries.retain(|_, expires_at| *expires_at > now);
+        self.entries.contains_key(&crate::utils::normalize_path(path))
+    }
+}
+
 /// Represents the application state that contains shared resources such as
 /// a file watcher and a database connection.
 ///
 /// # Fields
 ///
-/// * `watcher` - A thread-safe, optional wrapper around a `RecommendedWatcher` instance.
-///   This watcher is typically used for monitoring file system events.
-///   It is wrapped in a `Mutex` to ensure safe concurrent access across threads.
+/// * `watchers` - One `RecommendedWatcher` per watched vault, keyed by the
+///   normalized vault path. Dropping an entry stops watching that vault.
 ///
 /// * `db` - A thread-safe, optional shared reference to a `Database` instance.
 ///   The `Database` is wrapped in an `Arc` for shared ownership across threads.
@@ -51,8 +138,10 @@ impl Default for SearchReadinessState {
 ///
 /// * `file_index` - Cached FileIndex to resolve links quickly without traversing the FS.
 /// * `asset_index` - Cached AssetIndex for media embeds.
+/// * `pending_index` - Dirty set drained by the debounced autosave indexer.
+/// * `expected_changes` - Recent self-writes the watcher should not report.
 pub struct AppState {
-    pub watcher: tokio::sync::Mutex<Option<RecommendedWatcher>>,
+    pub watchers: tokio::sync::Mutex<HashMap<String, RecommendedWatcher>>,
     pub db: Arc<Database>,
     pub file_index: Arc<Mutex<Option<FileIndex>>>,
     pub asset_index: Arc<Mutex<Option<AssetIndex>>>,
@@ -61,18 +150,52 @@ pub struct AppState {
     /// Guard against concurrent full_sync calls: the filesystem-watcher may
     /// trigger a second sync while a manual rebuild is already running.
     pub sync_in_progress: Arc<AtomicBool>,
+    /// Set by `cancel_sync`; the running full_sync stops at the next file or batch boundary.
+    pub sync_cancel_requested: Arc<AtomicBool>,
+    pub pending_index: Mutex<PendingIndexState>,
+    /// Locked from the synchronous notify callback, hence a std mutex.
+    pub expected_changes: Arc<std::sync::Mutex<ExpectedChanges>>,
 }
 
 impl AppState {
     pub fn new(db: Database, search_index: SearchIndex) -> Self {
         Self {
             db: Arc::new(db),
-            watcher: tokio::sync::Mutex::new(None),
+            watchers: tokio::sync::Mutex::new(HashMap::new()),
             file_index: Arc::new(Mutex::new(None)),
             asset_index: Arc::new(Mutex::new(None)),
             search_index: Arc::new(Mutex::new(search_index)),
             search_readiness: Mutex::new(SearchReadinessState::default()),
             sync_in_progress: Arc::new(AtomicBool::new(false)),
+            sync_cancel_requested: Arc::new(AtomicBool::new(false)),
+            pending_index: Mutex::new(PendingIndexState::default()),
+            expected_changes: Arc::new(std::sync::Mutex::new(ExpectedChanges::default())),
+        }
+    }
+
+    /// The cached link-resolution index, built from `vault_path` on first use.
+    /// Aliases come from `note_aliases`, so no note is read to build it.
+    pub async fn current_file_index(&self, vault_path: &str) -> Result<FileIndex, TessellumError> {
+        let mut guard = self.file_index.lock().await;
+        if let Some(index) = guard.as_ref() {
+            return Ok(index.clone());
+        }
+        let mut index = FileIndex::build(vault_path)
+            .map_err(|e| TessellumError::Internal(format!("Failed to build file index: {e}")))?;
+        index.load_aliases(self.db.get_all_note_aliases().await?);
+        *guard = Some(index.clone());
+        Ok(index)
+    }
+
+    /// The cached attachment index, built from `vault_path` on first use.
+    pub async fn current_asset_index(&self, vault_path: &str) -> Result<AssetIndex, TessellumError> {
+        let mut guard = self.asset_index.lock().await;
+        if let Some(index) = guard.as_ref() {
+            return Ok(index.clone());
         }
+        let index = AssetIndex::build(vault_path)
+            .map_err(|e| TessellumError::Internal(format!("Failed to build asset index: {e}")))?;
+        *guard = Some(index.clone());
+        Ok(index)
     }
 }
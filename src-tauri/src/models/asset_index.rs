@@ -3,7 +3,7 @@ use std::fs::canonicalize;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
-use crate::utils::is_hidden_or_special;
+use crate::utils::IgnoreRules;
 
 const SUPPORTED_EXTS: &[&str] = &[
 	"png", "jpg", "jpeg", "gif", "webp", "svg", "bmp", "tif", "tiff", "avif", "pdf",
@@ -16,48 +16,82 @@ pub struct AssetIndex {
 
 impl AssetIndex {
 	pub fn build(vault_path: &str) -> Result<Self, String> {
-		let mut name_to_paths: HashMap<String, Vec<PathBuf>> = HashMap::new();
-		
 		if !Path::new(vault_path).exists() {
 			return Err("Vault path does not exist".to_string());
 		}
 		
-		for entry in WalkDir::new(vault_path).into_iter().filter_map(|e| e.ok()) {
-			let path = entry.path();
-			let rel_path = path.strip_prefix(vault_path).unwrap_or(path);
-			if is_hidden_or_special(rel_path) {
-				continue;
-			}
-			
-			if path.is_file() {
-				let ext = path
-					.extension()
-					.and_then(|s| s.to_str())
-					.unwrap_or("")
-					.to_lowercase();
-				if !SUPPORTED_EXTS.contains(&ext.as_str()) {
-					continue;
-				}
-				
-				if let Some(filename) = path.file_name() {
-					let filename_str = filename.to_string_lossy().to_string();
-					name_to_paths
-						.entry(filename_str.clone())
-						.or_default()
-						.push(path.to_path_buf());
-					
-					if let Some(stem) = path.file_stem() {
-						let stem_str = stem.to_string_lossy().to_string();
-						name_to_paths
-							.entry(stem_str)
-							.or_default()
-							.push(path.to_path_buf());
-					}
-				}
-			}
+		let ignore = IgnoreRules::load(vault_path);
+		let paths = WalkDir::new(vault_path)
+			.into_iter()
+			.filter_entry(|e| ignore.keeps(vault_path, e))
+			.filter_map(|e| e.ok())
+			.map(|entry| entry.into_path())
+			.filter(|path| path.is_file());
+		
+		Ok(Self::from_paths(paths))
+	}
+	
+	/// Build an index from an existing file collection, keeping supported attachments.
+	pub fn from_paths<I, P>(paths: I) -> Self
+	where
+		I: IntoIterator<Item = P>,
+		P: Into<PathBuf>,
+	{
+		let mut index = Self { name_to_paths: HashMap::new() };
+		for path in paths {
+			index.insert(path);
+		}
+		index
+	}
+	
+	/// Add an attachment, keyed by filename and stem. Unsupported files are skipped.
+	pub fn insert(&mut self, path: impl Into<PathBuf>) {
+		let path = path.into();
+		let ext = path
+			.extension()
+			.and_then(|s| s.to_str())
+			.unwrap_or("")
+			.to_lowercase();
+		if !SUPPORTED_EXTS.contains(&ext.as_str()) {
+			return;
 		}
 		
-		Ok(Self { name_to_paths })
+		let Some(filename) = path.file_name() else {
+			return;
+		};
+		let mut keys = vec![filename.to_string_lossy().to_string()];
+		if let Some(stem) = path.file_stem() {
+			keys.push(stem.to_string_lossy().to_string());
+		}
+		for key in keys {
+			let paths = self.name_to_paths.entry(key).or_default();
+			if !paths.contains(&path) {
+				paths.push(path.clone());
+			}
+		}
+	}
+	
+	/// Drop an attachment. Separator style is ignored when matching.
+	pub fn remove(&mut self, path: impl AsRef<Path>) {
+		let target = crate::utils::normalize_path(&path.as_ref().to_string_lossy());
+		self.retain(|p| p != target);
+	}
+	
+	/// Drop every attachment inside the folder `dir`.
+	pub fn remove_under(&mut self, dir: impl AsRef<Path>) {
+		let prefix = format!(
+			"{}/",
+			crate::utils::normalize_path(&dir.as_ref().to_string_lossy()).trim_end_matches('/')
+		);
+		self.retain(|p| !p.starts_with(&prefix));
+	}
+	
+	/// Keep only paths whose normalized form passes `keep`.
+	fn retain(&mut self, keep: impl Fn(&str) -> bool) {
+		self.name_to_paths.retain(|_, paths| {
+			paths.retain(|p| keep(&crate::utils::normalize_path(&p.to_string_lossy())));
+			!paths.is_empty()
+		});
 	}
 	
 	pub fn resolve(&self, vault_path: &str, link_target: &str) -> Option<PathBuf> {
@@ -0,0 +1,66 @@
+use notify::event::{CreateKind, ModifyKind, RemoveKind};
+use notify::{Event, EventKind};
+use serde::{Deserialize, Serialize};
+
+/// What happened to the paths in a `FileChangeEvent`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FileChangeKind {
+    Create,
+    Modify,
+    Remove,
+    Rename,
+}
+
+/// One change reported in a `file-changed` event.
+///
+/// # Fields
+///
+/// * `kind` - The kind of change.
+/// * `paths` - The affected paths. For renames this is `[from, to]` when the
+///   platform reports both sides, otherwise a single path.
+/// * `is_dir` - Whether the change concerns a directory.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FileChangeEvent {
+    pub kind: FileChangeKind,
+    pub paths: Vec<String>,
+    pub is_dir: bool,
+}
+
+/// Payload of one `file-changed` event: a debounced burst from one vault.
+///
+/// `vault_path` is the path `watch_vault` was called with, so listeners can
+/// ignore vaults other than the one they show.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VaultFileChanges {
+    pub vault_path: String,
+    pub changes: Vec<FileChangeEvent>,
+}
+
+impl FileChangeEvent {
+    /// Map a raw notify event onto a frontend change event.
+    ///
+    /// Access and other metadata-free events return `None`.
+    pub fn from_notify(event: &Event) -> Option<Self> {
+        let (kind, folder_hint) = match event.kind {
+            EventKind::Create(create) => (FileChangeKind::Create, create == CreateKind::Folder),
+            EventKind::Modify(ModifyKind::Name(_)) => (FileChangeKind::Rename, false),
+            EventKind::Modify(_) | EventKind::Any => (FileChangeKind::Modify, false),
+            EventKind::Remove(remove) => (FileChangeKind::Remove, remove == RemoveKind::Folder),
+            EventKind::Access(_) | EventKind::Other => return None,
+        };
+        if event.paths.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            kind,
+            paths: event
+                .paths
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect(),
+            is_dir: folder_hint || event.paths.iter().any(|path| path.is_dir()),
+        })
+    }
+}
@@ -1,14 +1,78 @@
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use serde::{Deserialize, Serialize};
 use walkdir::WalkDir;
 
-use crate::utils::is_hidden_or_special;
+use super::wikilink::split_anchor;
+use crate::utils::IgnoreRules;
 
 /// In-memory index of files in the vault for fast wikilink resolution.
 #[derive(Debug, Clone)]
 pub struct FileIndex {
     /// Map: filename -> Vec<full_path>
     name_to_paths: HashMap<String, Vec<PathBuf>>,
+    /// Map: lowercased frontmatter alias -> Vec<full_path>. Only consulted
+    /// when no file has that name, so a real filename always wins over an
+    /// alias. Lowercased to match the `NOCASE` lookups on `note_aliases`.
+    alias_to_paths: HashMap<String, Vec<PathBuf>>,
+    /// Map: normalized path -> (full_path, aliases as written), for cheap
+    /// removal and for offering aliases in their original case.
+    path_to_aliases: HashMap<String, (PathBuf, Vec<String>)>,
+}
+
+/// The candidate with the fewest path components below `vault_root`, ties
+/// broken by the normalized relative path so resolution is reproducible.
+fn closest_to_root<'a>(
+    candidates: impl Iterator<Item = &'a PathBuf>,
+    vault_root: &Path,
+) -> Option<&'a PathBuf> {
+    candidates.min_by_key(|p| match p.strip_prefix(vault_root) {
+        Ok(rel) => (
+            rel.components().count(),
+            crate::utils::normalize_path(&rel.to_string_lossy()),
+        ),
+        Err(_) => (usize::MAX, crate::utils::normalize_path(&p.to_string_lossy())),
+    })
+}
+
+/// How link targets are matched to notes, set by `link_resolution` in the
+/// vault config.
+///
+/// * `ShortestPath` - Obsidian's rules, see [`FileIndex::resolve`]. The default.
+/// * `AbsolutePath` - The target is a path from the vault root, so `Note` only
+///   matches a root-level `Note.md`. Frontmatter aliases still apply.
+/// * `RelativeToSource` - The target is first tried as a path from the linking
+///   note's folder (`../` allowed), then by the shortest-path rules.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkStrategy {
+    #[default]
+    ShortestPath,
+    AbsolutePath,
+    RelativeToSource,
+}
+
+/// The note path `target` names below `base`, with `.md` added when missing.
+///
+/// `.` and `..` are applied lexically; paths leaving the vault yield `None`.
+fn note_path_at(base: &Path, vault_root: &Path, target: &str) -> Option<PathBuf> {
+    let mut path = base.to_path_buf();
+    for component in Path::new(target).components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::ParentDir => {
+                path.pop();
+            }
+            Component::CurDir => {}
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    if path.extension().is_none_or(|ext| ext != "md") {
+        let mut with_ext = path.into_os_string();
+        with_ext.push(".md");
+        path = PathBuf::from(with_ext);
+    }
+    path.starts_with(vault_root).then_some(path)
 }
 
 impl FileIndex {
@@ -18,16 +82,13 @@ impl FileIndex {
             return Err("Vault path does not exist".to_string());
         }
 
+        let ignore = IgnoreRules::load(vault_path);
         let markdown_paths = WalkDir::new(vault_path)
             .into_iter()
+            .filter_entry(|e| ignore.keeps(vault_path, e))
             .filter_map(|e| e.ok())
             .map(|entry| entry.into_path())
-            .filter(|path| {
-                let rel_path = path.strip_prefix(vault_path).unwrap_or(path);
-                !is_hidden_or_special(rel_path)
-                    && path.is_file()
-                    && path.extension().and_then(|s| s.to_str()) == Some("md")
-            })
+            .filter(|path| path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md"))
             .collect::<Vec<_>>();
 
         Ok(Self::from_markdown_paths(markdown_paths))
@@ -39,34 +100,202 @@ impl FileIndex {
         I: IntoIterator<Item = P>,
         P: Into<PathBuf>,
     {
-        let mut name_to_paths: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        let mut index = Self {
+            name_to_paths: HashMap::new(),
+            alias_to_paths: HashMap::new(),
+            path_to_aliases: HashMap::new(),
+        };
+        for path in paths {
+            index.insert(path);
+        }
+        index
+    }
 
-        for path in paths.into_iter().map(Into::into) {
-            if let Some(filename) = path.file_name() {
-                let filename_str = filename.to_string_lossy().to_string();
+    /// Add a note to the index, keyed by filename and stem.
+    ///
+    /// Nothing is read from disk; aliases come from the database or the
+    /// indexer through [`FileIndex::set_aliases`] and [`FileIndex::load_aliases`].
+    pub fn insert(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        let Some(filename) = path.file_name() else {
+            return;
+        };
+        let mut keys = vec![filename.to_string_lossy().to_string()];
+        if let Some(stem) = path.file_stem() {
+            keys.push(stem.to_string_lossy().to_string());
+        }
 
-                name_to_paths
-                    .entry(filename_str.clone())
-                    .or_default()
-                    .push(path.clone());
+        for key in keys {
+            let paths = self.name_to_paths.entry(key).or_default();
+            if !paths.contains(&path) {
+                paths.push(path.clone());
+            }
+        }
+    }
+
+    /// Drop a note from the index. Separator style is ignored when matching.
+    pub fn remove(&mut self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        let Some(filename) = path.file_name() else {
+            return;
+        };
+        let target = crate::utils::normalize_path(&path.to_string_lossy());
+        let mut keys = vec![filename.to_string_lossy().to_string()];
+        if let Some(stem) = path.file_stem() {
+            keys.push(stem.to_string_lossy().to_string());
+        }
 
-                if let Some(stem) = path.file_stem() {
-                    let stem_str = stem.to_string_lossy().to_string();
-                    name_to_paths.entry(stem_str).or_default().push(path.clone());
+        for key in keys {
+            if let Some(paths) = self.name_to_paths.get_mut(&key) {
+                paths.retain(|p| crate::utils::normalize_path(&p.to_string_lossy()) != target);
+                if paths.is_empty() {
+                    self.name_to_paths.remove(&key);
                 }
             }
         }
+        self.set_aliases(path, &[]);
+    }
+    
+    /// Drop every note inside the folder `dir`, with its aliases.
+    pub fn remove_under(&mut self, dir: impl AsRef<Path>) {
+        let prefix = format!(
+            "{}/",
+            crate::utils::normalize_path(&dir.as_ref().to_string_lossy()).trim_end_matches('/')
+        );
+        let mut inside: Vec<PathBuf> = self
+            .name_to_paths
+            .values()
+            .flatten()
+            .filter(|p| crate::utils::normalize_path(&p.to_string_lossy()).starts_with(&prefix))
+            .cloned()
+            .collect();
+        inside.sort();
+        inside.dedup();
+        for path in inside {
+            self.remove(path);
+        }
+    }
 
-        Self { name_to_paths }
+    /// Move a note to `to`, keeping the aliases it registered.
+    pub fn rename(&mut self, from: impl AsRef<Path>, to: impl Into<PathBuf>) {
+        let from = from.as_ref();
+        let to = to.into();
+        let (_, aliases) = self
+            .path_to_aliases
+            .get(&crate::utils::normalize_path(&from.to_string_lossy()))
+            .cloned()
+            .unwrap_or_default();
+        self.remove(from);
+        self.set_aliases(&to, &aliases);
+        self.insert(to);
+    }
+
+    /// Replace the aliases registered for `path`, e.g. after its frontmatter
+    /// changed. Separator style is ignored when matching.
+    pub fn set_aliases(&mut self, path: impl AsRef<Path>, aliases: &[String]) {
+        let path = path.as_ref();
+        let target = crate::utils::normalize_path(&path.to_string_lossy());
+        let (_, old_aliases) = self.path_to_aliases.remove(&target).unwrap_or_default();
+        for alias in old_aliases {
+            let key = alias.to_lowercase();
+            if let Some(paths) = self.alias_to_paths.get_mut(&key) {
+                paths.retain(|p| crate::utils::normalize_path(&p.to_string_lossy()) != target);
+                if paths.is_empty() {
+                    self.alias_to_paths.remove(&key);
+                }
+            }
+        }
+        if aliases.is_empty() {
+            return;
+        }
+        self.path_to_aliases.insert(target, (path.to_path_buf(), aliases.to_vec()));
+        for alias in aliases {
+            let paths = self.alias_to_paths.entry(alias.to_lowercase()).or_default();
+            if !paths.iter().any(|p| p == path) {
+                paths.push(path.to_path_buf());
+            }
+        }
+    }
+
+    /// Register aliases from `(path, alias)` rows as stored in `note_aliases`,
+    /// replacing whatever each listed path had before.
+    pub fn load_aliases(&mut self, rows: impl IntoIterator<Item = (String, String)>) {
+        let mut by_path: HashMap<String, Vec<String>> = HashMap::new();
+        for (path, alias) in rows {
+            by_path.entry(path).or_default().push(alias);
+        }
+        for (path, aliases) in by_path {
+            self.set_aliases(path, &aliases);
+        }
+    }
+    
+    /// Note stems and frontmatter aliases starting with `prefix`, ignoring
+    /// case, as (name, path, is_alias) tuples sorted by name and path.
+    pub fn names_with_prefix(&self, prefix: &str) -> Vec<(String, PathBuf, bool)> {
+        let prefix = prefix.to_lowercase();
+        let stems = self.name_to_paths.iter().flat_map(|(name, paths)| {
+            paths
+                .iter()
+                .filter(|p| p.file_stem().is_some_and(|stem| stem.to_string_lossy() == name.as_str()))
+                .map(move |p| (name, p, false))
+        });
+        let aliases = self
+            .path_to_aliases
+            .values()
+            .flat_map(|(path, aliases)| aliases.iter().map(move |alias| (alias, path, true)));
+
+        let mut names: Vec<(String, PathBuf, bool)> = stems
+            .chain(aliases)
+            .filter(|(name, _, _)| name.to_lowercase().starts_with(&prefix))
+            .map(|(name, path, is_alias)| (name.clone(), path.clone(), is_alias))
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Names shared by more than one note, with their paths sorted.
+    ///
+    /// A group keyed by full filename (`Plan.md`) always has a twin keyed by
+    /// stem (`Plan`) with the same paths, so only the stem group is returned.
+    /// Groups come back ordered by name.
+    pub fn duplicate_names(&self) -> Vec<(String, Vec<PathBuf>)> {
+        let mut groups: Vec<(String, Vec<PathBuf>)> = self
+            .name_to_paths
+            .iter()
+            .filter(|(_, paths)| paths.len() > 1)
+            .filter(|(name, paths)| {
+                !paths
+                    .iter()
+                    .all(|p| p.file_name().is_some_and(|f| f.to_string_lossy() == name.as_str()))
+            })
+            .map(|(name, paths)| {
+                let mut paths = paths.clone();
+                paths.sort_by_key(|p| crate::utils::normalize_path(&p.to_string_lossy()));
+                (name.clone(), paths)
+            })
+            .collect();
+        groups.sort_by(|a, b| a.0.cmp(&b.0));
+        groups
     }
     
     /// Resolve a wikilink target to a full file path.
     /// Returns the best match based on Obsidian's resolution rules:
     /// 1. If the link contains a path (e.g., "folder/Note"), try to match that structure
     /// 2. If multiple files have the same name, prefer the shortest path (closest to root)
-    /// 3. Return None if no match is found
+    /// 3. Among equally shallow matches, take the lexicographically smallest
+    ///    vault-relative path (`/` separators), so the result never depends on walk order
+    /// 4. Fall back to notes declaring the target in frontmatter `aliases:`,
+    ///    ignoring case, chosen by the same closest-to-root rule
+    /// 5. Return None if no match is found
+    ///
+    /// A `#heading` or `#^block` anchor on the target is ignored here; the link
+    /// still resolves to the note file.
     pub fn resolve(&self, vault_path: &str, link_target: &str) -> Option<PathBuf> {
         let vault_root = Path::new(vault_path);
+        let (link_target, _) = split_anchor(link_target);
+        if link_target.is_empty() {
+            return None;
+        }
         
         // Check if this is a path-based link (contains /)
         if link_target.contains('/') {
@@ -87,19 +316,16 @@ impl FileIndex {
                 let filename_str = filename.to_string_lossy().to_string();
                 if let Some(candidates) = self.name_to_paths.get(&filename_str) {
                     // Filter candidates that end with the specified path structure
-                    let matching: Vec<_> = candidates
-                        .iter()
-                        .filter(|p| {
-                            if let Ok(rel) = p.strip_prefix(vault_root) {
-                                rel.to_string_lossy().contains(link_target)
-                            } else {
-                                false
-                            }
-                        })
-                        .collect();
+                    let matching = candidates.iter().filter(|p| {
+                        if let Ok(rel) = p.strip_prefix(vault_root) {
+                            rel.to_string_lossy().contains(link_target)
+                        } else {
+                            false
+                        }
+                    });
                     
-                    if !matching.is_empty() {
-                        return Some(matching[0].clone());
+                    if let Some(best_match) = closest_to_root(matching, vault_root) {
+                        return Some(best_match.clone());
                     }
                 }
             }
@@ -114,17 +340,75 @@ impl FileIndex {
             }
             
             // If multiple matches, prefer shortest path (closest to vault root)
-            let best_match = candidates.iter().min_by_key(|p| {
-                p.strip_prefix(vault_root)
-                    .ok()
-                    .map(|rel| rel.components().count())
-                    .unwrap_or(usize::MAX)
-            })?;
+            let best_match = closest_to_root(candidates.iter(), vault_root)?;
             
             return Some(best_match.clone());
         }
         
-        None
+        self.alias_to_paths
+            .get(&search_key.to_lowercase())
+            .and_then(|candidates| closest_to_root(candidates.iter(), vault_root))
+            .cloned()
+    }
+    
+    /// The indexed note at `target` below `base`, if any.
+    ///
+    /// Only paths in the index count, so notes excluded by `.tessellumignore`
+    /// never resolve even though they exist on disk.
+    fn note_at(&self, base: &Path, vault_root: &Path, target: &str) -> Option<PathBuf> {
+        let path = note_path_at(base, vault_root, target)?;
+        let filename = path.file_name()?.to_string_lossy().to_string();
+        let wanted = crate::utils::normalize_path(&path.to_string_lossy());
+        self.name_to_paths
+            .get(&filename)?
+            .iter()
+            .find(|p| crate::utils::normalize_path(&p.to_string_lossy()) == wanted)
+            .cloned()
+    }
+    
+    /// Resolve a link target written in the note at `source` using `strategy`.
+    ///
+    /// [`Self::resolve`] is the `ShortestPath` case, which ignores `source`.
+    pub fn resolve_with(
+        &self,
+        vault_path: &str,
+        link_target: &str,
+        source: &Path,
+        strategy: LinkStrategy,
+    ) -> Option<PathBuf> {
+        let vault_root = Path::new(vault_path);
+        let (target, _) = split_anchor(link_target);
+        if target.is_empty() {
+            return None;
+        }
+        
+        match strategy {
+            LinkStrategy::ShortestPath => self.resolve(vault_path, link_target),
+            LinkStrategy::AbsolutePath => self
+                .note_at(vault_root, vault_root, target.trim_start_matches('/'))
+                .or_else(|| {
+                    self.alias_to_paths
+                        .get(&target.to_lowercase())
+                        .and_then(|candidates| closest_to_root(candidates.iter(), vault_root))
+                        .cloned()
+                }),
+            LinkStrategy::RelativeToSource => source
+                .parent()
+                .and_then(|dir| self.note_at(dir, vault_root, target))
+                .or_else(|| self.resolve(vault_path, link_target)),
+        }
+    }
+    
+    /// [`Self::resolve_with`], falling back like [`Self::resolve_or_default`].
+    pub fn resolve_or_default_with(
+        &self,
+        vault_path: &str,
+        link_target: &str,
+        source: &Path,
+        strategy: LinkStrategy,
+    ) -> PathBuf {
+        self.resolve_with(vault_path, link_target, source, strategy)
+            .unwrap_or_else(|| default_note_path(vault_path, link_target))
     }
     
     /// Resolve a wikilink target, or generate a default path if the target doesn't exist.
@@ -132,15 +416,16 @@ impl FileIndex {
     /// If the target can be resolved to an existing file, returns that path.
     /// Otherwise, returns a fallback path: `vault_path / target.md`.
     pub fn resolve_or_default(&self, vault_path: &str, link_target: &str) -> PathBuf {
-        if let Some(resolved) = self.resolve(vault_path, link_target) {
-            return resolved;
-        }
-        
-        // Fallback: vault_path / link_target (with .md extension if not present)
-        let mut target = link_target.to_string();
-        if !target.ends_with(".md") {
-            target.push_str(".md");
-        }
-        Path::new(vault_path).join(target)
+        self.resolve(vault_path, link_target)
+            .unwrap_or_else(|| default_note_path(vault_path, link_target))
+    }
+}
+
+/// Where an unresolved link would point: `vault_path / target.md`.
+fn default_note_path(vault_path: &str, link_target: &str) -> PathBuf {
+    let mut target = split_anchor(link_target).0.to_string();
+    if !target.ends_with(".md") {
+        target.push_str(".md");
     }
+    Path::new(vault_path).join(target)
 }
@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::fs::Metadata;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
 
 /// Struct representing metadata information for a file or directory.
 ///
@@ -11,7 +14,9 @@ use serde::{Deserialize, Serialize};
 /// * `filename` - A `String` representing the name of the file or directory.
 /// * `is_dir` - A `bool` indicating whether the path is a directory (`true`) or a file (`false`).
 /// * `size` - A `u64` representing the size of the file in bytes.
-/// * `last_modified` - An `i64` representing the last modified timestamp in Unix epoch time.
+/// * `last_modified` - An `i64` representing the last modified timestamp in Unix epoch milliseconds.
+/// * `created` - An `i64` creation timestamp in the same unit as `last_modified`. Falls back
+///   to `last_modified` on platforms or filesystems that do not record creation time.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct FileMetadata {
     pub path: String,
@@ -19,4 +24,37 @@ pub struct FileMetadata {
     pub is_dir: bool,
     pub size: u64,
     pub last_modified: i64,
+    pub created: i64,
+}
+
+impl FileMetadata {
+    /// Build from filesystem metadata, with both timestamps in milliseconds.
+    pub fn from_fs(path: &Path, meta: &Metadata) -> Self {
+        let modified_time = meta
+            .modified()
+            .unwrap_or(UNIX_EPOCH)
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as i64;
+        // Not every platform or filesystem records a birth time
+        let created_time = meta
+            .created()
+            .ok()
+            .and_then(|created| created.duration_since(UNIX_EPOCH).ok())
+            .map(|created| created.as_millis() as i64)
+            .unwrap_or(modified_time);
+
+        Self {
+            path: crate::utils::normalize_path(&path.to_string_lossy()),
+            filename: path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            is_dir: meta.is_dir(),
+            size: meta.len(),
+            last_modified: modified_time,
+            created: created_time,
+        }
+    }
 }
@@ -4,8 +4,16 @@ pub struct IndexedMarkdownFile {
     pub modified: i64,
     pub size: u64,
     pub frontmatter_json: Option<String>,
+    /// Display title from frontmatter `title:` or the first `# Heading`.
+    pub title: Option<String>,
+    /// Frontmatter `aliases:`, usable as wikilink targets.
+    pub aliases: Vec<String>,
     pub inline_tags: Vec<String>,
     pub resolved_links: Vec<String>,
+    /// (target_path, frontmatter field) for links declared in frontmatter.
+    pub link_fields: Vec<(String, String)>,
+    /// Attachment paths embedded with `![[...]]`.
+    pub embeds: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
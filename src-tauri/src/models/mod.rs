@@ -1,13 +1,17 @@
 mod app_state;
 mod asset_index;
+mod file_change_event;
 mod file_index;
 mod file_metadata;
 mod indexing_record;
 mod wikilink;
 
-pub use app_state::{AppState, SearchReadinessState, SearchReadinessStatus};
+pub use app_state::{
+    AppState, ExpectedChanges, PendingIndexState, SearchReadinessState, SearchReadinessStatus,
+};
 pub use asset_index::AssetIndex;
-pub use file_index::FileIndex;
+pub use file_change_event::{FileChangeEvent, FileChangeKind, VaultFileChanges};
+pub use file_index::{FileIndex, LinkStrategy};
 pub use file_metadata::FileMetadata;
 pub use indexing_record::{IndexedMarkdownFile, IndexedSearchFile};
 pub use wikilink::WikiLink;
@@ -4,8 +4,57 @@
 ///
 /// * `target` - The link target (e.g., "Note" or "folder/Note")
 /// * `alias` - Optional display text after the pipe (e.g., "custom text" in [[Note|custom text]])
+/// * `heading` - Optional heading anchor (e.g., "Section" in [[Note#Section]])
+/// * `block_id` - Optional block reference (e.g., "abc123" in [[Note#^abc123]])
+/// * `is_embed` - Whether the link was written as an embed (`![[diagram.png]]`)
 #[derive(Debug, Clone, PartialEq)]
 pub struct WikiLink {
     pub target: String,
     pub alias: Option<String>,
+    pub heading: Option<String>,
+    pub block_id: Option<String>,
+    pub is_embed: bool,
+}
+
+impl WikiLink {
+    /// Parse the text between `[[` and `]]`.
+    ///
+    /// The alias is split off first, so `[[Note#Section|text]]` yields target
+    /// "Note", heading "Section" and alias "text". A link such as `[[#Section]]`
+    /// points into the current note and has an empty target. The result is a
+    /// plain link; callers set `is_embed` when the match was preceded by `!`.
+    pub fn parse(inner: &str) -> Self {
+        let (link, alias) = match inner.split_once('|') {
+            Some((link, alias)) => (link, Some(alias.trim().to_string())),
+            None => (inner, None),
+        };
+        
+        let (target, anchor) = split_anchor(link);
+        let (heading, block_id) = match anchor {
+            Some(anchor) => match anchor.strip_prefix('^') {
+                Some(block) => (None, Some(block.trim().to_string())),
+                None => (Some(anchor.to_string()), None),
+            },
+            None => (None, None),
+        };
+        
+        Self {
+            target: target.to_string(),
+            alias,
+            heading,
+            block_id,
+            is_embed: false,
+        }
+    }
+}
+
+/// Split a link target into the note part and the anchor after the first `#`.
+pub fn split_anchor(target: &str) -> (&str, Option<&str>) {
+    match target.split_once('#') {
+        Some((note, anchor)) => {
+            let anchor = anchor.trim();
+            (note.trim(), (!anchor.is_empty()).then_some(anchor))
+        }
+        None => (target.trim(), None),
+    }
 }
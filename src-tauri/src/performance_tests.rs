@@ -1,12 +1,10 @@
 use std::sync::{Arc, Mutex as StdMutex};
-use tokio::sync::Mutex as TokioMutex;
 use std::time::Instant;
 use tempfile::tempdir;
 
 use crate::db::Database;
 use crate::indexer::VaultIndexer;
-use crate::search::SearchIndex;
-use crate::test_support::TestVault;
+use crate::test_support::{TestSearchIndex, TestVault};
 use crate::grafeo_projection;
 
 #[tokio::test]
@@ -25,16 +23,13 @@ async fn benchmark_10k_file_indexing() {
         .await
         .expect("Database should initialize");
         
-    let search_dir = tempdir().unwrap();
-    let search_index = Arc::new(TokioMutex::new(
-        SearchIndex::open_or_create(&search_dir.path().join("search-index")).unwrap(),
-    ));
+    let search_index = TestSearchIndex::new();
 
     // Time the indexing operation
     let start = Instant::now();
     let stats = VaultIndexer::full_sync(
         &db,
-        search_index,
+        search_index.clone(),
         vault.path().to_str().unwrap(),
     )
     .await
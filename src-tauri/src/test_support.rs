@@ -1,6 +1,13 @@
 use std::fs;
+use std::ops::Deref;
 use std::path::Path;
+use std::sync::Arc;
 use tempfile::{tempdir, TempDir};
+use tokio::sync::Mutex;
+
+use crate::db::Database;
+use crate::models::AppState;
+use crate::search::SearchIndex;
 
 pub struct TestVaultBuilder {
     markdown_files: Vec<(String, String)>,
@@ -46,6 +53,63 @@ impl TestVaultBuilder {
     }
 }
 
+/// A search index in its own temp dir, removed once this is dropped.
+pub struct TestSearchIndex {
+    index: Arc<Mutex<SearchIndex>>,
+    _dir: TempDir,
+}
+
+impl TestSearchIndex {
+    pub fn new() -> Self {
+        let dir = tempdir().expect("temp search dir should be created");
+        let index = SearchIndex::open_or_create(&dir.path().join("search-index"))
+            .expect("search index should open");
+        Self {
+            index: Arc::new(Mutex::new(index)),
+            _dir: dir,
+        }
+    }
+}
+
+impl Deref for TestSearchIndex {
+    type Target = Arc<Mutex<SearchIndex>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.index
+    }
+}
+
+/// An `AppState` whose search index lives in a temp dir kept alive with it.
+pub struct TestAppState {
+    state: AppState,
+    _search_dir: TempDir,
+}
+
+impl TestAppState {
+    /// State over a fresh in-memory database.
+    pub async fn new() -> Self {
+        Self::with_db(Database::init_memory().await.expect("in-memory database should open"))
+    }
+
+    pub fn with_db(db: Database) -> Self {
+        let search_dir = tempdir().expect("temp search dir should be created");
+        let search_index = SearchIndex::open_or_create(&search_dir.path().join("search-index"))
+            .expect("search index should open");
+        Self {
+            state: AppState::new(db, search_index),
+            _search_dir: search_dir,
+        }
+    }
+}
+
+impl Deref for TestAppState {
+    type Target = AppState;
+
+    fn deref(&self) -> &Self::Target {
+        &self.state
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::TestVault;
@@ -7,9 +7,13 @@ const MILLIS_PER_DAY: u128 = 24 * 60 * 60 * 1000;
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct PurgeReport {
+	/// Top-level trash entries removed.
 	pub deleted: usize,
 	pub skipped_invalid_name: usize,
 	pub errors: usize,
+	/// Files removed, counting everything inside deleted folders.
+	pub files_removed: usize,
+	pub bytes_freed: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -267,14 +271,37 @@ pub fn purge_expired_trash(vault_path: &str, retention_days: u64) -> PurgeReport
 	purge_expired_trash_with_now(vault, retention_days, now_ms)
 }
 
+/// Permanently deletes everything in `.trash`, including entries whose names
+/// do not follow the trash naming scheme.
+pub fn purge_all_trash(vault_path: &str) -> PurgeReport {
+	purge_trash_entries(Path::new(vault_path), None)
+}
+
 fn purge_expired_trash_with_now(vault_path: &Path, retention_days: u64, now_ms: u128) -> PurgeReport {
+	purge_trash_entries(vault_path, Some((retention_days as u128 * MILLIS_PER_DAY, now_ms)))
+}
+
+/// Number of files and total bytes under `path`, without following symlinks.
+fn measure_trash_entry(path: &Path) -> (usize, u64) {
+	walkdir::WalkDir::new(path)
+		.into_iter()
+		.filter_map(|entry| entry.ok())
+		.filter(|entry| !entry.file_type().is_dir())
+		.fold((0, 0), |(files, bytes), entry| {
+			let size = entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+			(files + 1, bytes + size)
+		})
+}
+
+/// Deletes top-level trash entries. With `cutoff = Some((retention_ms, now_ms))`
+/// only entries whose parsed timestamp is older than the retention are removed.
+fn purge_trash_entries(vault_path: &Path, cutoff: Option<(u128, u128)>) -> PurgeReport {
 	let trash_dir = vault_path.join(TRASH_DIR_NAME);
 	if !trash_dir.exists() {
 		return PurgeReport::default();
 	}
 	
 	let mut report = PurgeReport::default();
-	let retention_ms = retention_days as u128 * MILLIS_PER_DAY;
 	
 	let entries = match fs::read_dir(&trash_dir) {
 		Ok(entries) => entries,
@@ -300,22 +327,25 @@ fn purge_expired_trash_with_now(vault_path: &Path, retention_days: u64, now_ms:
 		};
 		
 		let entry_path = entry.path();
-		let entry_name = entry.file_name();
-		let Some(entry_name_str) = entry_name.to_str() else {
-			report.skipped_invalid_name += 1;
-			continue;
-		};
-		
-		let Some(item_timestamp_ms) = parse_trash_timestamp(entry_name_str) else {
-			report.skipped_invalid_name += 1;
-			continue;
-		};
-		
-		let age_ms = now_ms.saturating_sub(item_timestamp_ms);
-		if age_ms <= retention_ms {
-			continue;
+		if let Some((retention_ms, now_ms)) = cutoff {
+			let entry_name = entry.file_name();
+			let Some(entry_name_str) = entry_name.to_str() else {
+				report.skipped_invalid_name += 1;
+				continue;
+			};
+			
+			let Some(item_timestamp_ms) = parse_trash_timestamp(entry_name_str) else {
+				report.skipped_invalid_name += 1;
+				continue;
+			};
+			
+			let age_ms = now_ms.saturating_sub(item_timestamp_ms);
+			if age_ms <= retention_ms {
+				continue;
+			}
 		}
 		
+		let (files, bytes) = measure_trash_entry(&entry_path);
 		let delete_result = if entry_path.is_dir() {
 			fs::remove_dir_all(&entry_path)
 		} else {
@@ -323,7 +353,11 @@ fn purge_expired_trash_with_now(vault_path: &Path, retention_days: u64, now_ms:
 		};
 		
 		match delete_result {
-			Ok(()) => report.deleted += 1,
+			Ok(()) => {
+				report.deleted += 1;
+				report.files_removed += files;
+				report.bytes_freed += bytes;
+			}
 			Err(err) => {
 				report.errors += 1;
 				log::warn!(
@@ -573,6 +607,42 @@ mod tests {
 		assert!(nested_old.exists());
 	}
 	
+	#[test]
+	fn purge_all_trash_removes_nested_entries_and_reports_sizes() {
+		let dir = tempdir().unwrap();
+		let vault = dir.path();
+		let trash = vault.join(".trash");
+		let trashed_dir = trash.join("Project (p:) 1000");
+		fs::create_dir_all(trashed_dir.join("Sub (p:) 1000")).unwrap();
+		fs::write(trashed_dir.join("Sub (p:) 1000").join("Deep (p:) 1000.md"), "12345").unwrap();
+		fs::write(trash.join("Loose (p:) 2000.md"), "abc").unwrap();
+		fs::write(trash.join("no timestamp here.md"), "xy").unwrap();
+		
+		let report = purge_all_trash(&vault.to_string_lossy());
+		
+		assert_eq!(report.deleted, 3);
+		assert_eq!(report.files_removed, 3);
+		assert_eq!(report.bytes_freed, 10);
+		assert_eq!(fs::read_dir(&trash).unwrap().count(), 0);
+	}
+	
+	#[test]
+	fn purge_expired_trash_reports_bytes_of_removed_entries_only() {
+		let dir = tempdir().unwrap();
+		let vault = dir.path();
+		let trash = vault.join(".trash");
+		fs::create_dir_all(&trash).unwrap();
+		
+		let now_ms = 2_000_000_000_000_u128;
+		fs::write(trash.join(format!("Old (p:) {}.md", now_ms - days_to_ms(10))), "1234").unwrap();
+		fs::write(trash.join(format!("New (p:) {}.md", now_ms - days_to_ms(1))), "123456").unwrap();
+		
+		let report = purge_expired_trash_with_now(vault, 7, now_ms);
+		
+		assert_eq!(report.files_removed, 1);
+		assert_eq!(report.bytes_freed, 4);
+	}
+	
 	#[test]
 	fn purge_counts_invalid_names() {
 		let dir = tempdir().unwrap();
@@ -3,6 +3,8 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::error::TessellumError;
+use crate::models::LinkStrategy;
+use crate::utils::SanitizePolicy;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DailyNotesConfig {
@@ -12,11 +14,82 @@ pub struct DailyNotesConfig {
 	pub template_name: String,
 }
 
+/// Indexing tunables for slow disks or many-core machines.
+///
+/// Valid ranges (out-of-range values are clamped when read):
+/// - `index_concurrency`: worker threads used to read and parse notes, `1..=64`.
+/// - `index_batch_size`: notes written per database transaction, `50..=5000`.
+///
+/// `link_source_extensions` lists text formats besides `.md` (e.g. `canvas`,
+/// `excalidraw`) whose `[[links]]` are indexed as graph edges. Binary formats
+/// are ignored here; they still show up as link targets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexingConfig {
+	#[serde(default = "default_index_concurrency")]
+	pub index_concurrency: usize,
+	#[serde(default = "default_index_batch_size")]
+	pub index_batch_size: usize,
+	#[serde(default)]
+	pub link_source_extensions: Vec<String>,
+}
+
+/// Where pasted and dropped attachments are saved.
+///
+/// `folder` is relative to the vault root and created on first use.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentsConfig {
+	#[serde(default = "default_attachments_folder")]
+	pub folder: String,
+}
+
+/// Note history kept in `.tessellum/history/` as saves come in.
+///
+/// While `enabled` (the default), every save is snapshotted, and a note
+/// changed outside the app is also snapshotted before a save overwrites it.
+/// At most `max_per_note` unpinned snapshots (at least 1) are kept per note.
+/// With `enabled` off, saves write no versions at all.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionsConfig {
+	#[serde(default = "default_versions_enabled")]
+	pub enabled: bool,
+	#[serde(default = "default_max_versions_per_note")]
+	pub max_per_note: usize,
+}
+
+/// Template applied to notes made with `create_note`.
+///
+/// `default_template` names a file in `.tessellum/templates/` (with or without
+/// `.md`); empty means new notes start blank.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TemplatesConfig {
+	#[serde(default)]
+	pub default_template: String,
+}
+
+/// Per-vault settings stored in `.tessellum/config.json`.
+///
+/// Every section has defaults, so a partial file only overrides what it names.
+/// `ignore_patterns` uses `.tessellumignore` syntax and is applied before the
+/// rules in that file. `link_resolution` picks how links find their notes.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[derive(Default)]
 pub struct AppConfig {
 	#[serde(default)]
 	pub daily_notes: DailyNotesConfig,
+	#[serde(default)]
+	pub indexing: IndexingConfig,
+	#[serde(default)]
+	pub attachments: AttachmentsConfig,
+	#[serde(default)]
+	pub filenames: SanitizePolicy,
+	#[serde(default)]
+	pub versions: VersionsConfig,
+	#[serde(default)]
+	pub templates: TemplatesConfig,
+	#[serde(default)]
+	pub ignore_patterns: Vec<String>,
+	#[serde(default)]
+	pub link_resolution: LinkStrategy,
 }
 
 impl Default for DailyNotesConfig {
@@ -28,6 +101,93 @@ impl Default for DailyNotesConfig {
 	}
 }
 
+impl Default for IndexingConfig {
+	fn default() -> Self {
+		Self {
+			index_concurrency: default_index_concurrency(),
+			index_batch_size: default_index_batch_size(),
+			link_source_extensions: Vec::new(),
+		}
+	}
+}
+
+impl Default for AttachmentsConfig {
+	fn default() -> Self {
+		Self {
+			folder: default_attachments_folder(),
+		}
+	}
+}
+
+impl Default for VersionsConfig {
+	fn default() -> Self {
+		Self {
+			enabled: default_versions_enabled(),
+			max_per_note: default_max_versions_per_note(),
+		}
+	}
+}
+
+impl IndexingConfig {
+	pub const CONCURRENCY_RANGE: (usize, usize) = (1, 64);
+	pub const BATCH_SIZE_RANGE: (usize, usize) = (50, 5000);
+
+	/// Effective worker count, clamped to [`Self::CONCURRENCY_RANGE`].
+	pub fn concurrency(&self) -> usize {
+		let (min, max) = Self::CONCURRENCY_RANGE;
+		self.index_concurrency.clamp(min, max)
+	}
+
+	/// Effective transaction batch size, clamped to [`Self::BATCH_SIZE_RANGE`].
+	pub fn batch_size(&self) -> usize {
+		let (min, max) = Self::BATCH_SIZE_RANGE;
+		self.index_batch_size.clamp(min, max)
+	}
+
+	/// Normalized non-markdown extensions to extract links from.
+	///
+	/// Lowercases entries, drops a leading `.`, and skips `md` and binary formats.
+	pub fn link_sources(&self) -> Vec<String> {
+		let mut extensions: Vec<String> = self
+			.link_source_extensions
+			.iter()
+			.map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+			.filter(|ext| !ext.is_empty() && ext != "md" && !BINARY_EXTENSIONS.contains(&ext.as_str()))
+			.collect();
+		extensions.sort();
+		extensions.dedup();
+		extensions
+	}
+}
+
+/// Formats that can never contain readable `[[links]]`.
+const BINARY_EXTENSIONS: &[&str] = &[
+	"png", "jpg", "jpeg", "gif", "webp", "svg", "bmp", "tif", "tiff", "avif", "pdf", "zip",
+	"mp3", "mp4", "wav", "mov", "webm", "docx", "xlsx", "pptx",
+];
+
+fn default_index_concurrency() -> usize {
+	std::thread::available_parallelism()
+		.map(|n| n.get())
+		.unwrap_or(1)
+		.min(8)
+}
+
+fn default_index_batch_size() -> usize {
+	500
+}
+
+fn default_versions_enabled() -> bool {
+	true
+}
+
+fn default_max_versions_per_note() -> usize {
+	20
+}
+
+fn default_attachments_folder() -> String {
+	"attachments".to_string()
+}
 
 fn default_daily_notes_path_template() -> String {
 	"Daily/{YYYY}/{MM}/{DD}.md".to_string()
@@ -48,17 +208,8 @@ pub fn load_or_init_config(vault_path: &str) -> Result<AppConfig, TessellumError
 	}
 	
 	if path.exists() {
-		match fs::read_to_string(&path) {
-			Ok(raw) => match serde_json::from_str::<AppConfig>(&raw) {
-				Ok(cfg) => return Ok(cfg),
-				Err(e) => {
-					log::warn!("Invalid config.json, using defaults: {}", e);
-				}
-			},
-			Err(e) => {
-				log::warn!("Failed to read config.json, using defaults: {}", e);
-			}
-		}
+		// A malformed file is left in place so the user can fix it by hand.
+		return Ok(read_config(vault_path));
 	}
 	
 	let cfg = AppConfig::default();
@@ -66,9 +217,81 @@ pub fn load_or_init_config(vault_path: &str) -> Result<AppConfig, TessellumError
 	Ok(cfg)
 }
 
+/// Read the vault config without creating anything on disk.
+///
+/// A missing, unreadable or malformed file yields the defaults.
+pub fn read_config(vault_path: &str) -> AppConfig {
+	let path = config_path(vault_path);
+	match fs::read_to_string(&path) {
+		Ok(raw) => serde_json::from_str::<AppConfig>(&raw).unwrap_or_else(|e| {
+			log::warn!("Invalid config.json, using defaults: {}", e);
+			AppConfig::default()
+		}),
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => AppConfig::default(),
+		Err(e) => {
+			log::warn!("Failed to read config.json, using defaults: {}", e);
+			AppConfig::default()
+		}
+	}
+}
+
+/// Write `config` to `.tessellum/config.json`, creating the folder if needed.
+pub fn save_config(vault_path: &str, config: &AppConfig) -> Result<(), TessellumError> {
+	let path = config_path(vault_path);
+	if let Some(parent) = path.parent() {
+		fs::create_dir_all(parent)?;
+	}
+	write_config(&path, config)
+}
+
 fn write_config(path: &Path, config: &AppConfig) -> Result<(), TessellumError> {
 	let raw = serde_json::to_string_pretty(config)
 		.map_err(|e| TessellumError::Internal(e.to_string()))?;
 	fs::write(path, raw)?;
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use std::fs;
+
+	use tempfile::tempdir;
+
+	use super::{config_path, load_or_init_config, read_config, save_config};
+
+	#[test]
+	fn saves_settings_and_falls_back_to_defaults_without_clobbering_bad_files() {
+		let dir = tempdir().unwrap();
+		let vault_path = dir.path().to_str().unwrap();
+		assert_eq!(read_config(vault_path).attachments.folder, "attachments");
+		assert!(!config_path(vault_path).exists());
+
+		let mut config = read_config(vault_path);
+		config.templates.default_template = "Meeting".to_string();
+		config.ignore_patterns = vec!["*.tmp".to_string()];
+		save_config(vault_path, &config).unwrap();
+		let loaded = load_or_init_config(vault_path).unwrap();
+		assert_eq!(loaded.templates.default_template, "Meeting");
+		assert_eq!(loaded.ignore_patterns, vec!["*.tmp"]);
+
+		fs::write(config_path(vault_path), "{ not json").unwrap();
+		assert_eq!(load_or_init_config(vault_path).unwrap().templates.default_template, "");
+		assert_eq!(fs::read_to_string(config_path(vault_path)).unwrap(), "{ not json");
+	}
+
+	#[test]
+	fn versions_stay_on_unless_turned_off() {
+		let dir = tempdir().unwrap();
+		let vault_path = dir.path().to_str().unwrap();
+		assert!(read_config(vault_path).versions.enabled);
+
+		fs::create_dir_all(config_path(vault_path).parent().unwrap()).unwrap();
+		fs::write(config_path(vault_path), r#"{ "versions": { "max_per_note": 5 } }"#).unwrap();
+		let versions = read_config(vault_path).versions;
+		assert!(versions.enabled);
+		assert_eq!(versions.max_per_note, 5);
+
+		fs::write(config_path(vault_path), r#"{ "versions": { "enabled": false } }"#).unwrap();
+		assert!(!read_config(vault_path).versions.enabled);
+	}
+}
@@ -1,8 +1,16 @@
 use serde_json::Value;
 
+use super::extract_wikilinks;
+
+/// Removes a leading UTF-8 byte order mark, as written by some Windows editors.
+pub fn strip_bom(content: &str) -> &str {
+	content.strip_prefix('\u{FEFF}').unwrap_or(content)
+}
+
 /// Extracts the raw YAML string and the body (content after frontmatter).
 /// Returns `None` if no frontmatter exists.
 pub fn parse_frontmatter(content: &str) -> Option<(String, String)> {
+	let content = strip_bom(content);
 	let frontmatter_start = if content.starts_with("---\r\n") {
 		5
 	} else if content.starts_with("---\n") {
@@ -59,8 +67,96 @@ pub fn frontmatter_to_json(yaml_str: &str) -> Result<String, String> {
 	serde_json::to_string(&json_val).map_err(|e| format!("Failed to serialize JSON: {}", e))
 }
 
+/// Extracts wikilinks declared in top-level frontmatter fields, e.g.
+/// `up: "[[Parent]]"` or `related: ["[[A]]", "[[B]]"]`.
+///
+/// Returns `(field, target)` pairs in document order. Unquoted `[[Note]]`
+/// values, which YAML reads as a nested list, are recognised too.
+pub fn extract_frontmatter_links(yaml_str: &str) -> Vec<(String, String)> {
+	let Ok(serde_yaml::Value::Mapping(mapping)) = serde_yaml::from_str::<serde_yaml::Value>(yaml_str) else {
+		return Vec::new();
+	};
+	
+	let mut links = Vec::new();
+	for (key, value) in &mapping {
+		let Some(field) = key.as_str() else {
+			continue;
+		};
+		let mut targets = Vec::new();
+		collect_value_links(value, &mut targets);
+		for target in targets {
+			if !links.iter().any(|(f, t)| f == field && *t == target) {
+				links.push((field.to_string(), target));
+			}
+		}
+	}
+	links
+}
+
+/// Reads the `aliases:` field (or `alias:`) as a list of names. Accepts a
+/// single string, a comma-separated string or a YAML list; blanks are dropped.
+pub fn extract_aliases(yaml_str: &str) -> Vec<String> {
+	let Ok(serde_yaml::Value::Mapping(mapping)) = serde_yaml::from_str::<serde_yaml::Value>(yaml_str) else {
+		return Vec::new();
+	};
+	let Some(value) = mapping.get("aliases").or_else(|| mapping.get("alias")) else {
+		return Vec::new();
+	};
+	
+	let raw: Vec<String> = match value {
+		serde_yaml::Value::String(text) => text.split(',').map(str::to_string).collect(),
+		serde_yaml::Value::Sequence(items) => items
+			.iter()
+			.filter_map(|item| match item {
+				serde_yaml::Value::String(text) => Some(text.clone()),
+				serde_yaml::Value::Number(number) => Some(number.to_string()),
+				_ => None,
+			})
+			.collect(),
+		_ => Vec::new(),
+	};
+	let mut aliases: Vec<String> = Vec::new();
+	for alias in raw {
+		let alias = alias.trim();
+		if !alias.is_empty() && !aliases.iter().any(|a| a == alias) {
+			aliases.push(alias.to_string());
+		}
+	}
+	aliases
+}
+
+fn collect_value_links(value: &serde_yaml::Value, out: &mut Vec<String>) {
+	match value {
+		serde_yaml::Value::String(text) => {
+			out.extend(
+				extract_wikilinks(text)
+					.into_iter()
+					.map(|link| link.target)
+					.filter(|target| !target.is_empty()),
+			);
+		}
+		serde_yaml::Value::Sequence(items) => {
+			// `[[Note]]` without quotes parses as a list holding a one-string list.
+			if let [serde_yaml::Value::Sequence(inner)] = items.as_slice()
+				&& let [serde_yaml::Value::String(target)] = inner.as_slice()
+			{
+				let target = target.trim();
+				if !target.is_empty() {
+					out.push(target.to_string());
+				}
+				return;
+			}
+			for item in items {
+				collect_value_links(item, out);
+			}
+		}
+		_ => {}
+	}
+}
+
 /// Returns the body content without frontmatter (for wikilink extraction, etc.).
 pub fn strip_frontmatter(content: &str) -> &str {
+	let content = strip_bom(content);
 	let frontmatter_start = if content.starts_with("---\r\n") {
 		5
 	} else if content.starts_with("---\n") {
@@ -97,9 +193,64 @@ pub fn strip_frontmatter(content: &str) -> &str {
 	after_dash
 }
 
+/// Return the text of the first level-one ATX heading (`# Title`), ignoring
+/// frontmatter and fenced code blocks.
+pub fn extract_first_h1(content: &str) -> Option<String> {
+	let body = strip_frontmatter(content);
+	let mut in_fence = false;
+	for line in body.lines() {
+		let trimmed = line.trim_start();
+		if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+			in_fence = !in_fence;
+			continue;
+		}
+		if in_fence || line.len() - trimmed.len() > 3 {
+			continue;
+		}
+		if let Some(heading) = trimmed.strip_prefix("# ") {
+			// Drop an optional closing sequence: `# Title #`
+			let heading = heading.trim().trim_end_matches('#').trim();
+			if !heading.is_empty() {
+				return Some(heading.to_string());
+			}
+		}
+	}
+	None
+}
+
+/// Display title of a note: the frontmatter `title:` string, else the first
+/// `# Heading`, else the file stem of `path`.
+pub fn note_title(content: &str, path: &str) -> String {
+	let from_frontmatter = parse_frontmatter(content).and_then(|(yaml, _)| {
+		let Ok(serde_yaml::Value::Mapping(mapping)) = serde_yaml::from_str::<serde_yaml::Value>(&yaml) else {
+			return None;
+		};
+		mapping
+			.get("title")
+			.and_then(serde_yaml::Value::as_str)
+			.map(str::trim)
+			.filter(|title| !title.is_empty())
+			.map(str::to_string)
+	});
+	from_frontmatter
+		.or_else(|| extract_first_h1(content))
+		.unwrap_or_else(|| title_from_path(path))
+}
+
+/// File stem of a note path, used when a note declares no title.
+pub fn title_from_path(path: &str) -> String {
+	std::path::Path::new(path)
+		.file_stem()
+		.map(|stem| stem.to_string_lossy().to_string())
+		.unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
-	use super::{parse_frontmatter, strip_frontmatter};
+	use super::{
+		extract_aliases, extract_frontmatter_links, note_title, parse_frontmatter, strip_bom,
+		strip_frontmatter,
+	};
 	
 	#[test]
 	fn parses_frontmatter_with_crlf_delimiters() {
@@ -124,4 +275,47 @@ mod tests {
 		assert_eq!(parsed.0, "title: Test");
 		assert_eq!(parsed.1, "Body");
 	}
-}
\ No newline at end of file
+	
+	#[test]
+	fn tolerates_leading_bom() {
+		let content = "\u{FEFF}---\ntitle: Test\n---\nBody";
+		let parsed = parse_frontmatter(content).expect("expected frontmatter to parse");
+		
+		assert_eq!(parsed.0, "title: Test");
+		assert_eq!(strip_frontmatter(content), "Body");
+		assert_eq!(strip_bom("\u{FEFF}Body"), "Body");
+		assert_eq!(strip_bom("Body"), "Body");
+	}
+	
+	#[test]
+	fn extracts_wikilinks_from_frontmatter_fields() {
+		let yaml = "title: \"[[Cover]]\"\nrelated:\n  - \"[[Alpha]]\"\n  - \"[[Beta|b]]\"\nup: [[Parent]]\ncount: 3";
+		
+		let links = extract_frontmatter_links(yaml);
+		
+		assert_eq!(
+			links,
+			vec![
+				("title".to_string(), "Cover".to_string()),
+				("related".to_string(), "Alpha".to_string()),
+				("related".to_string(), "Beta".to_string()),
+				("up".to_string(), "Parent".to_string()),
+			]
+		);
+	}
+	
+	#[test]
+	fn note_title_prefers_frontmatter_then_heading_then_stem() {
+		assert_eq!(note_title("---\ntitle: Roadmap\n---\n# Plan\n", "Vault/plan.md"), "Roadmap");
+		assert_eq!(note_title("---\ntags: [a]\n---\n# Plan\n", "Vault/plan.md"), "Plan");
+		assert_eq!(note_title("```\n# Not this\n```\nBody", "Vault/plan.md"), "plan");
+		assert_eq!(note_title("---\ntitle: \"\"\n---\nBody", "Vault/plan.md"), "plan");
+	}
+	
+	#[test]
+	fn extracts_aliases_from_lists_and_strings() {
+		assert_eq!(extract_aliases("aliases: [Auth, Authentication, Auth]"), vec!["Auth", "Authentication"]);
+		assert_eq!(extract_aliases("alias: Auth, Login"), vec!["Auth", "Login"]);
+		assert!(extract_aliases("title: Auth").is_empty());
+	}
+}
@@ -0,0 +1,191 @@
+use std::fs;
+use std::path::{Component, Path};
+
+use glob::{MatchOptions, Pattern};
+use walkdir::DirEntry;
+
+use super::is_hidden_or_special;
+
+/// Name of the optional ignore file at the vault root.
+pub const IGNORE_FILE_NAME: &str = ".tessellumignore";
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+	case_sensitive: true,
+	require_literal_separator: true,
+	require_literal_leading_dot: false,
+};
+
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+	pattern: Pattern,
+	negated: bool,
+	dir_only: bool,
+	anchored: bool,
+}
+
+/// Which vault entries the walkers skip.
+///
+/// Hidden entries (`.git`, `.trash`, `.tessellum`, ...) are always skipped.
+/// A `.tessellumignore` file at the vault root adds gitignore-style rules:
+/// one glob per line, `#` comments, `!` to re-include, a trailing `/` to match
+/// folders only, and a leading or inner `/` to anchor the glob at the vault
+/// root. Patterns from `ignore_patterns` in the vault config come first, so
+/// the file can override them. Without either only the hidden-entry default
+/// applies.
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+	rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreRules {
+	/// Read the config's `ignore_patterns` and `.tessellumignore` from the
+	/// vault root, falling back to the defaults.
+	pub fn load(vault_path: &str) -> Self {
+		let mut content = super::config::read_config(vault_path).ignore_patterns.join("\n");
+		if let Ok(file) = fs::read_to_string(Path::new(vault_path).join(IGNORE_FILE_NAME)) {
+			content.push('\n');
+			content.push_str(&file);
+		}
+		Self::parse(&content)
+	}
+
+	/// Parse ignore file contents. Invalid globs are skipped with a warning.
+	pub fn parse(content: &str) -> Self {
+		let rules = content
+			.lines()
+			.filter_map(|line| {
+				let line = line.trim();
+				if line.is_empty() || line.starts_with('#') {
+					return None;
+				}
+				let (negated, line) = match line.strip_prefix('!') {
+					Some(rest) => (true, rest),
+					None => (false, line),
+				};
+				let (dir_only, line) = match line.strip_suffix('/') {
+					Some(rest) => (true, rest),
+					None => (false, line),
+				};
+				let anchored = line.contains('/');
+				let line = line.trim_start_matches('/');
+				if line.is_empty() {
+					return None;
+				}
+				match Pattern::new(line) {
+					Ok(pattern) => Some(IgnoreRule {
+						pattern,
+						negated,
+						dir_only,
+						anchored,
+					}),
+					Err(e) => {
+						log::warn!("Skipping invalid pattern '{}' in {}: {}", line, IGNORE_FILE_NAME, e);
+						None
+					}
+				}
+			})
+			.collect();
+
+		Self { rules }
+	}
+
+	/// Whether a vault-relative path is skipped.
+	///
+	/// `is_dir` tells whether the path itself is a folder. A path inside an
+	/// ignored folder is ignored too, even if a `!` rule matches it.
+	pub fn is_ignored(&self, relative: &Path, is_dir: bool) -> bool {
+		if is_hidden_or_special(relative) {
+			return true;
+		}
+		if self.rules.is_empty() {
+			return false;
+		}
+
+		let names: Vec<String> = relative
+			.components()
+			.filter_map(|c| match c {
+				Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+				_ => None,
+			})
+			.collect();
+		(1..=names.len()).any(|depth| self.matches(&names[..depth], depth < names.len() || is_dir))
+	}
+
+	/// Whether a walked entry below `vault_root` is kept. Meant for
+	/// `WalkDir::filter_entry`, so ignored folders are not descended into.
+	pub fn keeps(&self, vault_root: impl AsRef<Path>, entry: &DirEntry) -> bool {
+		let relative = entry.path().strip_prefix(vault_root).unwrap_or(entry.path());
+		!self.is_ignored(relative, entry.file_type().is_dir())
+	}
+
+	/// Last matching rule wins, as in `.gitignore`.
+	fn matches(&self, names: &[String], is_dir: bool) -> bool {
+		let joined = names.join("/");
+		let name = names.last().map(String::as_str).unwrap_or_default();
+		let mut ignored = false;
+		for rule in &self.rules {
+			if rule.dir_only && !is_dir {
+				continue;
+			}
+			let candidate = if rule.anchored { joined.as_str() } else { name };
+			if rule.pattern.matches_with(candidate, MATCH_OPTIONS) {
+				ignored = !rule.negated;
+			}
+		}
+		ignored
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::fs;
+	use std::path::Path;
+
+	use tempfile::tempdir;
+
+	use super::{IgnoreRules, IGNORE_FILE_NAME};
+
+	#[test]
+	fn defaults_only_skip_hidden_entries() {
+		let dir = tempdir().unwrap();
+		let rules = IgnoreRules::load(dir.path().to_str().unwrap());
+
+		assert!(rules.is_ignored(Path::new(".git/config"), false));
+		assert!(rules.is_ignored(Path::new("Notes/.trash"), true));
+		assert!(!rules.is_ignored(Path::new("Notes/Entry.md"), false));
+	}
+
+	#[test]
+	fn applies_gitignore_style_rules_from_the_vault_root() {
+		let dir = tempdir().unwrap();
+		fs::write(
+			dir.path().join(IGNORE_FILE_NAME),
+			"# generated output\n*.tmp\nbuild/\n/Archive/*.md\n!Archive/Keep.md\n",
+		)
+		.unwrap();
+		let rules = IgnoreRules::load(dir.path().to_str().unwrap());
+
+		assert!(rules.is_ignored(Path::new("Notes/scratch.tmp"), false));
+		assert!(rules.is_ignored(Path::new("build"), true));
+		assert!(rules.is_ignored(Path::new("Projects/build/Out.md"), false));
+		assert!(!rules.is_ignored(Path::new("Notes/build"), false));
+		assert!(rules.is_ignored(Path::new("Archive/Old.md"), false));
+		assert!(!rules.is_ignored(Path::new("Archive/Keep.md"), false));
+		assert!(!rules.is_ignored(Path::new("Notes/Archive/Old.md"), false));
+	}
+
+	#[test]
+	fn config_patterns_apply_before_the_ignore_file() {
+		let dir = tempdir().unwrap();
+		let vault_path = dir.path().to_str().unwrap();
+		let mut config = crate::utils::config::read_config(vault_path);
+		config.ignore_patterns = vec!["Drafts/".to_string(), "*.bak".to_string()];
+		crate::utils::config::save_config(vault_path, &config).unwrap();
+		fs::write(dir.path().join(IGNORE_FILE_NAME), "!keep.bak\n").unwrap();
+		let rules = IgnoreRules::load(vault_path);
+
+		assert!(rules.is_ignored(Path::new("Drafts"), true));
+		assert!(rules.is_ignored(Path::new("old.bak"), false));
+		assert!(!rules.is_ignored(Path::new("keep.bak"), false));
+	}
+}
@@ -1,12 +1,19 @@
+mod ignore;
 mod sanitize;
 mod validate;
 pub mod frontmatter;
 pub mod config;
 mod tags;
+mod wikilinks;
 
-pub use sanitize::sanitize_string;
-pub use validate::{is_hidden_or_special, validate_path_in_vault};
-pub use tags::extract_tags;
+pub use ignore::IgnoreRules;
+pub use sanitize::{sanitize_string, SanitizePolicy};
+pub use validate::{
+	is_hidden_or_special, validate_path_in_vault, validate_target_dir, validate_vault_root,
+};
+pub use tags::{extract_tags, normalize_tag, rename_tag_in_content};
+pub use wikilinks::extract_wikilinks;
+pub(crate) use wikilinks::{code_ranges, WIKILINK_RE};
 
 /// Normalize path separators to forward slashes (for cross-platform consistency)
 pub fn normalize_path(path: &str) -> String {
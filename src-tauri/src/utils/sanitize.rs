@@ -1,25 +1,95 @@
-/// Sanitizes a given string by filtering out any characters that are not alphanumeric
-/// or one of the following allowed special characters: space (' '), hyphen ('-'),
-/// underscore ('_'), parentheses, or period.
+use serde::{Deserialize, Serialize};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Characters that break `[[wikilinks]]` and are never kept, whatever the policy.
+const WIKILINK_SYNTAX: &[char] = &['[', ']', '#', '^', '|'];
+
+/// Zero-width joiner, kept so emoji sequences like 👩‍💻 stay whole.
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+/// Characters Windows refuses in file names.
+const FORBIDDEN_ON_WINDOWS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Invisible formatting characters: soft hyphen, zero-width spaces and
+/// marks, bidi embeddings and overrides, word joiners and the BOM.
+fn is_invisible_format(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}'
+            | '\u{061C}'
+            | '\u{180E}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{206F}'
+            | '\u{FEFF}'
+            | '\u{FFF9}'..='\u{FFFB}'
+    ) && c != ZERO_WIDTH_JOINER
+}
+
+/// Which characters `sanitize_string` keeps besides its built-in set.
+///
+/// Stored as the `filenames` section of the vault config. `extra_allowed`
+/// lists additional characters to keep, e.g. `"&+"`. Characters the current
+/// platform cannot store in a file name (`/` everywhere, plus
+/// `< > : " \ | ? *` on Windows), control characters and wikilink syntax
+/// (`[ ] # ^ |`) are dropped even if listed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SanitizePolicy {
+    #[serde(default)]
+    pub extra_allowed: String,
+}
+
+impl SanitizePolicy {
+    /// Whether `c` survives sanitization under this policy.
+    ///
+    /// The built-in set is:
+    /// - letters and numbers in any script,
+    /// - combining marks (accents, vowel signs, emoji variation selectors),
+    /// - symbols and punctuation outside ASCII (emoji, `€`, `«»`, `、`),
+    ///   plus the zero-width joiner used by emoji sequences,
+    /// - space, `-`, `_`, `(`, `)` and `.`.
+    ///
+    /// Other ASCII punctuation, whitespace besides the plain space, control
+    /// characters and invisible formatting characters are dropped unless
+    /// listed in `extra_allowed`, and the platform limits always apply.
+    pub fn allows(&self, c: char) -> bool {
+        if c.is_alphanumeric()
+            || is_combining_mark(c)
+            || matches!(c, ' ' | '-' | '_' | '(' | ')' | '.')
+        {
+            return true;
+        }
+        if !c.is_ascii() && !c.is_whitespace() && !is_invisible_format(c) {
+            return is_storable(c);
+        }
+        self.extra_allowed.contains(c) && is_storable(c)
+    }
+}
+
+/// Whether a file name on this platform can hold `c` without breaking links.
+fn is_storable(c: char) -> bool {
+    if c.is_control() || is_invisible_format(c) || c == '/' || WIKILINK_SYNTAX.contains(&c) {
+        return false;
+    }
+    !(cfg!(windows) && FORBIDDEN_ON_WINDOWS.contains(&c))
+}
+
+/// Sanitizes a given string for use as a file or folder name.
+///
+/// The input is normalized to NFC first, so `e` + combining acute and a
+/// precomposed `é` give the same name. Characters outside the set described
+/// on [`SanitizePolicy::allows`] are removed. Trailing periods and spaces are
+/// trimmed, since Windows cannot store them.
 ///
 /// # Parameters
 /// - `s`: A `String` input containing the text to be sanitized.
+/// - `policy`: The vault's filename policy; `SanitizePolicy::default()` keeps only the built-in set.
 ///
 /// # Returns
 /// A new `String` containing only the allowed characters from the input.
-pub fn sanitize_string(s: String) -> String {
-    let sanitized: String = s
-        .chars()
-        .filter(|c| {
-            c.is_alphanumeric()
-                || *c == ' '
-                || *c == '-'
-                || *c == '_'
-                || *c == '('
-                || *c == ')'
-                || *c == '.'
-        })
-        .collect();
+pub fn sanitize_string(s: String, policy: &SanitizePolicy) -> String {
+    let sanitized: String = s.nfc().filter(|c| policy.allows(*c)).collect();
     sanitized
         .trim_end_matches(['.', ' '])
         .to_string()
@@ -27,26 +97,65 @@ pub fn sanitize_string(s: String) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::sanitize_string;
+    use super::{sanitize_string, SanitizePolicy};
 
     #[test]
     fn keeps_allowed_characters_and_trims_forbidden_suffixes() {
-        let sanitized = sanitize_string(" Note_Name-01 ().md.. ".to_string());
+        let sanitized = sanitize_string(" Note_Name-01 ().md.. ".to_string(), &SanitizePolicy::default());
 
         assert_eq!(sanitized, " Note_Name-01 ().md");
     }
 
     #[test]
     fn removes_disallowed_characters_but_keeps_inner_spaces() {
-        let sanitized = sanitize_string("Budget: Q2 / Draft #1".to_string());
+        let sanitized = sanitize_string("Budget: Q2 / Draft #1".to_string(), &SanitizePolicy::default());
 
         assert_eq!(sanitized, "Budget Q2  Draft 1");
     }
 
     #[test]
     fn can_sanitize_to_empty_string() {
-        let sanitized = sanitize_string("...   ".to_string());
+        let sanitized = sanitize_string("...   ".to_string(), &SanitizePolicy::default());
 
         assert_eq!(sanitized, "");
     }
+
+    #[test]
+    fn policy_keeps_extra_characters_but_never_link_syntax() {
+        let policy = SanitizePolicy {
+            extra_allowed: "&+#/".to_string(),
+        };
+
+        let sanitized = sanitize_string("R&D + Ops #2/3".to_string(), &policy);
+
+        assert_eq!(sanitized, "R&D + Ops 23");
+    }
+
+    #[test]
+    fn keeps_cjk_cyrillic_and_accented_titles() {
+        let policy = SanitizePolicy::default();
+
+        assert_eq!(sanitize_string("会议记录：第三季度".to_string(), &policy), "会议记录：第三季度");
+        assert_eq!(sanitize_string("Заметки о проекте".to_string(), &policy), "Заметки о проекте");
+        assert_eq!(
+            sanitize_string("Crème brûlée, São Paulo".to_string(), &policy),
+            "Crème brûlée São Paulo"
+        );
+    }
+
+    #[test]
+    fn normalizes_to_nfc_and_keeps_emoji_but_not_invisible_characters() {
+        let policy = SanitizePolicy::default();
+
+        let decomposed = sanitize_string("Cafe\u{301}".to_string(), &policy);
+        assert_eq!(decomposed, "Caf\u{E9}");
+        assert_eq!(
+            sanitize_string("🚀 Launch 👩\u{200D}💻".to_string(), &policy),
+            "🚀 Launch 👩\u{200D}💻"
+        );
+        assert_eq!(
+            sanitize_string("Report\u{202E}fdp.exe\u{200B}".to_string(), &policy),
+            "Reportfdp.exe"
+        );
+    }
 }
@@ -1,5 +1,11 @@
 use regex::Regex;
 use std::collections::HashSet;
+use std::sync::LazyLock;
+
+/// Inline `#tag`, `#area/health`. The `#` must start the line or follow
+/// whitespace, so URL fragments like `page#section` never match.
+static TAG_RE: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(r"(?:^|\s)#([a-zA-Z0-9_\-/]+)").unwrap());
 
 fn is_fence_line(line: &str) -> bool {
 	let trimmed = line.trim_start();
@@ -39,31 +45,35 @@ fn strip_inline_code_spans_for_tag_scan(line: &str) -> String {
 			continue;
 		}
 
-		result.push(if in_code { ' ' } else { chars[i] });
+		// Blank out code byte for byte so offsets still match `line`.
+		if in_code {
+			result.extend(std::iter::repeat_n(' ', chars[i].len_utf8()));
+		} else {
+			result.push(chars[i]);
+		}
 		i += 1;
 	}
 
 	result
 }
 
-/// Extract normalized tags from markdown content.
+/// Inline tags in `content` as (byte offset of the name after `#`, raw name).
 ///
-/// Normalization rules:
-/// - strip leading '#'
-/// - lowercase
-/// - keep '/' for hierarchical tags
-pub fn extract_tags(content: &str) -> Vec<String> {
-	let mut tags = HashSet::new();
+/// Skips frontmatter, fenced code, inline code and blockquotes, exactly like
+/// `extract_tags`. A trailing `/` is not part of the name.
+pub fn inline_tag_spans(content: &str) -> Vec<(usize, String)> {
+	let body = if crate::utils::frontmatter::parse_frontmatter(content).is_some() {
+		crate::utils::frontmatter::strip_frontmatter(content)
+	} else {
+		content
+	};
+	let mut line_start = content.len() - body.len();
 	
-	let mut body_content = content;
-	if let Some((_, _)) = crate::utils::frontmatter::parse_frontmatter(content) {
-		body_content = crate::utils::frontmatter::strip_frontmatter(content);
-	}
-	
-	// Inline tags: #tag-name
-	let tag_regex = Regex::new(r"(?:^|\s)#([a-zA-Z0-9_\-/]+)").unwrap();
+	let mut spans = Vec::new();
 	let mut in_fenced_block = false;
-	for line in body_content.lines() {
+	for line in body.split_inclusive('\n') {
+		let start = line_start;
+		line_start += line.len();
 		if is_fence_line(line) {
 			in_fenced_block = !in_fenced_block;
 			continue;
@@ -74,15 +84,32 @@ pub fn extract_tags(content: &str) -> Vec<String> {
 		}
 		let scan_line = strip_inline_code_spans_for_tag_scan(line);
 
-		for cap in tag_regex.captures_iter(&scan_line) {
+		for cap in TAG_RE.captures_iter(&scan_line) {
 			if let Some(tag_match) = cap.get(1) {
-				let normalized = normalize_tag(tag_match.as_str());
-				if !normalized.is_empty() {
-					tags.insert(normalized);
+				let name = tag_match.as_str().trim_end_matches('/');
+				let normalized = normalize_tag(name);
+				// `#42` is an issue reference, not a tag
+				if !normalized.is_empty() && !normalized.chars().all(|c| c.is_ascii_digit()) {
+					spans.push((start + tag_match.start(), name.to_string()));
 				}
 			}
 		}
 	}
+	spans
+}
+
+/// Extract normalized tags from markdown content.
+///
+/// Normalization rules:
+/// - strip leading '#'
+/// - lowercase
+/// - keep '/' for hierarchical tags
+pub fn extract_tags(content: &str) -> Vec<String> {
+	// Inline tags: #tag-name
+	let mut tags: HashSet<String> = inline_tag_spans(content)
+		.into_iter()
+		.map(|(_, name)| normalize_tag(&name))
+		.collect();
 	
 	// Frontmatter tags
 	if let Some((yaml, _)) = crate::utils::frontmatter::parse_frontmatter(content)
@@ -117,7 +144,95 @@ pub fn extract_tags(content: &str) -> Vec<String> {
 	result
 }
 
-fn normalize_tag(tag: &str) -> String {
+/// Whether normalized `tag` is `old` or nested below it (`old/...`).
+fn is_tag_or_child(tag: &str, old: &str) -> bool {
+	tag.strip_prefix(old)
+		.is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Rename tag `old` to `new` in the `tags:` field of a frontmatter block.
+///
+/// Handles inline lists (`tags: [a, b]`), comma strings and YAML item lists.
+/// Nested tags keep their suffix: `proj/web` becomes `project/web`.
+fn rename_frontmatter_tag(frontmatter: &str, old: &str, new: &str) -> String {
+	let is_delimiter = |c: char| c.is_whitespace() || matches!(c, '[' | ']' | ',' | '\'' | '"');
+	let mut out = String::with_capacity(frontmatter.len());
+	let mut in_tags = false;
+	for line in frontmatter.split_inclusive('\n') {
+		let value_start = if let Some(rest) = line.strip_prefix("tags:") {
+			in_tags = true;
+			line.len() - rest.len()
+		} else if in_tags && line.starts_with(|c: char| c.is_whitespace() || c == '-') {
+			0
+		} else {
+			in_tags = false;
+			out.push_str(line);
+			continue;
+		};
+		
+		out.push_str(&line[..value_start]);
+		let value = &line[value_start..];
+		let mut token_start = None;
+		let mut last = 0;
+		for (i, c) in value.char_indices().chain(std::iter::once((value.len(), ' '))) {
+			match (token_start, is_delimiter(c)) {
+				(None, false) => token_start = Some(i),
+				(Some(start), true) => {
+					token_start = None;
+					let token = &value[start..i];
+					let name_start = start + (token.len() - token.trim_start_matches('#').len());
+					let name = &value[name_start..i];
+					if is_tag_or_child(&name.to_lowercase(), old) {
+						// Lowercasing can change byte lengths (`K` -> `k`), so
+						// the renamed part ends at the segment boundary in `name`.
+						let depth = old.split('/').count();
+						let old_end = name.match_indices('/').nth(depth - 1).map_or(name.len(), |(j, _)| j);
+						out.push_str(&value[last..name_start]);
+						out.push_str(new);
+						out.push_str(&name[old_end..]);
+						last = i;
+					}
+				}
+				_ => {}
+			}
+		}
+		out.push_str(&value[last..]);
+	}
+	out
+}
+
+/// Rename tag `old` to `new` in note content, frontmatter and inline.
+///
+/// Both names are normalized first. Inline occurrences are found with
+/// `inline_tag_spans`, so code, blockquotes and URL fragments stay untouched.
+/// Tags nested below `old` move along with it. Returns the content unchanged
+/// when nothing matched.
+pub fn rename_tag_in_content(content: &str, old: &str, new: &str) -> String {
+	let old = normalize_tag(old);
+	let new = normalize_tag(new);
+	if old.is_empty() || old == new {
+		return content.to_string();
+	}
+	
+	let mut out = content.to_string();
+	let mut spans = inline_tag_spans(content);
+	spans.reverse();
+	for (start, name) in spans {
+		if is_tag_or_child(&normalize_tag(&name), &old) {
+			out.replace_range(start..start + old.len(), &new);
+		}
+	}
+	
+	if crate::utils::frontmatter::parse_frontmatter(&out).is_some() {
+		let body_start = out.len() - crate::utils::frontmatter::strip_frontmatter(&out).len();
+		let frontmatter = rename_frontmatter_tag(&out[..body_start], &old, &new);
+		out.replace_range(..body_start, &frontmatter);
+	}
+	out
+}
+
+/// Normalize a tag the way it is stored: no leading '#', lowercase.
+pub fn normalize_tag(tag: &str) -> String {
 	tag.trim()
 		.trim_start_matches('#')
 		.to_lowercase()
@@ -125,7 +240,7 @@ fn normalize_tag(tag: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-	use super::extract_tags;
+	use super::{extract_tags, normalize_tag, rename_tag_in_content};
 
 	#[test]
 	fn ignores_tags_in_fenced_blocks_and_blockquotes() {
@@ -162,6 +277,15 @@ body
 		assert!(tags.contains(&"beta".to_string()));
 	}
 
+	#[test]
+	fn keeps_frontmatter_tags_written_as_yaml_list() {
+		let content = "---\ntags:\n  - Project\n  - '#draft'\n---\nbody";
+		
+		let tags = extract_tags(content);
+		assert_eq!(tags, vec!["draft".to_string(), "project".to_string()]);
+		assert_eq!(normalize_tag(" #Project/Alpha "), "project/alpha");
+	}
+
 	#[test]
 	fn ignores_tags_inside_inline_code_spans() {
 		let content = r##"normal #visible `#ignored_inline`
@@ -174,5 +298,43 @@ text ``#also_ignored`` and #kept
 		assert!(!tags.contains(&"ignored_inline".to_string()));
 		assert!(!tags.contains(&"also_ignored".to_string()));
 	}
-}
 
+	#[test]
+	fn captures_nested_tags_but_not_url_fragments_or_numbers() {
+		let content = "#todo and #area/health/sleep\nSee https://example.com/page#section and [x](other.md#heading)\nFixes #42, #2024-review";
+
+		let tags = extract_tags(content);
+		assert_eq!(tags, vec!["2024-review", "area/health/sleep", "todo"]);
+	}
+
+	#[test]
+	fn renames_tags_in_frontmatter_and_text_but_not_code_or_urls() {
+		let content = "---\ntags: [proj, other]\naliases:\n  - proj\n---\n#proj and #Proj/web, not #project\n`#proj` https://x.io/a#proj\n```\n#proj\n```\n";
+		
+		let renamed = rename_tag_in_content(content, "#proj", "project");
+		assert_eq!(
+			renamed,
+			"---\ntags: [project, other]\naliases:\n  - proj\n---\n#project and #project/web, not #project\n`#proj` https://x.io/a#proj\n```\n#proj\n```\n"
+		);
+		assert_eq!(rename_tag_in_content("no tags here", "proj", "project"), "no tags here");
+		assert_eq!(
+			rename_tag_in_content("---\ntags:\n  - Proj\n  - '#proj/a'\n---\nbody", "proj", "work"),
+			"---\ntags:\n  - work\n  - '#work/a'\n---\nbody"
+		);
+	}
+
+	#[test]
+	fn renames_frontmatter_tags_whose_lowercase_changes_length() {
+		// The Kelvin sign (3 bytes) lowercases to `k` (1 byte); `İ` grows instead.
+		let content = "---\ntags: [\u{212A}, \u{212A}/sub, \u{130}x]\n---\nbody";
+		
+		assert_eq!(
+			rename_tag_in_content(content, "k", "kelvin"),
+			"---\ntags: [kelvin, kelvin/sub, \u{130}x]\n---\nbody"
+		);
+		assert_eq!(
+			rename_tag_in_content(content, &"\u{130}x".to_lowercase(), "ix"),
+			"---\ntags: [\u{212A}, \u{212A}/sub, ix]\n---\nbody"
+		);
+	}
+}
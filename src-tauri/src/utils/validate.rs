@@ -1,4 +1,20 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::error::TessellumError;
+
+/// Checks that `vault_path` names an existing directory before any path is joined onto it.
+/// Returns the same `Vault path does not exist` error as `list_files`, or
+/// `Vault path is not a directory` when it points at a file.
+pub fn validate_vault_root(vault_path: &str) -> Result<(), TessellumError> {
+	let root = Path::new(vault_path);
+	if vault_path.trim().is_empty() || !root.exists() {
+		return Err(TessellumError::NotFound("Vault path does not exist".to_string()));
+	}
+	if !root.is_dir() {
+		return Err(TessellumError::Validation("Vault path is not a directory".to_string()));
+	}
+	Ok(())
+}
 
 /// Validates that a given path resolves to a location inside the vault directory.
 /// Uses `canonicalize()` to resolve symlinks and `..` components, preventing
@@ -16,6 +32,15 @@ pub fn validate_path_in_vault(path: &str, vault_path: &str) -> Result<PathBuf, S
 	Ok(resolved)
 }
 
+/// Checks the folder a new note or folder is created in: it must be an existing
+/// directory that resolves inside `vault_root` (symlinks and `..` included).
+pub fn validate_target_dir(target_dir: &str, vault_root: &str) -> Result<(), TessellumError> {
+	validate_vault_root(target_dir)?;
+	validate_vault_root(vault_root)?;
+	validate_path_in_vault(target_dir, vault_root).map_err(TessellumError::Validation)?;
+	Ok(())
+}
+
 /// Checks if a path contains hidden or special directories/files (starting with `.`).
 /// This covers `.git`, `.trash`, `.obsidian`, etc. in a cross-platform way.
 pub fn is_hidden_or_special(path: &std::path::Path) -> bool {
@@ -29,7 +54,7 @@ mod tests {
 
 	use tempfile::tempdir;
 
-	use super::{is_hidden_or_special, validate_path_in_vault};
+	use super::{is_hidden_or_special, validate_path_in_vault, validate_target_dir, validate_vault_root};
 
 	#[test]
 	fn validates_paths_that_resolve_inside_the_vault() {
@@ -64,10 +89,48 @@ mod tests {
 		assert!(err.contains("outside the vault"));
 	}
 
+	#[test]
+	fn rejects_missing_empty_and_file_vault_roots() {
+		let vault = tempdir().unwrap();
+		let file = vault.path().join("file.md");
+		fs::write(&file, "# File").unwrap();
+
+		assert!(validate_vault_root(vault.path().to_str().unwrap()).is_ok());
+		let missing = validate_vault_root(vault.path().join("missing").to_str().unwrap()).unwrap_err();
+		assert!(missing.to_string().contains("Vault path does not exist"));
+		let empty = validate_vault_root("").unwrap_err();
+		assert!(empty.to_string().contains("Vault path does not exist"));
+		let not_dir = validate_vault_root(file.to_str().unwrap()).unwrap_err();
+		assert!(not_dir.to_string().contains("Vault path is not a directory"));
+	}
+
+	#[test]
+	fn target_dirs_must_stay_inside_the_vault_root() {
+		let outer = tempdir().unwrap();
+		let vault = outer.path().join("Vault");
+		fs::create_dir_all(vault.join("Notes")).unwrap();
+		fs::create_dir_all(outer.path().join("Outside")).unwrap();
+		let root = vault.to_str().unwrap();
+
+		assert!(validate_target_dir(vault.join("Notes").to_str().unwrap(), root).is_ok());
+		assert!(validate_target_dir(root, root).is_ok());
+		let escaped = validate_target_dir(vault.join("../Outside").to_str().unwrap(), root).unwrap_err();
+		assert!(escaped.to_string().contains("outside the vault"));
+		let missing_root = validate_target_dir(vault.join("Notes").to_str().unwrap(), "").unwrap_err();
+		assert!(missing_root.to_string().contains("Vault path does not exist"));
+	}
+
 	#[test]
 	fn detects_hidden_and_special_path_components() {
 		assert!(is_hidden_or_special(std::path::Path::new(".git/config")));
 		assert!(is_hidden_or_special(std::path::Path::new("Notes/.trash/Entry.md")));
 		assert!(!is_hidden_or_special(std::path::Path::new("Notes/Entry.md")));
 	}
+
+	#[test]
+	fn names_merely_containing_git_or_trash_are_not_hidden() {
+		assert!(!is_hidden_or_special(std::path::Path::new("my.github-notes.md")));
+		assert!(!is_hidden_or_special(std::path::Path::new("project.trashcan/Plan.md")));
+		assert!(!is_hidden_or_special(std::path::Path::new("Notes/site.git.md")));
+	}
 }
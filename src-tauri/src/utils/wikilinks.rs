@@ -0,0 +1,46 @@
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use pulldown_cmark::{Event, Parser, Tag};
+use regex::Regex;
+
+use crate::models::WikiLink;
+
+pub(crate) static WIKILINK_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(!)?(\\)?\[\[(.*?)\]\]").unwrap());
+
+/// Byte ranges of fenced or indented code blocks and `inline code` spans.
+pub(crate) fn code_ranges(content: &str) -> Vec<Range<usize>> {
+	Parser::new(content)
+		.into_offset_iter()
+		.filter(|(event, _)| matches!(event, Event::Code(_) | Event::Start(Tag::CodeBlock(_))))
+		.map(|(_, range)| range)
+		.collect()
+}
+
+/// Extracts all wikilinks from the given input string.
+///
+/// Wikilinks are denoted by the pattern `[[...]]`, where "..." represents
+/// the content of the link. This function uses a statically compiled regex
+/// to find all occurrences and extracts their inner content, including any
+/// `#heading` or `#^block` anchor. A leading `!` marks the link as an embed.
+/// Links inside code blocks or `inline code` are sample text, not links,
+/// and are skipped.
+pub fn extract_wikilinks(content: &str) -> Vec<WikiLink> {
+	// Only parse the markdown when there is something to filter.
+	let code = if content.contains("[[") { code_ranges(content) } else { Vec::new() };
+	WIKILINK_RE
+		.captures_iter(content)
+		.filter_map(|c| {
+			let start = c.get(0).map_or(0, |m| m.start());
+			// If there is a backslash before `[[`, this was an escaped literal
+			if c.get(2).is_some() || code.iter().any(|range| range.contains(&start)) {
+				None
+			} else {
+				Some(WikiLink {
+					is_embed: c.get(1).is_some(),
+					..WikiLink::parse(&c[3])
+				})
+			}
+		})
+		.collect()
+}
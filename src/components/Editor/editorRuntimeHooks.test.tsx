@@ -168,6 +168,7 @@ describe("editor runtime hooks", () => {
         expect(invokeMock).toHaveBeenCalledWith("create_folder", {
             vaultPath: "vault/projects",
             folderName: "New Folder",
+            vaultRoot: "vault",
         });
         expect(useVaultStore.getState().files.some((file) => file.path === "vault/projects/New Folder")).toBe(true);
         expect(useUiStore.getState().expandedFolders["vault/projects"]).toBe(true);
@@ -14,7 +14,8 @@ export function useCreateFolder() {
             // 1. Backend Operation
             const newPath = await invoke<string>('create_folder', {
                 vaultPath: targetDir,
-                folderName: name
+                folderName: name,
+                vaultRoot: vaultPath
             });
 
             // 2. UI Updates (Side Effects)
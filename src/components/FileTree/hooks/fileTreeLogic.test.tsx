@@ -373,6 +373,35 @@ describe("file tree logic", () => {
             destDir: "vault/folder",
         });
         expect(fileTreeMocks.toastSuccess).toHaveBeenCalledWith("Moved successfully");
+
+        invokeMock.mockResolvedValueOnce([
+            {
+                source: "vault/a.md",
+                destination: null,
+                error: "A file or folder with that name already exists in the destination",
+            },
+        ]);
+        act(() => {
+            result.current.onDragStartIntent({
+                button: 0,
+                shiftKey: false,
+                metaKey: false,
+                ctrlKey: false,
+                altKey: false,
+                clientX: 0,
+                clientY: 0,
+            } as never, fileNode, false);
+        });
+        act(() => {
+            window.dispatchEvent(new MouseEvent("mousemove", { clientX: 10, clientY: 50 }));
+        });
+        await act(async () => {
+            window.dispatchEvent(new MouseEvent("mouseup", { clientX: 10, clientY: 50 }));
+        });
+
+        expect(fileTreeMocks.toastError).toHaveBeenCalledWith("Failed to move items", {
+            description: "a.md: A file or folder with that name already exists in the destination",
+        });
     });
 
     test("maps the file-tree composition hook to the expected folder, rename, and context fields", async () => {
@@ -1,9 +1,10 @@
 import { useCallback, useMemo, useRef, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { toast } from "sonner";
-import { TreeNode } from '../../../types.ts';
+import { MoveItemResult, TreeNode } from '../../../types.ts';
 import { DropPosition } from '../FileNode.tsx';
 import { getParentPath } from '../../../utils/pathUtils.ts';
+import { getFilenameFromPath } from '../../../utils/noteUtils.ts';
 import { useSelectionStore, useVaultStore } from "../../../stores";
 
 export type DragOverState = {
@@ -214,8 +215,20 @@ export function useFileTreeDrag(data: TreeNode[]) {
             }
 
             try {
-                await invoke('move_items', { vaultPath, itemPaths: moveCandidates, destDir });
-                toast.success("Moved successfully");
+                const results = await invoke<MoveItemResult[] | null>('move_items', { vaultPath, itemPaths: moveCandidates, destDir });
+                const failures = (results ?? []).filter((result) => result.error);
+                if (failures.length === 0) {
+                    toast.success("Moved successfully");
+                } else {
+                    const moved = moveCandidates.length - failures.length;
+                    const details = failures
+                        .map((result) => `${getFilenameFromPath(result.source) ?? result.source}: ${result.error}`)
+                        .join("\n");
+                    toast.error(
+                        moved > 0 ? `Moved ${moved} of ${moveCandidates.length} items` : "Failed to move items",
+                        { description: details },
+                    );
+                }
             } catch (err: unknown) {
                 console.error(err);
                 toast.error(typeof err === 'string' ? err : "Failed to move items");
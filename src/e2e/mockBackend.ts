@@ -415,6 +415,9 @@ export async function invokeMock<T>(command: string, payload?: Record<string, un
             return undefined as T;
         }
         case "create_note": {
+            if (!payload?.vaultRoot) {
+                throw new Error("invalid args `vaultRoot` for command `create_note`: missing required key vaultRoot");
+            }
             const targetDir = normalizePath(String(payload?.vaultPath ?? ""));
             const title = String(payload?.title ?? "Untitled");
             const normalizedDir = targetDir.endsWith(".md")
@@ -428,7 +431,7 @@ export async function invokeMock<T>(command: string, payload?: Record<string, un
                 lastModified: Date.now(),
             });
             emitEvent("file-changed");
-            return path as T;
+            return { path, sanitized_title: title, was_modified: false } as T;
         }
         case "create_note_from_template": {
             const targetDir = normalizePath(String(payload?.targetDir ?? payload?.vaultPath ?? ""));
@@ -506,10 +509,39 @@ export async function invokeMock<T>(command: string, payload?: Record<string, un
             const file = vault.files.get(path);
             return (file ? parseTags(file.content) : []) as T;
         }
+        case "move_items": {
+            const destDir = normalizePath(String(payload?.destDir ?? ""));
+            const itemPaths = (payload?.itemPaths as string[] | undefined) ?? [];
+            const results = itemPaths.map((item) => {
+                const source = normalizePath(item);
+                const name = source.split("/").pop() ?? source;
+                const destination = destDir ? `${destDir}/${name}` : name;
+                const moved = [...vault.files.values()].filter(
+                    (file) => file.path === source || file.path.startsWith(`${source}/`),
+                );
+                if (moved.length === 0) {
+                    return { source: item, destination: null, error: "Source not found" };
+                }
+                if (destination !== source && vault.files.has(destination)) {
+                    return {
+                        source: item,
+                        destination: null,
+                        error: "A file or folder with that name already exists in the destination",
+                    };
+                }
+                moved.forEach((file) => {
+                    vault.files.delete(file.path);
+                    const path = destination + file.path.slice(source.length);
+                    vault.files.set(path, { ...file, path });
+                });
+                return { source: item, destination, error: null };
+            });
+            emitEvent("file-changed");
+            return results as T;
+        }
         case "sync_vault":
         case "watch_vault":
         case "unwatch_vault":
-        case "move_items":
             return undefined as T;
         default:
             console.warn(`[e2e-mock] Unhandled invoke: ${command}`);
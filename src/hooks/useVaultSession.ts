@@ -150,7 +150,8 @@ export function useVaultSession(app: TessellumApp): { workspaceRestored: boolean
         }
 
         return () => {
-            invoke("unwatch_vault").catch(() => {
+            if (!vaultPath) return;
+            invoke("unwatch_vault", { vaultPath }).catch(() => {
                 // Ignore teardown errors during dev reload/unmount.
             });
         };
@@ -171,8 +172,10 @@ export function useVaultSession(app: TessellumApp): { workspaceRestored: boolean
     // Refresh files (and debounce an index sync) whenever the watcher reports a change.
     useEffect(() => {
         let syncTimer: number | null = null;
-        const unlistenPromise = listen("file-changed", () => {
+        const unlistenPromise = listen<{ vault_path?: string }>("file-changed", (event) => {
             if (!vaultPath) return;
+            // Other watched vaults report through the same event.
+            if (event.payload?.vault_path && event.payload.vault_path !== vaultPath) return;
             refreshFiles(vaultPath, false);
             if (syncTimer) window.clearTimeout(syncTimer);
             syncTimer = window.setTimeout(() => {
@@ -3,7 +3,20 @@ export interface FileMetadata {
     filename: string,
     is_dir: boolean,
     size: number,
-    last_modified: number
+    last_modified: number,
+    created?: number
+}
+
+export interface CreateNoteResult {
+    path: string,
+    sanitized_title: string,
+    was_modified: boolean
+}
+
+export interface MoveItemResult {
+    source: string,
+    destination: string | null,
+    error: string | null
 }
 
 export interface TreeNode {
@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { FileMetadata } from "../types";
+import type { CreateNoteResult, FileMetadata } from "../types";
+import { useVaultStore } from "../stores/vaultStore";
 
 export function getFilenameFromPath(path: string): string | null {
     if (!path) return null;
@@ -21,12 +22,17 @@ export function buildNoteMetadata(path: string, fallbackFilename: string): FileM
 }
 
 export async function createNoteInDir(targetDir: string, title: string): Promise<FileMetadata> {
-    const newPath = await invoke<string>("create_note", {
+    const vaultRoot = useVaultStore.getState().vaultPath;
+    if (!vaultRoot) {
+        throw new Error("No vault is open");
+    }
+    const result = await invoke<CreateNoteResult>("create_note", {
         vaultPath: targetDir,
         title,
+        vaultRoot,
     });
-    const fallbackFilename = `${title}.md`;
-    return buildNoteMetadata(newPath, fallbackFilename);
+    const fallbackFilename = `${result.sanitized_title}.md`;
+    return buildNoteMetadata(result.path, fallbackFilename);
 }
 
 export async function createNoteFromTemplateInDir(
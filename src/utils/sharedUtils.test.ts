@@ -1,5 +1,6 @@
 import { describe, expect, test } from "vitest";
 import { invokeMock } from "../test/tauriMocks";
+import { useVaultStore } from "../stores/vaultStore";
 import { ensureMarkdownExtension, getNameWithoutExtension, getParentFromTarget, getParentPath } from "./pathUtils";
 import { buildNoteMetadata, createNoteFromTemplateInDir, createNoteInDir, getFilenameFromPath } from "./noteUtils";
 import { getFileExtension, isImageFile, isMediaFile, isPdfFile } from "./fileType";
@@ -43,11 +44,21 @@ describe("shared utilities", () => {
         expect(metadata.filename).toBe("Note.md");
         expect(metadata.is_dir).toBe(false);
 
-        invokeMock.mockResolvedValueOnce("Inbox/New.md");
-        await expect(createNoteInDir("Inbox", "New")).resolves.toMatchObject({
-            path: "Inbox/New.md",
+        useVaultStore.setState({ vaultPath: null });
+        await expect(createNoteInDir("Inbox", "New")).rejects.toThrow("No vault is open");
+        expect(invokeMock).not.toHaveBeenCalledWith("create_note", expect.anything());
+
+        useVaultStore.setState({ vaultPath: "Vault" });
+        invokeMock.mockResolvedValueOnce({ path: "Vault/Inbox/New.md", sanitized_title: "New", was_modified: false });
+        await expect(createNoteInDir("Vault/Inbox", "New")).resolves.toMatchObject({
+            path: "Vault/Inbox/New.md",
             filename: "New.md",
         });
+        expect(invokeMock).toHaveBeenCalledWith("create_note", {
+            vaultPath: "Vault/Inbox",
+            title: "New",
+            vaultRoot: "Vault",
+        });
 
         invokeMock.mockResolvedValueOnce("Inbox/Template.md");
         await expect(